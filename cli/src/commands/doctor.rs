@@ -0,0 +1,152 @@
+use clap::Parser;
+use rensa_core::osv::DEFAULT_OSV_API;
+use rensa_core::{Config, HttpClient};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+const PING_TIMEOUT: Duration = Duration::from_secs(10);
+
+#[derive(Parser)]
+pub struct Doctor {
+    #[arg(value_name = "PATH", default_value = ".", help = "Directory to look for a config file in")]
+    path: PathBuf,
+}
+
+/// The outcome of one check: a detail line on success, the reason on failure.
+struct Diagnostic {
+    name: String,
+    outcome: Result<String, String>,
+}
+
+impl Diagnostic {
+    fn print(&self) {
+        match &self.outcome {
+            Ok(detail) => println!("  [PASS] {}: {}", self.name, detail),
+            Err(reason) => println!("  [FAIL] {}: {}", self.name, reason),
+        }
+    }
+}
+
+impl Doctor {
+    /// Runs every check and returns the process exit code.
+    pub async fn run(&self) -> anyhow::Result<i32> {
+        println!("Checking rensa's environment:");
+
+        let (config_check, config) = check_config(&self.path);
+        let osv_url = config.as_ref().and_then(|c| c.osv_url.clone());
+        let cache_dir = config
+            .and_then(|c| c.cache_dir)
+            .or_else(|| std::env::var_os("RENSA_CACHE_DIR").map(PathBuf::from));
+        let mut checks = vec![config_check, check_cache_dir(cache_dir.as_deref())];
+
+        let client = HttpClient::new().with_timeout(PING_TIMEOUT);
+        checks.push(check_endpoint(&client, "OSV", osv_url.as_deref().unwrap_or(DEFAULT_OSV_API)).await);
+
+        let registry = crate::scan::build_registry(&Default::default());
+        for registry_client in registry.registry_clients() {
+            if let Some(url) = registry_client.endpoint() {
+                let name = format!("{} registry", registry_client.ecosystem());
+                checks.push(check_endpoint(&client, &name, url).await);
+            }
+        }
+
+        for check in &checks {
+            check.print();
+        }
+
+        let failed = checks.iter().filter(|c| c.outcome.is_err()).count();
+        if failed > 0 {
+            println!("{} check(s) failed.", failed);
+            return Ok(1);
+        }
+        println!("All checks passed.");
+        Ok(0)
+    }
+}
+
+/// Loads the config in `dir`, if there is one.
+fn check_config(dir: &Path) -> (Diagnostic, Option<Config>) {
+    let (outcome, config) = match Config::discover(dir) {
+        Ok(Some(config)) => (Ok("valid".to_string()), Some(config)),
+        Ok(None) => (Ok(format!("no config file in {}, using defaults", dir.display())), None),
+        Err(e) => (Err(e.to_string()), None),
+    };
+    let check = Diagnostic {
+        name: "Config".to_string(),
+        outcome,
+    };
+    (check, config)
+}
+
+/// Creates the cache directory if needed and writes a probe file to it.
+fn check_cache_dir(dir: Option<&Path>) -> Diagnostic {
+    let outcome = match dir {
+        None => Ok("not configured, responses are not cached".to_string()),
+        Some(dir) => {
+            let probe = dir.join(".rensa-doctor");
+            std::fs::create_dir_all(dir)
+                .and_then(|_| std::fs::write(&probe, b"ok"))
+                .and_then(|_| std::fs::remove_file(&probe))
+                .map(|_| format!("{} is writable", dir.display()))
+                .map_err(|e| format!("{} is not writable: {}", dir.display(), e))
+        }
+    };
+    Diagnostic {
+        name: "Cache directory".to_string(),
+        outcome,
+    }
+}
+
+async fn check_endpoint(client: &HttpClient, name: &str, url: &str) -> Diagnostic {
+    Diagnostic {
+        name: name.to_string(),
+        outcome: client
+            .ping(url)
+            .await
+            .map(|_| format!("{} is reachable", url))
+            .map_err(|e| e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_cache_dir_is_created_and_writable() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = temp_dir.path().join("cache");
+
+        assert!(check_cache_dir(Some(&cache)).outcome.is_ok());
+        assert!(cache.is_dir());
+        assert_eq!(std::fs::read_dir(&cache).unwrap().count(), 0);
+        assert!(check_cache_dir(None).outcome.is_ok());
+    }
+
+    #[test]
+    fn test_cache_dir_under_a_file_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = temp_dir.path().join("not-a-dir");
+        std::fs::write(&file, "").unwrap();
+
+        assert!(check_cache_dir(Some(&file.join("cache"))).outcome.is_err());
+    }
+
+    #[test]
+    fn test_config_checks() {
+        let temp_dir = TempDir::new().unwrap();
+        let (check, config) = check_config(temp_dir.path());
+        assert!(check.outcome.is_ok());
+        assert!(config.is_none());
+
+        std::fs::write(temp_dir.path().join("rensa.yaml"), "version: 2\ncache_dir: /tmp/rensa\n").unwrap();
+        let (check, config) = check_config(temp_dir.path());
+        assert!(check.outcome.is_ok());
+        assert_eq!(config.unwrap().cache_dir, Some(PathBuf::from("/tmp/rensa")));
+
+        std::fs::write(temp_dir.path().join("rensa.yaml"), "version: 2\nregistry_timeout: 0\n").unwrap();
+        let (check, _) = check_config(temp_dir.path());
+        assert!(check.outcome.is_err());
+    }
+}
@@ -0,0 +1,132 @@
+use clap::Parser;
+use rensa_core::config::CONFIG_FILE_NAMES;
+use rensa_core::Ecosystem;
+use std::path::{Path, PathBuf};
+
+#[derive(Parser)]
+pub struct Init {
+    #[arg(value_name = "PATH", default_value = ".", help = "Directory to write rensa.yaml to")]
+    path: PathBuf,
+    #[arg(long, help = "Overwrite an existing config file")]
+    force: bool,
+}
+
+impl Init {
+    pub async fn run(&self) -> anyhow::Result<()> {
+        let file = self.path.join(CONFIG_FILE_NAMES[0]);
+        if !self.force {
+            if let Some(existing) = CONFIG_FILE_NAMES.iter().map(|n| self.path.join(n)).find(|p| p.is_file()) {
+                anyhow::bail!("{} already exists, pass --force to overwrite it", existing.display());
+            }
+        }
+
+        let ecosystems = discover_ecosystems(&self.path).await?;
+        std::fs::write(&file, render(&ecosystems))
+            .map_err(|e| anyhow::anyhow!("Failed to write {}: {}", file.display(), e))?;
+
+        println!("Wrote {}", file.display());
+        for (ecosystem, directory) in &ecosystems {
+            println!("  - {} in {}", ecosystem, directory);
+        }
+        Ok(())
+    }
+}
+
+/// Ecosystems with a manifest under `root`, each with the directory it was
+/// found in relative to `root` (`/` for `root` itself).
+async fn discover_ecosystems(root: &Path) -> anyhow::Result<Vec<(Ecosystem, String)>> {
+    let registry = crate::scan::build_registry(&Default::default());
+    let mut found = Vec::new();
+
+    for detector in registry.detectors() {
+        for file in detector.detect(root).await? {
+            let dir = file.path.parent().unwrap_or(root);
+            let relative = dir.strip_prefix(root).unwrap_or(dir);
+            let directory = format!("/{}", relative.to_string_lossy().trim_start_matches('/'));
+            if !found.contains(&(file.ecosystem, directory.clone())) {
+                found.push((file.ecosystem, directory));
+            }
+        }
+    }
+
+    found.sort();
+    Ok(found)
+}
+
+/// A commented default config listing `ecosystems`.
+fn render(ecosystems: &[(Ecosystem, String)]) -> String {
+    let mut yaml = String::from(
+        "# Rensa configuration. Command line flags take precedence over these settings.\n\
+         version: 2\n\
+         \n\
+         # Manifests found when this file was generated. Set `enabled: false` to\n\
+         # skip an ecosystem.\n",
+    );
+
+    if ecosystems.is_empty() {
+        yaml.push_str("ecosystems: []\n");
+    } else {
+        yaml.push_str("ecosystems:\n");
+        for (ecosystem, directory) in ecosystems {
+            yaml.push_str(&format!(
+                "  - ecosystem: {}\n    directory: \"{}\"\n",
+                ecosystem, directory
+            ));
+        }
+    }
+
+    yaml.push_str(
+        "\n\
+         # Lowest severity marked as failing in JUnit reports: low, medium, high\n\
+         # or critical. `rensa check --fail-on` overrides it and sets the exit code.\n\
+         severity_threshold: medium\n\
+         \n\
+         # Advisory ids left out of reports, e.g. GHSA-xxxx-xxxx-xxxx.\n\
+         ignore: []\n\
+         \n\
+         # Registry responses and scan results are cached here. Remove this line\n\
+         # to disable caching.\n\
+         cache_dir: .rensa-cache\n",
+    );
+    yaml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rensa_core::{Config, SeverityThreshold};
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_generated_config_loads_cleanly() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("composer.json"), r#"{"require": {}}"#).unwrap();
+        std::fs::create_dir(temp_dir.path().join("api")).unwrap();
+        std::fs::write(temp_dir.path().join("api/composer.json"), r#"{"require": {}}"#).unwrap();
+
+        let ecosystems = discover_ecosystems(temp_dir.path()).await.unwrap();
+        let config = Config::from_yaml(&render(&ecosystems), Path::new("rensa.yaml")).unwrap();
+
+        let found: Vec<(&str, &str)> =
+            config.ecosystems.iter().map(|e| (e.ecosystem.as_str(), e.directory.as_str())).collect();
+        assert_eq!(found, vec![("composer", "/"), ("composer", "/api")]);
+        assert_eq!(config.severity_threshold, SeverityThreshold::Medium);
+        assert!(config.ignore.is_empty());
+        assert_eq!(config.cache_dir, Some(PathBuf::from(".rensa-cache")));
+
+        assert!(Config::from_yaml(&render(&[]), Path::new("rensa.yaml")).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_existing_config_needs_force() {
+        let temp_dir = TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join(".rensa.yml"), "version: 2\n").unwrap();
+        let path = temp_dir.path().to_str().unwrap();
+
+        assert!(Init::parse_from(["init", path]).run().await.is_err());
+        assert!(!temp_dir.path().join("rensa.yaml").exists());
+
+        Init::parse_from(["init", path, "--force"]).run().await.unwrap();
+        assert!(Config::load(&temp_dir.path().join("rensa.yaml")).is_ok());
+    }
+}
@@ -1,32 +1,420 @@
+mod doctor;
+mod init;
+
+pub use doctor::Doctor;
+pub use init::Init;
+
 use clap::Parser;
-use std::path::PathBuf;
+use rensa_core::webhook::WebhookFormat;
+use rensa_core::config::{resolve_scan_paths, UnresolvedPolicy};
+use rensa_core::report::ReportDiff;
+use rensa_core::filter::DependencyFilter;
+use rensa_core::{Config, HttpClient, ScanReport, Severity, SeverityThreshold};
+use std::collections::HashMap;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Parser)]
 pub struct Check {
-    #[arg(short, long, help = "Path to scan")]
-    path: Option<PathBuf>,
-    #[arg(short, long, help = "Output in JSON format")]
+    #[arg(value_name = "PATH", help = "Paths or glob patterns to scan (default: .)")]
+    paths: Vec<String>,
+    #[arg(short, long, help = "Path to scan (may be repeated)")]
+    path: Vec<String>,
+    #[arg(short, long, help = "Output in JSON format (same as --format json)", conflicts_with = "format")]
     json: bool,
+    #[arg(long, help = "Output single-line JSON (same as --format json-compact)", conflicts_with_all = ["format", "json"])]
+    json_compact: bool,
+    #[arg(long, value_enum, default_value = "text", help = "Output format")]
+    format: super::display::OutputFormat,
+    #[arg(long, help = "Scan a Syft JSON SBOM for vulnerabilities instead of a path", conflicts_with_all = ["path", "paths"])]
+    sbom: Option<PathBuf>,
+    #[arg(long, value_name = "FILE", help = "Scan the output of `composer show --format=json` instead of a path", conflicts_with_all = ["path", "paths", "sbom"])]
+    composer_show: Option<PathBuf>,
+    #[arg(long, value_name = "URL", conflicts_with_all = ["path", "paths", "sbom", "composer_show", "git"], help = "Fetch and scan a raw composer.json from URL")]
+    url: Option<String>,
+    #[arg(long, value_name = "REPO", conflicts_with_all = ["path", "paths", "sbom", "composer_show"], help = "Shallow-clone a git repository and scan it")]
+    git: Option<String>,
+    #[arg(long = "ref", value_name = "REF", requires = "git", help = "Branch or tag to clone with --git (default: the default branch)")]
+    git_ref: Option<String>,
+    #[arg(long, value_name = "N", help = "Only check the first N dependencies (sorted by name)")]
+    limit: Option<usize>,
+    #[arg(long, value_name = "SEVERITY", help = "Exit non-zero if a vulnerability at or above this severity is found (low, medium, high, critical)")]
+    fail_on: Option<SeverityThreshold>,
+    #[arg(long, requires = "fail_on", help = "Stop scanning as soon as a finding meets --fail-on")]
+    fail_fast: bool,
+    #[arg(short, long, help = "List every dependency with its source repository")]
+    verbose: bool,
+    #[arg(long, value_name = "N", default_value_t = super::display::DEFAULT_MAX_REFERENCES, help = "Reference URLs to show per vulnerability in text output; JSON keeps them all")]
+    max_references: usize,
+    #[arg(long, visible_alias = "trim", help = "Only list dependencies with an update or vulnerability; summary counts still cover every dependency")]
+    actionable_only: bool,
+    #[arg(long, value_name = "URL", help = "POST the results to this URL after the scan")]
+    webhook: Option<String>,
+    #[arg(long, value_name = "FORMAT", default_value = "summary", help = "Webhook payload: summary or full")]
+    webhook_format: WebhookFormat,
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..), help = "Timeout for package registry requests")]
+    registry_timeout: Option<u64>,
+    #[arg(long, value_name = "SECONDS", value_parser = clap::value_parser!(u64).range(1..), help = "Timeout for OSV vulnerability queries")]
+    osv_timeout: Option<u64>,
+    #[arg(long, value_name = "URL", help = "Query this OSV-compatible API instead of https://api.osv.dev, e.g. a self-hosted mirror")]
+    osv_url: Option<String>,
+    #[arg(long, help = "Also report advisories OSV lists as related to the ones found")]
+    include_related: bool,
+    #[arg(long, value_name = "SEVERITY", help = "Ignore advisories rated below this severity (low, medium, high, critical); unrated ones are kept")]
+    min_severity: Option<SeverityThreshold>,
+    #[arg(long, value_name = "N", default_value_t = 0, help = "Also scan locked transitive dependencies up to N levels below the direct ones (composer.lock)")]
+    transitive_depth: usize,
+    #[arg(long, help = "Warn when PHP or other tracked releases are past end-of-life")]
+    eol: bool,
+    #[arg(long, visible_alias = "warnings-as-errors", help = "Exit non-zero if the scan produced any warnings")]
+    strict: bool,
+    #[arg(long, conflicts_with_all = ["sbom", "composer_show"], help = "Only scan manifests changed since --base (git diff <base>...HEAD)")]
+    changed_only: bool,
+    #[arg(long, value_name = "REF", default_value = "origin/main", requires = "changed_only", help = "Git ref --changed-only compares against")]
+    base: String,
+    #[arg(long, value_name = "ECOSYSTEM", value_parser = rensa_core::config::parse_ecosystem, help = "Leave an ecosystem's plugin out of the scan (may be repeated)")]
+    skip_ecosystem: Vec<rensa_core::Ecosystem>,
+    #[arg(long, help = "Also scan manifests inside git submodules")]
+    include_submodules: bool,
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..), help = "Number of registry and vulnerability lookups to run at once; other limits follow it unless set themselves (default: the CPU count)")]
+    concurrency: Option<u64>,
+    #[arg(long, value_name = "N", value_parser = clap::value_parser!(u64).range(1..), help = "Most network requests in flight at once across the whole scan, which also sizes connection pools (default: twice --concurrency)")]
+    max_concurrency: Option<u64>,
+    #[arg(short, long, conflicts_with_all = ["json", "json_compact", "format"], help = "Browse the results in a terminal UI (falls back to text when stdout is not a terminal)")]
+    interactive: bool,
+    #[arg(long, value_name = "REPORT", conflicts_with = "interactive", help = "Only print what changed since a previous JSON report; --fail-on then only counts new vulnerabilities")]
+    since: Option<PathBuf>,
+    #[arg(short, long, value_name = "FILE", conflicts_with_all = ["interactive", "since"], help = "Write the report to FILE instead of stdout (needs a non-text --format)")]
+    output: Option<PathBuf>,
+    #[arg(short, long, conflicts_with = "interactive", help = "Print nothing on stdout; rely on the exit code and --output")]
+    quiet: bool,
+    #[arg(long, value_name = "FILE", help = "Append a JSON line recording this scan (paths, versions, findings, endpoints) to FILE")]
+    audit_log: Option<PathBuf>,
+    #[arg(long, conflicts_with_all = ["sbom", "composer_show", "url", "git", "limit", "fail_fast", "interactive", "since", "actionable_only", "format", "json", "json_compact"], help = "Write each manifest's result as a JSON line as soon as it is scanned, then a summary line; keeps memory bounded on very large repositories")]
+    streaming: bool,
+    #[arg(long = "composer-section", value_name = "SECTION", help = "Only parse this composer.json section for requirements, e.g. require (may be repeated; default require and require-dev)")]
+    composer_sections: Vec<String>,
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["sbom", "composer_show"], help = "Record finished lookups in FILE and skip the ones an interrupted scan already recorded there; FILE is deleted once the scan completes")]
+    resume: Option<PathBuf>,
+    #[arg(long, value_name = "GLOB", value_parser = rensa_core::filter::parse_pattern, help = "Only scan dependencies whose names match GLOB, e.g. 'symfony/*'; prefix with ! to leave matches out instead (may be repeated)")]
+    filter: Vec<String>,
+    #[arg(long, help = "Exit non-zero if a vulnerability database could not be reached at all, instead of reporting no vulnerabilities")]
+    require_vuln_db: bool,
+    #[arg(long, conflicts_with = "sbom", help = "Record on each dependency which URLs were queried, whether the answers were cached and how its update was classified (shown in JSON output)")]
+    trace: bool,
 }
 
 impl Check {
-    pub async fn run(&self) -> anyhow::Result<()> {
-        let path = self.path.clone().unwrap_or_else(|| PathBuf::from("."));
+    /// Runs the scan and returns the process exit code.
+    pub async fn run(&self) -> anyhow::Result<i32> {
+        let cli_patterns: Vec<String> = self.path.iter().chain(&self.paths).cloned().collect();
+
+        // The config is looked up next to the first path given, or in the
+        // current directory, where it may itself declare what to scan.
+        let (paths, config) = if cli_patterns.is_empty() {
+            let config = discover_config(Path::new("."))?;
+            let patterns = resolve_scan_paths(&cli_patterns, config.as_ref());
+            (rensa_core::scanner::expand_paths(&patterns)?, config)
+        } else {
+            let paths = rensa_core::scanner::expand_paths(&cli_patterns)?;
+            let config = discover_config(&paths[0])?;
+            (paths, config)
+        };
+        let config = config.unwrap_or_default();
+        let severity_labels = config.severity_labels.clone();
+        let options = self.scan_options(config);
+        let baseline = self.since.as_deref().map(load_baseline).transpose()?;
+
+        let progress = options.progress.clone().map(super::display::ProgressLine::start);
+        let (report, scanned) = match (&self.sbom, &self.composer_show) {
+            (Some(sbom), _) => {
+                if !sbom.is_file() {
+                    anyhow::bail!("SBOM file does not exist: {}", sbom.display());
+                }
+                (super::scan::run_sbom_scan(sbom, &options).await?, vec![sbom.clone()])
+            }
+            (None, Some(show)) => {
+                if !show.is_file() {
+                    anyhow::bail!("composer show output does not exist: {}", show.display());
+                }
+                (super::scan::run_composer_show_scan(show, &options).await?, vec![show.clone()])
+            }
+            (None, None) if self.url.is_some() || self.git.is_some() => {
+                let (checkout, source) = self.fetch_remote().await?;
+                let mut report = super::scan::run_scan(&[checkout.path().to_path_buf()], &options).await?;
+                report.scanned_path = PathBuf::from(&source);
+                (report, vec![PathBuf::from(source)])
+            }
+            (None, None) => {
+                if let Some(missing) = paths.iter().find(|p| !p.exists()) {
+                    anyhow::bail!("Path does not exist: {}", missing.display());
+                }
+                let paths = if self.changed_only { self.changed_paths(&paths)? } else { paths };
+
+                if self.streaming {
+                    (self.stream(&paths, &options).await?, paths)
+                } else {
+                    (super::scan::run_scan(&paths, &options).await?, paths)
+                }
+            }
+        };
+        drop(progress);
 
-        if !path.exists() {
-            anyhow::bail!("Path does not exist: {}", path.display());
+        if let Some(log) = &self.audit_log {
+            let entry = rensa_core::audit::AuditEntry::new(&report, scanned, super::scan::endpoints(&options));
+            rensa_core::audit::append(log, &entry)
+                .map_err(|e| anyhow::anyhow!("Failed to write audit log {}: {}", log.display(), e))?;
         }
 
-        let report = super::scan::run_scan(&path).await?;
+        let diff = baseline.as_ref().map(|baseline| report.diff(baseline));
+        if !self.streaming {
+            let output = if self.actionable_only { report.actionable() } else { report.clone() };
+            let format = if self.json {
+                super::display::OutputFormat::Json
+            } else if self.json_compact {
+                super::display::OutputFormat::JsonCompact
+            } else {
+                self.format
+            };
+            self.emit(&output, format, diff.as_ref(), &severity_labels, &mut std::io::stdout())?;
+        }
 
-        if self.json {
-            println!("{}", super::display::print_json(&report)?);
-        } else {
-            super::display::print_report(&report);
+        if let Some(url) = &self.webhook {
+            if let Err(e) = rensa_core::webhook::notify(&HttpClient::new(), url, &report, self.webhook_format).await {
+                eprintln!("Warning: Failed to send webhook notification: {}", e);
+            }
         }
 
+        if self.strict && !report.warnings.is_empty() {
+            eprintln!("Failing because of {} warning(s) (--strict):", report.warnings.len());
+            for warning in &report.warnings {
+                eprintln!("  - {}", warning);
+            }
+        }
+
+        if self.require_vuln_db {
+            for source in report.unreachable_vulnerability_databases() {
+                eprintln!(
+                    "Failing because the {} vulnerability database could not be reached (--require-vuln-db): {}",
+                    source.ecosystem,
+                    source.last_error.as_deref().unwrap_or("no response")
+                );
+            }
+        }
+
+        if options.unresolved == UnresolvedPolicy::Error && !report.unresolved_dependencies.is_empty() {
+            eprintln!(
+                "Failing because {} dependency(ies) were not found in any registry (unresolved: error)",
+                report.unresolved_dependencies.len()
+            );
+            return Ok(1);
+        }
+
+        Ok(self.exit_code(&report, diff.as_ref()))
+    }
+
+    /// Writes the report to `--output` or prints it. With `--quiet`,
+    /// nothing at all goes to `stdout`; warnings are on stderr regardless.
+    fn emit(
+        &self,
+        report: &ScanReport,
+        format: super::display::OutputFormat,
+        diff: Option<&ReportDiff>,
+        severity_labels: &HashMap<Severity, String>,
+        stdout: &mut impl Write,
+    ) -> anyhow::Result<()> {
+        if let Some(file) = &self.output {
+            let rendered = super::display::render(report, format)?;
+            std::fs::write(file, rendered)
+                .map_err(|e| anyhow::anyhow!("Failed to write report to {}: {}", file.display(), e))?;
+            if !self.quiet {
+                writeln!(stdout, "Report written to {}", file.display())?;
+            }
+            return Ok(());
+        }
+        if self.quiet {
+            return Ok(());
+        }
+
+        let options = super::display::TextOptions {
+            verbose: self.verbose,
+            max_references: self.max_references,
+            severity_labels: severity_labels.clone(),
+        };
+        if let Some(diff) = diff {
+            super::display::print_diff(diff, format, &options)?;
+        } else if self.interactive && std::io::stdout().is_terminal() {
+            super::tui::run(report)?;
+        } else {
+            super::display::print(report, format, &options)?;
+        }
         Ok(())
     }
+
+    /// Runs a `--streaming` scan into `--output` or stdout, ending with the
+    /// summary report on a line of its own.
+    async fn stream(&self, paths: &[PathBuf], options: &super::scan::ScanOptions) -> anyhow::Result<ScanReport> {
+        let mut out: Box<dyn Write> = match &self.output {
+            Some(file) => Box::new(std::io::BufWriter::new(
+                std::fs::File::create(file)
+                    .map_err(|e| anyhow::anyhow!("Failed to write report to {}: {}", file.display(), e))?,
+            )),
+            None if self.quiet => Box::new(std::io::sink()),
+            None => Box::new(std::io::stdout()),
+        };
+
+        let report = super::scan::run_streaming_scan(paths, options, &mut out).await?;
+        serde_json::to_writer(&mut out, &report)?;
+        writeln!(out)?;
+        out.flush()?;
+        Ok(report)
+    }
+
+    /// Downloads `--url` or clones `--git` into a temporary directory, which
+    /// is removed once the returned handle is dropped.
+    async fn fetch_remote(&self) -> anyhow::Result<(tempfile::TempDir, String)> {
+        if let Some(url) = &self.url {
+            let checkout = rensa_core::remote::fetch_manifest(&HttpClient::new(), url)
+                .await
+                .map_err(|e| anyhow::anyhow!("Failed to fetch composer.json from {}: {}", url, e))?;
+            return Ok((checkout, url.clone()));
+        }
+
+        let repo = self.git.as_deref().unwrap_or_default();
+        let checkout = rensa_core::remote::clone_repository(repo, self.git_ref.as_deref())
+            .map_err(|e| anyhow::anyhow!("Failed to clone {}: {}", repo, e))?;
+        let source = match &self.git_ref {
+            Some(git_ref) => format!("{}@{}", repo, git_ref),
+            None => repo.to_string(),
+        };
+        Ok((checkout, source))
+    }
+
+    /// The manifests under `paths` that changed since `--base`.
+    fn changed_paths(&self, paths: &[PathBuf]) -> anyhow::Result<Vec<PathBuf>> {
+        let mut changed = Vec::new();
+        for path in paths {
+            let dir = if path.is_file() { path.parent().unwrap_or(Path::new(".")) } else { path.as_path() };
+            let dir = if dir.as_os_str().is_empty() { Path::new(".") } else { dir };
+            for file in rensa_core::changed::changed_manifests(dir, &self.base)? {
+                if !changed.contains(&file) {
+                    changed.push(file);
+                }
+            }
+        }
+        if changed.is_empty() {
+            eprintln!("No manifests changed since {}", self.base);
+        }
+        Ok(changed)
+    }
+
+    /// Combines CLI flags with the config file; flags take precedence.
+    fn scan_options(&self, config: Config) -> super::scan::ScanOptions {
+        let seconds = |flag: Option<u64>, configured: Option<u64>| {
+            flag.or(configured).map(Duration::from_secs)
+        };
+        let groups = config.scan_groups();
+        let mut disabled_ecosystems = config.disabled_ecosystems();
+        disabled_ecosystems.extend(self.skip_ecosystem.iter().copied());
+        let registries = config.registry_configs();
+        let licenses = config.license_policy();
+        // Patterns were checked when the config was loaded.
+        let denylist = config.package_denylist().unwrap_or_default();
+        // So were --filter patterns, when the flags were parsed.
+        let filter = DependencyFilter::new(&self.filter).unwrap_or_default();
+        let concurrency = self
+            .concurrency
+            .map(|n| n as usize)
+            .or(config.concurrency)
+            .unwrap_or_else(rensa_core::scanner::default_concurrency);
+
+        super::scan::ScanOptions {
+            limit: self.limit,
+            fail_fast: if self.fail_fast { self.fail_on } else { None },
+            registry_timeout: seconds(self.registry_timeout, config.registry_timeout),
+            osv_timeout: seconds(self.osv_timeout, config.osv_timeout),
+            osv_url: self.osv_url.clone().or(config.osv_url),
+            include_related: self.include_related,
+            severity_floor: self.min_severity.or(config.min_severity),
+            transitive_depth: self.transitive_depth,
+            severity_threshold: self.fail_on.unwrap_or(config.severity_threshold),
+            overrides: config.overrides,
+            include_submodules: self.include_submodules,
+            max_file_size: config.max_file_size,
+            groups,
+            disabled_ecosystems,
+            registries,
+            concurrency: Some(concurrency),
+            max_concurrency: Some(
+                self.max_concurrency
+                    .map(|n| n as usize)
+                    .or(config.max_concurrency)
+                    .unwrap_or(concurrency * 2),
+            ),
+            licenses,
+            denylist,
+            ignore: config.ignore,
+            filter,
+            unresolved: config.unresolved,
+            cache_dir: config.cache_dir.or_else(|| std::env::var_os("RENSA_CACHE_DIR").map(PathBuf::from)),
+            composer_sections: (!self.composer_sections.is_empty())
+                .then(|| self.composer_sections.clone())
+                .or(config.composer_sections),
+            resume: self.resume.clone(),
+            trace: self.trace,
+            // Only drawn for someone watching, and not over streamed
+            // results or with --quiet.
+            progress: (!self.quiet && !self.streaming && std::io::stderr().is_terminal())
+                .then(|| std::sync::Arc::new(rensa_core::progress::ScanProgress::new())),
+            eol: (self.eol || config.eol.enabled).then_some(config.eol),
+        }
+    }
+
+    /// With a diff, only vulnerabilities new since the baseline count
+    /// towards `--fail-on`.
+    fn exit_code(&self, report: &ScanReport, diff: Option<&ReportDiff>) -> i32 {
+        if let Some(threshold) = self.fail_on {
+            let failing = match diff {
+                Some(diff) => diff.new_vulnerabilities.iter().any(|v| v.severity.meets_threshold(threshold)),
+                None if self.streaming => report.summary.vulnerabilities_at_or_above(threshold) > 0,
+                None => !report.vulnerabilities_at_or_above(threshold).is_empty(),
+            };
+            if failing {
+                return 1;
+            }
+        }
+        if self.strict && !report.warnings.is_empty() {
+            return 1;
+        }
+        if self.require_vuln_db && !report.unreachable_vulnerability_databases().is_empty() {
+            return 1;
+        }
+        0
+    }
+}
+
+/// Reads a report previously written with `--json`.
+fn load_baseline(path: &Path) -> anyhow::Result<ScanReport> {
+    let content = std::fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("Failed to read baseline report {}: {}", path.display(), e))?;
+    serde_json::from_str(&content)
+        .map_err(|e| anyhow::anyhow!("{} is not a JSON scan report: {}", path.display(), e))
+}
+
+/// Finds a config file in `root`, or in its directory when `root` is a file.
+fn discover_config(root: &Path) -> anyhow::Result<Option<Config>> {
+    let dir = match root.parent() {
+        Some(parent) if root.is_file() && !parent.as_os_str().is_empty() => parent,
+        Some(_) if root.is_file() => Path::new("."),
+        _ => root,
+    };
+
+    if dir.is_dir() {
+        Ok(Config::discover(dir)?)
+    } else {
+        Ok(None)
+    }
 }
 
 #[derive(Parser)]
@@ -36,8 +424,140 @@ impl Ecosystems {
     pub fn run(&self) {
         println!("Supported ecosystems:");
         println!("  - composer");
-        println!("  - npm (coming soon)");
+        println!("  - npm");
+        println!("  - pub");
+        println!("  - terraform");
         println!("  - cargo (coming soon)");
         println!("  - pypi (coming soon)");
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(args: &[&str]) -> Check {
+        Check::parse_from(std::iter::once("check").chain(args.iter().copied()))
+    }
+
+    #[test]
+    fn test_strict_fails_on_warning() {
+        let mut report = ScanReport::new(PathBuf::from("."));
+        report.warnings.push("No parser for ecosystem: Cargo".to_string());
+
+        assert_eq!(check(&["--strict"]).exit_code(&report, None), 1);
+        assert_eq!(check(&["--warnings-as-errors"]).exit_code(&report, None), 1);
+        assert_eq!(check(&[]).exit_code(&report, None), 0);
+    }
+
+    #[test]
+    fn test_require_vuln_db_fails_when_osv_is_unreachable() {
+        use rensa_core::report::SourceKind;
+
+        let mut report = ScanReport::new(PathBuf::from("."));
+        let refused = rensa_core::RensaError::Plugin {
+            message: "connection refused".to_string(),
+        };
+        report.record_source(rensa_core::Ecosystem::Composer, SourceKind::Registry, Ok(()));
+        report.record_source(rensa_core::Ecosystem::Composer, SourceKind::VulnerabilityDatabase, Err(&refused));
+        report.record_source(rensa_core::Ecosystem::Composer, SourceKind::VulnerabilityDatabase, Err(&refused));
+
+        assert_eq!(report.summary.vulnerabilities_found, 0);
+        assert_eq!(check(&["--require-vuln-db"]).exit_code(&report, None), 1);
+        assert_eq!(check(&[]).exit_code(&report, None), 0);
+
+        // One answer is enough to trust the results.
+        report.record_source(rensa_core::Ecosystem::Composer, SourceKind::VulnerabilityDatabase, Ok(()));
+        assert_eq!(check(&["--require-vuln-db"]).exit_code(&report, None), 0);
+    }
+
+    #[test]
+    fn test_trim_is_actionable_only() {
+        assert!(check(&["--trim"]).actionable_only);
+        assert!(!check(&[]).actionable_only);
+    }
+
+    #[test]
+    fn test_strict_passes_without_warnings() {
+        let report = ScanReport::new(PathBuf::from("."));
+        assert_eq!(check(&["--strict"]).exit_code(&report, None), 0);
+    }
+
+    #[test]
+    fn test_since_only_fails_on_new_vulnerabilities() {
+        let vulnerability = |package: &str| rensa_core::Vulnerability {
+            id: format!("GHSA-{}", package),
+            summary: String::new(),
+            details: String::new(),
+            severity: rensa_core::Severity::High,
+            affected_versions: Vec::new(),
+            fixed_versions: Vec::new(),
+            references: Vec::new(),
+            package: Some(package.to_string()),
+            related_to: None,
+            remediation: None,
+        };
+
+        let mut baseline = ScanReport::new(PathBuf::from("."));
+        baseline.vulnerabilities.push(vulnerability("known/pkg"));
+        let check = check(&["--fail-on", "high", "--since", "baseline.json"]);
+
+        let unchanged = baseline.clone();
+        let diff = unchanged.diff(&baseline);
+        assert_eq!(check.exit_code(&unchanged, Some(&diff)), 0);
+        assert_eq!(check.exit_code(&unchanged, None), 1);
+
+        let mut introduced = baseline.clone();
+        introduced.vulnerabilities.push(vulnerability("new/pkg"));
+        let diff = introduced.diff(&baseline);
+        assert_eq!(check.exit_code(&introduced, Some(&diff)), 1);
+    }
+
+    #[test]
+    fn test_quiet_output_writes_file_and_nothing_to_stdout() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir.path().join("report.json");
+        let file_arg = file.to_str().unwrap();
+        let report = ScanReport::new(PathBuf::from("."));
+
+        let mut stdout = Vec::new();
+        let quiet = check(&["--quiet", "--output", file_arg, "--format", "json"]);
+        quiet.emit(&report, quiet.format, None, &HashMap::new(), &mut stdout).unwrap();
+
+        assert!(stdout.is_empty());
+        let written: ScanReport = serde_json::from_str(&std::fs::read_to_string(&file).unwrap()).unwrap();
+        assert_eq!(written.scanned_path, PathBuf::from("."));
+
+        let loud = check(&["--output", file_arg, "--format", "json"]);
+        loud.emit(&report, loud.format, None, &HashMap::new(), &mut stdout).unwrap();
+        assert_eq!(String::from_utf8(stdout).unwrap(), format!("Report written to {}\n", file.display()));
+    }
+
+    #[test]
+    fn test_output_rejects_text_format() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let file = temp_dir.path().join("report.txt");
+        let check = check(&["--output", file.to_str().unwrap()]);
+
+        let result = check.emit(&ScanReport::new(PathBuf::from(".")), check.format, None, &HashMap::new(), &mut Vec::new());
+        assert!(result.is_err());
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn test_concurrency_flag_reaches_scanner() {
+        let options = check(&["--concurrency", "4"]).scan_options(Config::default());
+        assert_eq!(options.concurrency, Some(4));
+        assert_eq!(options.max_concurrency, Some(8));
+
+        let registry = super::super::scan::build_registry(&options);
+        let scanner = super::super::scan::path_scanner(&registry, &options).unwrap();
+        assert_eq!(scanner.concurrency(), 4);
+
+        let options = check(&["--concurrency", "4", "--max-concurrency", "5"]).scan_options(Config::default());
+        assert_eq!(options.max_concurrency, Some(5));
+
+        let options = check(&[]).scan_options(Config::default());
+        assert_eq!(options.concurrency, Some(rensa_core::scanner::default_concurrency()));
+    }
+}
@@ -0,0 +1,167 @@
+//! Output shaped like `composer audit --format=json`, so tooling written
+//! against composer keeps working.
+
+use rensa_core::{ScanReport, Severity, Vulnerability};
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+pub fn to_composer_audit(report: &ScanReport) -> anyhow::Result<String> {
+    let mut advisories: BTreeMap<String, Vec<Value>> = BTreeMap::new();
+    for vuln in &report.vulnerabilities {
+        let package = match &vuln.package {
+            Some(p) => p,
+            None => continue,
+        };
+        advisories.entry(package.clone()).or_default().push(advisory(package, vuln));
+    }
+
+    // PHP encodes empty maps as `[]`; mirror that so the output matches
+    // composer's when nothing is found. Abandoned packages aren't detected
+    // yet, so that section is always empty.
+    let advisories = if advisories.is_empty() { json!([]) } else { json!(advisories) };
+    let output = json!({
+        "advisories": advisories,
+        "abandoned": Value::Array(Vec::new()),
+    });
+
+    Ok(serde_json::to_string_pretty(&output)?)
+}
+
+fn advisory(package: &str, vuln: &Vulnerability) -> Value {
+    let link = vuln
+        .references
+        .first()
+        .cloned()
+        .unwrap_or_else(|| format!("https://osv.dev/vulnerability/{}", vuln.id));
+    let severity = match vuln.severity {
+        Severity::Critical => Some("critical"),
+        Severity::High => Some("high"),
+        Severity::Medium => Some("medium"),
+        Severity::Low => Some("low"),
+        Severity::Unknown => None,
+    };
+
+    json!({
+        "advisoryId": vuln.id,
+        "packageName": package,
+        "affectedVersions": affected_versions(vuln),
+        "title": vuln.summary,
+        "cve": cve(vuln),
+        "link": link,
+        // Rensa doesn't track publication dates; composer always sets one,
+        // so consumers need to tolerate null here.
+        "reportedAt": Value::Null,
+        "sources": [{ "name": "OSV", "remoteId": vuln.id }],
+        "severity": severity,
+    })
+}
+
+/// Composer joins ranges with `|`. When only fixed versions are known, each
+/// becomes an upper bound.
+fn affected_versions(vuln: &Vulnerability) -> String {
+    if !vuln.affected_versions.is_empty() {
+        return vuln.affected_versions.join("|");
+    }
+    vuln.fixed_versions
+        .iter()
+        .map(|fixed| format!("<{}", fixed))
+        .collect::<Vec<_>>()
+        .join("|")
+}
+
+/// The CVE id, taken from the advisory id or a reference URL like
+/// `https://nvd.nist.gov/vuln/detail/CVE-2023-1234`.
+fn cve(vuln: &Vulnerability) -> Option<String> {
+    if vuln.id.starts_with("CVE-") {
+        return Some(vuln.id.clone());
+    }
+    vuln.references
+        .iter()
+        .flat_map(|url| url.split('/'))
+        .find(|segment| segment.starts_with("CVE-"))
+        .map(str::to_string)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    /// Trimmed output of `composer audit --format=json` (composer 2.6).
+    const COMPOSER_SAMPLE: &str = r#"{
+    "advisories": {
+        "guzzlehttp/psr7": [
+            {
+                "advisoryId": "PKSA-8wmj-d9t8-ck6n",
+                "packageName": "guzzlehttp/psr7",
+                "affectedVersions": "<1.9.1|>=2,<2.4.5",
+                "title": "Improper header validation",
+                "cve": "CVE-2023-29197",
+                "link": "https://github.com/guzzle/psr7/security/advisories/GHSA-wxmh-65f7-jcvw",
+                "reportedAt": "2023-04-17T16:00:00+00:00",
+                "sources": [
+                    {
+                        "name": "GitHub",
+                        "remoteId": "GHSA-wxmh-65f7-jcvw"
+                    }
+                ],
+                "severity": "high"
+            }
+        ]
+    },
+    "abandoned": []
+}"#;
+
+    fn keys(value: &Value) -> Vec<String> {
+        let mut keys: Vec<_> = value.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    fn report() -> ScanReport {
+        let mut report = ScanReport::new(PathBuf::from("."));
+        report.vulnerabilities.push(Vulnerability {
+            id: "GHSA-wxmh-65f7-jcvw".to_string(),
+            summary: "Improper header validation".to_string(),
+            details: String::new(),
+            severity: Severity::High,
+            affected_versions: Vec::new(),
+            fixed_versions: vec!["1.9.1".to_string(), "2.4.5".to_string()],
+            references: vec![
+                "https://github.com/guzzle/psr7/security/advisories/GHSA-wxmh-65f7-jcvw".to_string(),
+                "https://nvd.nist.gov/vuln/detail/CVE-2023-29197".to_string(),
+            ],
+            package: Some("guzzlehttp/psr7".to_string()),
+            related_to: None,
+            remediation: None,
+        });
+        report
+    }
+
+    #[test]
+    fn test_output_matches_composer_schema() {
+        let sample: Value = serde_json::from_str(COMPOSER_SAMPLE).unwrap();
+        let output: Value = serde_json::from_str(&to_composer_audit(&report()).unwrap()).unwrap();
+
+        assert_eq!(keys(&output), keys(&sample));
+        assert_eq!(output["abandoned"], sample["abandoned"]);
+
+        let expected = &sample["advisories"]["guzzlehttp/psr7"][0];
+        let actual = &output["advisories"]["guzzlehttp/psr7"][0];
+        assert_eq!(keys(actual), keys(expected));
+        assert_eq!(keys(&actual["sources"][0]), keys(&expected["sources"][0]));
+
+        for field in ["packageName", "title", "cve", "link", "severity"] {
+            assert_eq!(actual[field], expected[field], "{}", field);
+        }
+        assert_eq!(actual["affectedVersions"], "<1.9.1|<2.4.5");
+        assert_eq!(actual["advisoryId"], "GHSA-wxmh-65f7-jcvw");
+    }
+
+    #[test]
+    fn test_empty_report_uses_php_empty_arrays() {
+        let output: Value =
+            serde_json::from_str(&to_composer_audit(&ScanReport::new(PathBuf::from("."))).unwrap()).unwrap();
+        assert_eq!(output, json!({ "advisories": [], "abandoned": [] }));
+    }
+}
@@ -1,35 +1,129 @@
-use rensa_core::ScanReport;
+mod composer_audit;
+mod progress;
 
-pub fn print_report(report: &ScanReport) {
+pub use progress::ProgressLine;
+
+use rensa_core::report::{Reachability, SourceKind};
+use rensa_core::report::ReportDiff;
+use rensa_core::{ProvenanceInfo, ScanReport, Severity};
+use colored::{ColoredString, Colorize};
+use std::collections::HashMap;
+use std::io::IsTerminal;
+
+/// References listed per vulnerability in the text report by default.
+pub const DEFAULT_MAX_REFERENCES: usize = 3;
+
+/// Settings that only affect the human-readable report.
+#[derive(Debug, Clone)]
+pub struct TextOptions {
+    /// List every dependency along with its source repository when the
+    /// registry reported one.
+    pub verbose: bool,
+    /// References shown per vulnerability; the rest are counted.
+    pub max_references: usize,
+    /// Custom names for severities, from the config's `severity_labels`.
+    pub severity_labels: HashMap<Severity, String>,
+}
+
+impl Default for TextOptions {
+    fn default() -> Self {
+        Self {
+            verbose: false,
+            max_references: DEFAULT_MAX_REFERENCES,
+            severity_labels: HashMap::new(),
+        }
+    }
+}
+
+/// Decides once for the whole process whether output is colored: not with
+/// `--no-color`, when stdout isn't a terminal, or when `NO_COLOR` is set to
+/// a non-empty value (https://no-color.org).
+pub fn set_color(no_color: bool) {
+    let env = std::env::var_os("NO_COLOR");
+    colored::control::set_override(color_enabled(no_color, std::io::stdout().is_terminal(), env.as_deref()));
+}
+
+/// `no_color_env` is the value of `NO_COLOR`, if it is set.
+fn color_enabled(no_color: bool, terminal: bool, no_color_env: Option<&std::ffi::OsStr>) -> bool {
+    !no_color && terminal && no_color_env.is_none_or(|v| v.is_empty())
+}
+
+/// The configured label for `severity`, or its own name.
+fn severity_name(severity: Severity, labels: &HashMap<Severity, String>) -> String {
+    labels.get(&severity).cloned().unwrap_or_else(|| format!("{:?}", severity))
+}
+
+fn severity_label(severity: Severity, labels: &HashMap<Severity, String>) -> ColoredString {
+    let label = severity_name(severity, labels);
+    match severity {
+        Severity::Critical => label.red().bold(),
+        Severity::High => label.red(),
+        Severity::Medium => label.yellow(),
+        _ => label.normal(),
+    }
+}
+
+pub fn print_report(report: &ScanReport, options: &TextOptions) {
     println!("\n{}", "=".repeat(60));
     println!("Rensa Scan Report");
     println!("{}", "=".repeat(60));
     println!("Path: {}", report.scanned_path.display());
+    if let Some(project) = &report.project {
+        println!("Project: {}", project);
+    }
     println!("Duration: {}ms", report.elapsed);
+    if let Some(partial) = &report.partial {
+        println!(
+            "Partial scan: {} of {} dependencies checked",
+            partial.scanned, partial.total
+        );
+    }
     println!();
 
     println!("Summary:");
     println!("  Dependency files: {}", report.total_dependency_files);
     println!("  Dependencies: {}", report.total_dependencies);
     println!("  Updates available: {}", report.summary.updates_available);
+    if report.total_dependencies > 0 {
+        match report.summary.unchecked_dependencies {
+            0 => println!("  Coverage: 100%"),
+            unchecked => println!(
+                "  Coverage: {:.0}% — {} dependencies could not be checked",
+                report.summary.coverage * 100.0,
+                unchecked
+            ),
+        }
+    }
     println!(
         "  Vulnerabilities: {}",
         report.summary.vulnerabilities_found
     );
-    if report.summary.critical_vulnerabilities > 0 {
-        println!("    Critical: {}", report.summary.critical_vulnerabilities);
+    for source in report.unreachable_vulnerability_databases() {
+        println!("    Not checked for {}: vulnerability database unreachable", source.ecosystem);
     }
-    if report.summary.high_vulnerabilities > 0 {
-        println!("    High: {}", report.summary.high_vulnerabilities);
-    }
-    if report.summary.medium_vulnerabilities > 0 {
-        println!("    Medium: {}", report.summary.medium_vulnerabilities);
+    let labels = &options.severity_labels;
+    for (severity, count) in [
+        (Severity::Critical, report.summary.critical_vulnerabilities),
+        (Severity::High, report.summary.high_vulnerabilities),
+        (Severity::Medium, report.summary.medium_vulnerabilities),
+        (Severity::Low, report.summary.low_vulnerabilities),
+    ] {
+        if count > 0 {
+            println!("    {}: {}", severity_name(severity, labels), count);
+        }
     }
-    if report.summary.low_vulnerabilities > 0 {
-        println!("    Low: {}", report.summary.low_vulnerabilities);
+    if report.summary.vulnerabilities_found > 0 {
+        println!(
+            "    In direct dependencies: {}, transitive only: {}",
+            report.summary.direct_vulnerabilities, report.summary.transitive_vulnerabilities
+        );
     }
     println!();
 
+    if options.verbose {
+        print_dependencies(report);
+    }
+
     if !report.updates.is_empty() {
         println!("Updates:");
         for update in &report.updates {
@@ -37,6 +131,26 @@ pub fn print_report(report: &ScanReport) {
                 "  - {} ({} -> {})",
                 update.dependency.name, update.current_version, update.latest_version
             );
+            if let Some(freshness) = update.freshness() {
+                println!("    {}", freshness);
+            }
+            if let Some(compatible) = &update.latest_in_constraint {
+                if compatible != &update.latest_version {
+                    println!(
+                        "    Latest within {}: {} (newer versions need a constraint change)",
+                        update.dependency.version, compatible
+                    );
+                }
+            }
+            if !update.intermediate_releases.is_empty() {
+                println!("    Release notes along the way:");
+                for note in &update.intermediate_releases {
+                    println!("      {}: {}", note.version, note.url);
+                }
+            }
+            if let Some(repository) = update.dependency.repository.as_ref().filter(|_| options.verbose) {
+                println!("    Repository: {}", repository);
+            }
         }
         println!();
     }
@@ -45,10 +159,73 @@ pub fn print_report(report: &ScanReport) {
         println!("Vulnerabilities:");
         for vuln in &report.vulnerabilities {
             println!("  - [{}] {}", vuln.id, vuln.summary);
-            println!("    Severity: {:?}", vuln.severity);
+            println!("    Severity: {}", severity_label(vuln.severity, labels));
+            if let Some(primary) = &vuln.related_to {
+                println!("    Related to: {}", primary);
+            }
             if !vuln.fixed_versions.is_empty() {
                 println!("    Fixed in: {}", vuln.fixed_versions.join(", "));
             }
+            if let Some(remediation) = &vuln.remediation {
+                match &remediation.fixed_in_constraint {
+                    Some(version) => println!("    Fixable within constraint: upgrade to {}", version),
+                    None => println!("    Fix requires a constraint change"),
+                }
+            }
+            for line in reference_lines(&vuln.references, options.max_references) {
+                println!("    {}", line);
+            }
+        }
+        println!();
+    }
+
+    if !report.sources.is_empty() {
+        println!("Sources:");
+        for source in &report.sources {
+            let kind = match source.source {
+                SourceKind::Registry => "registry",
+                SourceKind::VulnerabilityDatabase => "vulnerability database",
+            };
+            let status = match source.status {
+                Reachability::Reachable => "reachable",
+                Reachability::TimedOut => "timed out",
+                Reachability::Unreachable => "unreachable",
+            };
+            println!(
+                "  - {} {}: {} ({} ok, {} failed)",
+                source.ecosystem, kind, status, source.successes, source.failures
+            );
+            if source.status != Reachability::Reachable {
+                if let Some(error) = &source.last_error {
+                    println!("    Last error: {}", error);
+                }
+            }
+        }
+        println!();
+    }
+
+    if !report.end_of_life.is_empty() {
+        println!("End of life:");
+        for eol in &report.end_of_life {
+            match eol.eol {
+                Some(date) => println!("  - {} {} (support ended {})", eol.name, eol.cycle, date),
+                None => println!("  - {} {} (no longer supported)", eol.name, eol.cycle),
+            }
+        }
+        println!();
+    }
+
+    let effective: Vec<_> = report.effective_constraints.iter().filter(|c| c.effective.is_some()).collect();
+    if !effective.is_empty() {
+        println!("Effective constraints:");
+        for constraint in effective {
+            let declared: Vec<&str> = constraint.declared.iter().map(|d| d.constraint.as_str()).collect();
+            println!(
+                "  - {} {} (from {})",
+                constraint.name,
+                constraint.effective.as_deref().unwrap_or_default(),
+                declared.join(", ")
+            );
         }
         println!();
     }
@@ -69,6 +246,237 @@ pub fn print_report(report: &ScanReport) {
     }
 }
 
-pub fn print_json(report: &ScanReport) -> anyhow::Result<String> {
-    Ok(serde_json::to_string_pretty(report)?)
+fn print_dependencies(report: &ScanReport) {
+    let mut results: Vec<_> = report.ecosystem_results.values().collect();
+    results.sort_by_key(|r| r.ecosystem);
+    if results.iter().all(|r| r.dependencies.is_empty()) {
+        return;
+    }
+
+    println!("Dependencies:");
+    for result in results {
+        for dep in &result.dependencies {
+            match &dep.repository {
+                Some(repository) => println!("  - {} {} ({})", dep.name, dep.version, repository),
+                None => println!("  - {} {}", dep.name, dep.version),
+            }
+            match &dep.provenance {
+                Some(ProvenanceInfo { signed: true, attestation_url: Some(url) }) => println!("    Provenance: {}", url),
+                Some(ProvenanceInfo { signed: true, attestation_url: None }) => println!("    Provenance: signed"),
+                Some(ProvenanceInfo { signed: false, .. }) => println!("    Provenance: {}", "unsigned".yellow()),
+                None => {}
+            }
+        }
+    }
+    println!();
+}
+
+/// The first `max` references, then a count of the ones left out. Some
+/// advisories carry dozens, which would bury the rest of the report.
+fn reference_lines(references: &[String], max: usize) -> Vec<String> {
+    let mut lines: Vec<String> = references.iter().take(max).map(|url| format!("Reference: {}", url)).collect();
+    if references.len() > max {
+        lines.push(format!("(+{} more)", references.len() - max));
+    }
+    lines
+}
+
+/// Pretty-printed, or on a single line when `compact` is set, for tools
+/// that ingest JSON logs line by line.
+pub fn print_json(report: &ScanReport, compact: bool) -> anyhow::Result<String> {
+    if compact {
+        Ok(serde_json::to_string(report)?)
+    } else {
+        Ok(serde_json::to_string_pretty(report)?)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, clap::ValueEnum)]
+pub enum OutputFormat {
+    #[default]
+    Text,
+    Json,
+    /// JSON on a single line.
+    JsonCompact,
+    Yaml,
+    Toml,
+    /// The JSON shape of `composer audit --format=json`.
+    ComposerAudit,
+    /// JUnit XML, one failing test case per vulnerable dependency.
+    Junit,
+    /// An SPDX 2.3 JSON SBOM of the scanned dependencies.
+    Spdx,
+}
+
+/// Prints only what changed against a baseline report (`--since`).
+pub fn print_diff(diff: &ReportDiff, format: OutputFormat, options: &TextOptions) -> anyhow::Result<()> {
+    match format {
+        OutputFormat::Text => print_diff_text(diff, &options.severity_labels),
+        OutputFormat::Json => println!("{}", diff.to_json()?),
+        OutputFormat::JsonCompact => println!("{}", serde_json::to_string(diff)?),
+        other => anyhow::bail!("--since only supports text and json output, not {:?}", other),
+    }
+    Ok(())
+}
+
+fn print_diff_text(diff: &ReportDiff, labels: &HashMap<Severity, String>) {
+    if diff.is_empty() {
+        println!("No changes since the baseline report.");
+        return;
+    }
+
+    if !diff.new_vulnerabilities.is_empty() {
+        println!("New vulnerabilities:");
+        for vuln in &diff.new_vulnerabilities {
+            println!(
+                "  - [{}] {} ({})",
+                vuln.id,
+                vuln.summary,
+                vuln.package.as_deref().unwrap_or("unknown package")
+            );
+            println!("    Severity: {}", severity_label(vuln.severity, labels));
+        }
+        println!();
+    }
+
+    if !diff.resolved_vulnerabilities.is_empty() {
+        println!("Resolved vulnerabilities:");
+        for vuln in &diff.resolved_vulnerabilities {
+            println!(
+                "  - [{}] {} ({})",
+                vuln.id,
+                vuln.summary,
+                vuln.package.as_deref().unwrap_or("unknown package")
+            );
+        }
+        println!();
+    }
+
+    if !diff.new_updates.is_empty() {
+        println!("New updates:");
+        for update in &diff.new_updates {
+            println!(
+                "  - {} ({} -> {})",
+                update.dependency.name, update.current_version, update.latest_version
+            );
+        }
+        println!();
+    }
+}
+
+pub fn print(report: &ScanReport, format: OutputFormat, options: &TextOptions) -> anyhow::Result<()> {
+    match format {
+        OutputFormat::Text => print_report(report, options),
+        format => print!("{}", render(report, format)?),
+    }
+    Ok(())
+}
+
+/// The report in a machine-readable format, as written by `--output`.
+pub fn render(report: &ScanReport, format: OutputFormat) -> anyhow::Result<String> {
+    Ok(match format {
+        OutputFormat::Text => anyhow::bail!("text output can only be printed, pick another --format"),
+        OutputFormat::Json => format!("{}\n", print_json(report, false)?),
+        OutputFormat::JsonCompact => format!("{}\n", print_json(report, true)?),
+        OutputFormat::Yaml => report.to_yaml()?,
+        OutputFormat::Toml => report.to_toml()?,
+        OutputFormat::ComposerAudit => format!("{}\n", composer_audit::to_composer_audit(report)?),
+        OutputFormat::Junit => report.to_junit(),
+        OutputFormat::Spdx => format!("{}\n", report.to_spdx()?),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_no_color_env_disables_ansi() {
+        use std::ffi::OsStr;
+
+        assert!(color_enabled(false, true, None));
+        assert!(!color_enabled(false, true, Some(OsStr::new("1"))));
+        // An empty NO_COLOR is treated as unset.
+        assert!(color_enabled(false, true, Some(OsStr::new(""))));
+        assert!(!color_enabled(true, true, None));
+        assert!(!color_enabled(false, false, None));
+    }
+
+    #[test]
+    fn test_custom_severity_labels_only_apply_to_text() {
+        let yaml = "version: 2\nseverity_labels:\n  critical: P0\n  high: P1\n";
+        let config = rensa_core::Config::from_yaml(yaml, std::path::Path::new("rensa.yaml")).unwrap();
+        let labels = config.severity_labels;
+
+        colored::control::set_override(false);
+        assert_eq!(severity_label(Severity::Critical, &labels).to_string(), "P0");
+        assert_eq!(severity_name(Severity::High, &labels), "P1");
+        assert_eq!(severity_name(Severity::Low, &labels), "Low");
+
+        let mut report = ScanReport::new(PathBuf::from("."));
+        report.vulnerabilities.push(rensa_core::Vulnerability {
+            id: "GHSA-aaaa-bbbb-cccc".to_string(),
+            summary: String::new(),
+            details: String::new(),
+            severity: Severity::Critical,
+            affected_versions: Vec::new(),
+            fixed_versions: Vec::new(),
+            references: Vec::new(),
+            package: None,
+            related_to: None,
+            remediation: None,
+        });
+        let json: serde_json::Value = serde_json::from_str(&print_json(&report, false).unwrap()).unwrap();
+        assert_eq!(json["vulnerabilities"][0]["severity"], "critical");
+    }
+
+    #[test]
+    fn test_compact_json_is_single_line() {
+        let mut report = ScanReport::new(PathBuf::from("."));
+        report.warnings.push("No parser for ecosystem: Composer".to_string());
+
+        let compact = print_json(&report, true).unwrap();
+        let pretty = print_json(&report, false).unwrap();
+
+        assert!(!compact.contains('\n'));
+        assert!(pretty.contains('\n'));
+        assert_eq!(
+            serde_json::from_str::<serde_json::Value>(&compact).unwrap(),
+            serde_json::from_str::<serde_json::Value>(&pretty).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_text_truncates_references_but_json_keeps_them() {
+        let references: Vec<String> = (1..=5).map(|i| format!("https://example.com/advisory/{}", i)).collect();
+
+        assert_eq!(
+            reference_lines(&references, 3),
+            vec![
+                "Reference: https://example.com/advisory/1",
+                "Reference: https://example.com/advisory/2",
+                "Reference: https://example.com/advisory/3",
+                "(+2 more)",
+            ]
+        );
+        assert_eq!(reference_lines(&references, 5).len(), 5);
+        assert_eq!(reference_lines(&references, 0), vec!["(+5 more)"]);
+
+        let mut report = ScanReport::new(PathBuf::from("."));
+        report.vulnerabilities.push(rensa_core::Vulnerability {
+            id: "GHSA-aaaa-bbbb-cccc".to_string(),
+            summary: String::new(),
+            details: String::new(),
+            severity: rensa_core::Severity::High,
+            affected_versions: Vec::new(),
+            fixed_versions: Vec::new(),
+            references: references.clone(),
+            package: None,
+            related_to: None,
+            remediation: None,
+        });
+        let json: serde_json::Value = serde_json::from_str(&print_json(&report, false).unwrap()).unwrap();
+        assert_eq!(json["vulnerabilities"][0]["references"], serde_json::json!(references));
+    }
 }
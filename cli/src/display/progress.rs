@@ -0,0 +1,76 @@
+use rensa_core::progress::{ProgressSnapshot, ScanProgress};
+use std::io::Write;
+use std::sync::Arc;
+use std::time::Duration;
+
+const REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+const BAR_WIDTH: usize = 30;
+
+/// One progress bar on stderr covering every ecosystem in the scan,
+/// redrawn until it is dropped, which clears it again.
+pub struct ProgressLine {
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl ProgressLine {
+    pub fn start(progress: Arc<ScanProgress>) -> Self {
+        let task = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(REDRAW_INTERVAL);
+            loop {
+                interval.tick().await;
+                eprint!("\r{}", render(progress.snapshot()));
+                let _ = std::io::stderr().flush();
+            }
+        });
+        Self { task }
+    }
+}
+
+impl Drop for ProgressLine {
+    fn drop(&mut self) {
+        self.task.abort();
+        eprint!("\r\x1b[2K");
+    }
+}
+
+/// The bar only fills once the total is known; until every manifest is
+/// parsed the total is shown as a lower bound.
+fn render(snapshot: ProgressSnapshot) -> String {
+    let filled = match snapshot.total {
+        0 => 0,
+        total if snapshot.total_known => BAR_WIDTH * snapshot.completed.min(total) / total,
+        _ => 0,
+    };
+    format!(
+        "Checking dependencies [{}{}] {}/{}{}",
+        "#".repeat(filled),
+        "-".repeat(BAR_WIDTH - filled),
+        snapshot.completed,
+        snapshot.total,
+        if snapshot.total_known { "" } else { "+" }
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_waits_for_total() {
+        let parsing = ProgressSnapshot {
+            total: 40,
+            completed: 10,
+            total_known: false,
+        };
+        assert_eq!(render(parsing), format!("Checking dependencies [{}] 10/40+", "-".repeat(30)));
+
+        let parsed = ProgressSnapshot {
+            total_known: true,
+            ..parsing
+        };
+        assert_eq!(
+            render(parsed),
+            format!("Checking dependencies [{}{}] 10/40", "#".repeat(7), "-".repeat(23))
+        );
+    }
+}
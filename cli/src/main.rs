@@ -1,6 +1,7 @@
 mod commands;
 mod scan;
 mod display;
+mod tui;
 
 use clap::Parser;
 use anyhow::Result;
@@ -12,6 +13,8 @@ use std::path::PathBuf;
 struct Cli {
     #[command(subcommand)]
     command: Commands,
+    #[arg(long, global = true, help = "Never color the output (also off when piped or NO_COLOR is set)")]
+    no_color: bool,
 }
 
 #[derive(Parser)]
@@ -21,19 +24,38 @@ enum Commands {
 
     #[command(about = "List supported ecosystems")]
     Ecosystems(commands::Ecosystems),
+
+    #[command(about = "Check that the config, cache and package sources are usable")]
+    Doctor(commands::Doctor),
+
+    #[command(about = "Write a commented rensa.yaml for the project in the current directory")]
+    Init(commands::Init),
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
+    display::set_color(cli.no_color);
 
     match cli.command {
         Commands::Check(cmd) => {
-            cmd.run().await?;
+            let code = cmd.run().await?;
+            if code != 0 {
+                std::process::exit(code);
+            }
         }
         Commands::Ecosystems(cmd) => {
             cmd.run();
         }
+        Commands::Init(cmd) => {
+            cmd.run().await?;
+        }
+        Commands::Doctor(cmd) => {
+            let code = cmd.run().await?;
+            if code != 0 {
+                std::process::exit(code);
+            }
+        }
     }
 
     Ok(())
@@ -1,18 +1,364 @@
 use anyhow::Result;
-use std::path::PathBuf;
-use rensa_core::{PluginRegistry, scan_path, ScanReport};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use rensa_core::scanner::Scanner;
+use rensa_core::checkpoint::Checkpoint;
+use rensa_core::eol::{EolChecker, EolClient, DEFAULT_EOL_API};
+use rensa_core::config::UnresolvedPolicy;
+use rensa_core::denylist::PackageDenylist;
+use rensa_core::filter::DependencyFilter;
+use rensa_core::license::LicensePolicy;
+use rensa_core::progress::ScanProgress;
+use rensa_core::{DependencyScope, Ecosystem, EolConfig, PluginRegistry, ScanReport, SeverityThreshold};
 
 #[cfg(feature = "composer")]
 use rensa_plugin_composer::ComposerPlugin;
 
-pub async fn run_scan(path: &PathBuf) -> Result<ScanReport> {
+#[cfg(feature = "npm")]
+use rensa_plugin_npm::NpmPlugin;
+
+#[cfg(feature = "pub")]
+use rensa_plugin_pub::PubPlugin;
+
+#[cfg(feature = "terraform")]
+use rensa_plugin_terraform::TerraformPlugin;
+
+#[derive(Debug, Default, Clone)]
+pub struct ScanOptions {
+    pub limit: Option<usize>,
+    pub fail_fast: Option<SeverityThreshold>,
+    pub registry_timeout: Option<Duration>,
+    pub osv_timeout: Option<Duration>,
+    /// OSV-compatible API queried instead of the public one.
+    pub osv_url: Option<String>,
+    pub include_related: bool,
+    /// Advisories rated below this are dropped as OSV returns them.
+    pub severity_floor: Option<SeverityThreshold>,
+    /// Levels of locked transitive dependencies to scan; 0 is direct only.
+    pub transitive_depth: usize,
+    pub eol: Option<EolConfig>,
+    pub severity_threshold: SeverityThreshold,
+    pub overrides: HashMap<String, String>,
+    pub include_submodules: bool,
+    pub max_file_size: Option<u64>,
+    pub concurrency: Option<usize>,
+    pub max_concurrency: Option<usize>,
+    pub licenses: LicensePolicy,
+    pub denylist: PackageDenylist,
+    /// Advisory ids left out of the report.
+    pub ignore: Vec<String>,
+    /// Names of the dependencies to scan; empty scans them all.
+    pub filter: DependencyFilter,
+    pub unresolved: UnresolvedPolicy,
+    pub groups: Vec<DependencyScope>,
+    /// Compiled-in plugins to leave out of the registry.
+    pub disabled_ecosystems: Vec<Ecosystem>,
+    /// Private registries queried instead of the public ones.
+    pub registries: HashMap<Ecosystem, rensa_core::config::RegistryConfig>,
+    /// Where whole results are cached for manifests with a lockfile.
+    pub cache_dir: Option<PathBuf>,
+    /// composer.json sections to parse instead of the default ones.
+    pub composer_sections: Option<Vec<String>>,
+    /// Checkpoint file finished lookups are recorded in, and resumed from.
+    pub resume: Option<PathBuf>,
+    /// Records what was looked up for each dependency.
+    pub trace: bool,
+    /// Counts dependencies queued and checked, for a progress bar.
+    pub progress: Option<Arc<ScanProgress>>,
+}
+
+/// Registers the compiled-in plugins, minus any `disabled_ecosystems`.
+pub fn build_registry(options: &ScanOptions) -> PluginRegistry {
     let mut registry = PluginRegistry::new();
+    let enabled = |ecosystem: Ecosystem| !options.disabled_ecosystems.contains(&ecosystem);
+    // Each request in flight may need its own connection.
+    let pool_size = options.max_concurrency.or(options.concurrency);
 
     #[cfg(feature = "composer")]
-    {
-        registry.register_plugin(ComposerPlugin::new());
+    if enabled(Ecosystem::Composer) {
+        let mut plugin = ComposerPlugin::new().with_transitive_depth(options.transitive_depth);
+        if let Some(sections) = &options.composer_sections {
+            plugin = plugin.with_sections(sections.clone());
+        }
+        if let Some(timeout) = options.registry_timeout {
+            plugin = plugin.with_registry_timeout(timeout);
+        }
+        if let Some(timeout) = options.osv_timeout {
+            plugin = plugin.with_osv_timeout(timeout);
+        }
+        if let Some(url) = &options.osv_url {
+            plugin = plugin.with_osv_url(url.clone());
+        }
+        plugin = plugin.with_include_related(options.include_related);
+        if let Some(floor) = options.severity_floor {
+            plugin = plugin.with_severity_floor(floor);
+        }
+        if let Some(bytes) = options.max_file_size {
+            plugin = plugin.with_max_file_size(bytes);
+        }
+        if let Some(concurrency) = pool_size {
+            plugin = plugin.with_concurrency(concurrency);
+        }
+        if let Some(config) = options.registries.get(&Ecosystem::Composer) {
+            plugin = plugin.with_registry_config(config.clone());
+        }
+        registry.register_plugin(plugin);
+    }
+
+    #[cfg(feature = "npm")]
+    if enabled(Ecosystem::Npm) {
+        let mut plugin = NpmPlugin::new();
+        if let Some(timeout) = options.registry_timeout {
+            plugin = plugin.with_registry_timeout(timeout);
+        }
+        if let Some(timeout) = options.osv_timeout {
+            plugin = plugin.with_osv_timeout(timeout);
+        }
+        if let Some(url) = &options.osv_url {
+            plugin = plugin.with_osv_url(url.clone());
+        }
+        plugin = plugin.with_include_related(options.include_related);
+        if let Some(floor) = options.severity_floor {
+            plugin = plugin.with_severity_floor(floor);
+        }
+        if let Some(bytes) = options.max_file_size {
+            plugin = plugin.with_max_file_size(bytes);
+        }
+        if let Some(concurrency) = pool_size {
+            plugin = plugin.with_concurrency(concurrency);
+        }
+        if let Some(config) = options.registries.get(&Ecosystem::Npm) {
+            plugin = plugin.with_registry_config(config.clone());
+        }
+        registry.register_plugin(plugin);
+    }
+
+    #[cfg(feature = "pub")]
+    if enabled(Ecosystem::Pub) {
+        let mut plugin = PubPlugin::new();
+        if let Some(timeout) = options.registry_timeout {
+            plugin = plugin.with_registry_timeout(timeout);
+        }
+        if let Some(timeout) = options.osv_timeout {
+            plugin = plugin.with_osv_timeout(timeout);
+        }
+        if let Some(url) = &options.osv_url {
+            plugin = plugin.with_osv_url(url.clone());
+        }
+        plugin = plugin.with_include_related(options.include_related);
+        if let Some(floor) = options.severity_floor {
+            plugin = plugin.with_severity_floor(floor);
+        }
+        if let Some(bytes) = options.max_file_size {
+            plugin = plugin.with_max_file_size(bytes);
+        }
+        if let Some(concurrency) = pool_size {
+            plugin = plugin.with_concurrency(concurrency);
+        }
+        if let Some(config) = options.registries.get(&Ecosystem::Pub) {
+            plugin = plugin.with_registry_config(config.clone());
+        }
+        registry.register_plugin(plugin);
+    }
+
+    #[cfg(feature = "terraform")]
+    if enabled(Ecosystem::Terraform) {
+        let mut plugin = TerraformPlugin::new();
+        if let Some(timeout) = options.registry_timeout {
+            plugin = plugin.with_registry_timeout(timeout);
+        }
+        if let Some(bytes) = options.max_file_size {
+            plugin = plugin.with_max_file_size(bytes);
+        }
+        if let Some(concurrency) = pool_size {
+            plugin = plugin.with_concurrency(concurrency);
+        }
+        if let Some(config) = options.registries.get(&Ecosystem::Terraform) {
+            plugin = plugin.with_registry_config(config.clone());
+        }
+        registry.register_plugin(plugin);
+    }
+
+    registry
+}
+
+/// The registry and vulnerability database URLs a scan with these options
+/// may query, for the audit log.
+pub fn endpoints(options: &ScanOptions) -> Vec<String> {
+    let registry = build_registry(options);
+    let mut endpoints: Vec<String> = registry
+        .registry_clients()
+        .filter_map(|client| client.endpoint())
+        .map(str::to_string)
+        .collect();
+    endpoints.push(options.osv_url.clone().unwrap_or_else(|| rensa_core::osv::DEFAULT_OSV_API.to_string()));
+    if let Some(eol) = &options.eol {
+        endpoints.push(eol.api_url.clone().unwrap_or_else(|| DEFAULT_EOL_API.to_string()));
+    }
+    endpoints
+}
+
+fn eol_checker(config: &EolConfig) -> EolChecker {
+    let url = config.api_url.as_deref().unwrap_or(DEFAULT_EOL_API);
+    config
+        .products
+        .iter()
+        .fold(EolChecker::new(EolClient::new(url)), |checker, (name, product)| {
+            checker.with_product(name, product)
+        })
+}
+
+pub async fn run_scan(paths: &[PathBuf], options: &ScanOptions) -> Result<ScanReport> {
+    let registry = build_registry(options);
+    let report = path_scanner(&registry, options)?.scan_paths(paths).await?;
+    remove_checkpoint(options)?;
+    Ok(report)
+}
+
+/// Scans `paths` one manifest at a time, writing each result to `out` as a
+/// JSON line as soon as it is ready. Returns the summary, without the
+/// per-dependency lists.
+pub async fn run_streaming_scan(paths: &[PathBuf], options: &ScanOptions, out: &mut impl std::io::Write) -> Result<ScanReport> {
+    let registry = build_registry(options);
+    let report = path_scanner(&registry, options)?
+        .scan_streaming(paths, |result| {
+            serde_json::to_writer(&mut *out, result).map_err(std::io::Error::from)?;
+            out.write_all(b"\n")?;
+            Ok(())
+        })
+        .await?;
+    remove_checkpoint(options)?;
+    Ok(report)
+}
+
+/// A completed scan has no use for its checkpoint any more.
+fn remove_checkpoint(options: &ScanOptions) -> Result<()> {
+    match options.resume.as_deref().map(std::fs::remove_file) {
+        Some(Err(e)) if e.kind() != std::io::ErrorKind::NotFound => Err(e.into()),
+        _ => Ok(()),
+    }
+}
+
+pub(crate) fn path_scanner<'a>(registry: &'a PluginRegistry, options: &ScanOptions) -> Result<Scanner<'a>> {
+    let mut scanner = Scanner::new(registry)
+        .with_severity_threshold(options.severity_threshold)
+        .with_overrides(options.overrides.clone())
+        .with_license_policy(options.licenses.clone())
+        .with_denylist(options.denylist.clone())
+        .with_ignored_advisories(options.ignore.clone())
+        .with_unresolved_policy(options.unresolved)
+        .with_include_submodules(options.include_submodules)
+        .with_groups(options.groups.clone())
+        .with_filter(options.filter.clone())
+        .with_trace(options.trace);
+    if let Some(limit) = options.limit {
+        scanner = scanner.with_limit(limit);
+    }
+    if let Some(threshold) = options.fail_fast {
+        scanner = scanner.with_fail_fast(threshold);
+    }
+    if let Some(eol) = &options.eol {
+        scanner = scanner.with_eol(eol_checker(eol));
+    }
+    if let Some(concurrency) = options.concurrency {
+        scanner = scanner.with_concurrency(concurrency);
+    }
+    if let Some(max) = options.max_concurrency {
+        scanner = scanner.with_max_concurrency(max);
+    }
+    if let Some(dir) = &options.cache_dir {
+        scanner = scanner.with_result_cache(rensa_core::CacheManager::new(dir.clone()));
+    }
+    if let Some(path) = &options.resume {
+        let checkpoint = Checkpoint::open(path.clone())?;
+        if !checkpoint.is_empty() {
+            eprintln!("Resuming from {} ({} lookups already done)", path.display(), checkpoint.len());
+        }
+        scanner = scanner.with_checkpoint(checkpoint);
+    }
+    if let Some(progress) = &options.progress {
+        scanner = scanner.with_progress(progress.clone());
+    }
+    Ok(scanner)
+}
+
+/// Scans the packages listed by `composer show --format=json`.
+#[cfg(feature = "composer")]
+pub async fn run_composer_show_scan(file: &Path, options: &ScanOptions) -> Result<ScanReport> {
+    let registry = build_registry(options);
+    let content = std::fs::read_to_string(file)?;
+    let mut deps = rensa_plugin_composer::show::parse_composer_show(&content, file)?;
+    deps.retain(|d| options.filter.matches(&d.name));
+
+    let mut scanner = Scanner::new(&registry)
+        .with_severity_threshold(options.severity_threshold)
+        .with_overrides(options.overrides.clone())
+        .with_license_policy(options.licenses.clone())
+        .with_denylist(options.denylist.clone())
+        .with_ignored_advisories(options.ignore.clone())
+        .with_unresolved_policy(options.unresolved)
+        .with_trace(options.trace);
+    if let Some(limit) = options.limit {
+        scanner = scanner.with_limit(limit);
+    }
+    if let Some(threshold) = options.fail_fast {
+        scanner = scanner.with_fail_fast(threshold);
+    }
+    if let Some(concurrency) = options.concurrency {
+        scanner = scanner.with_concurrency(concurrency);
+    }
+    if let Some(max) = options.max_concurrency {
+        scanner = scanner.with_max_concurrency(max);
+    }
+    if let Some(progress) = &options.progress {
+        scanner = scanner.with_progress(progress.clone());
     }
 
-    let report = scan_path(path.clone(), &registry).await?;
+    let report = scanner.scan_installed(file.to_path_buf(), rensa_core::Ecosystem::Composer, deps).await?;
     Ok(report)
 }
+
+#[cfg(not(feature = "composer"))]
+pub async fn run_composer_show_scan(_file: &Path, _options: &ScanOptions) -> Result<ScanReport> {
+    anyhow::bail!("--composer-show requires rensa to be built with the composer feature")
+}
+
+pub async fn run_sbom_scan(sbom: &Path, options: &ScanOptions) -> Result<ScanReport> {
+    let registry = build_registry(options);
+
+    let mut scanner = Scanner::new(&registry)
+        .with_severity_threshold(options.severity_threshold)
+        .with_denylist(options.denylist.clone())
+        .with_ignored_advisories(options.ignore.clone());
+    if let Some(max) = options.max_concurrency {
+        scanner = scanner.with_max_concurrency(max);
+    }
+    if let Some(progress) = &options.progress {
+        scanner = scanner.with_progress(progress.clone());
+    }
+
+    let report = scanner.scan_sbom(sbom.to_path_buf()).await?;
+    Ok(report)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(feature = "composer")]
+    #[test]
+    fn test_disabled_ecosystem_is_not_registered() {
+        let registry = build_registry(&ScanOptions::default());
+        assert!(registry.get_detector(&Ecosystem::Composer).is_some());
+
+        let options = ScanOptions {
+            disabled_ecosystems: vec![Ecosystem::Composer],
+            ..ScanOptions::default()
+        };
+        let registry = build_registry(&options);
+        assert!(registry.get_detector(&Ecosystem::Composer).is_none());
+        assert!(registry.get_parser(&Ecosystem::Composer).is_none());
+    }
+}
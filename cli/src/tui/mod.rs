@@ -0,0 +1,379 @@
+//! Interactive browser for a finished scan (`rensa check --interactive`).
+
+use crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use crossterm::terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen};
+use crossterm::ExecutableCommand;
+use ratatui::backend::CrosstermBackend;
+use ratatui::layout::{Constraint, Direction, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{Frame, Terminal};
+use rensa_core::{ScanReport, Severity, SeverityThreshold, UpdateInfo, Vulnerability};
+use std::collections::BTreeMap;
+use std::io::stdout;
+
+/// A dependency and everything the scan found for it.
+struct Entry<'a> {
+    name: String,
+    version: String,
+    update: Option<&'a UpdateInfo>,
+    vulnerabilities: Vec<&'a Vulnerability>,
+}
+
+impl Entry<'_> {
+    fn worst_severity(&self) -> Option<Severity> {
+        self.vulnerabilities.iter().map(|v| v.severity).max()
+    }
+}
+
+struct App<'a> {
+    entries: Vec<Entry<'a>>,
+    /// Only dependencies with a vulnerability at or above this are listed.
+    filter: Option<SeverityThreshold>,
+    visible: Vec<usize>,
+    list: ListState,
+    detail_scroll: u16,
+}
+
+impl<'a> App<'a> {
+    fn new(report: &'a ScanReport) -> Self {
+        let mut entries: BTreeMap<String, Entry<'a>> = BTreeMap::new();
+
+        let mut results: Vec<_> = report.ecosystem_results.values().collect();
+        results.sort_by_key(|r| r.ecosystem);
+        for dep in results.iter().flat_map(|r| &r.dependencies) {
+            entries.entry(dep.name.clone()).or_insert_with(|| Entry {
+                name: dep.name.clone(),
+                version: dep.version.clone(),
+                update: None,
+                vulnerabilities: Vec::new(),
+            });
+        }
+
+        for update in &report.updates {
+            let name = &update.dependency.name;
+            let entry = entries.entry(name.clone()).or_insert_with(|| Entry {
+                name: name.clone(),
+                version: update.current_version.clone(),
+                update: None,
+                vulnerabilities: Vec::new(),
+            });
+            entry.update = Some(update);
+        }
+
+        for vuln in &report.vulnerabilities {
+            let name = vuln.package.clone().unwrap_or_else(|| "(unknown package)".to_string());
+            entries
+                .entry(name.clone())
+                .or_insert_with(|| Entry {
+                    name,
+                    version: String::new(),
+                    update: None,
+                    vulnerabilities: Vec::new(),
+                })
+                .vulnerabilities
+                .push(vuln);
+        }
+
+        // Most severe first, then dependencies with updates, then the rest.
+        let mut entries: Vec<_> = entries.into_values().collect();
+        entries.sort_by(|a, b| {
+            b.worst_severity()
+                .cmp(&a.worst_severity())
+                .then(b.update.is_some().cmp(&a.update.is_some()))
+                .then(a.name.cmp(&b.name))
+        });
+
+        let mut app = Self {
+            entries,
+            filter: None,
+            visible: Vec::new(),
+            list: ListState::default(),
+            detail_scroll: 0,
+        };
+        app.apply_filter();
+        app
+    }
+
+    fn apply_filter(&mut self) {
+        self.visible = self
+            .entries
+            .iter()
+            .enumerate()
+            .filter(|(_, e)| match self.filter {
+                Some(threshold) => e.vulnerabilities.iter().any(|v| v.severity.meets_threshold(threshold)),
+                None => true,
+            })
+            .map(|(i, _)| i)
+            .collect();
+
+        self.list.select(if self.visible.is_empty() { None } else { Some(0) });
+        self.detail_scroll = 0;
+    }
+
+    /// Steps through all -> low -> medium -> high -> critical -> all.
+    fn cycle_filter(&mut self) {
+        self.filter = match self.filter {
+            None => Some(SeverityThreshold::Low),
+            Some(SeverityThreshold::Low) => Some(SeverityThreshold::Medium),
+            Some(SeverityThreshold::Medium) => Some(SeverityThreshold::High),
+            Some(SeverityThreshold::High) => Some(SeverityThreshold::Critical),
+            Some(SeverityThreshold::Critical) => None,
+        };
+        self.apply_filter();
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        if self.visible.is_empty() {
+            return;
+        }
+        let last = self.visible.len() as isize - 1;
+        let current = self.list.selected().unwrap_or(0) as isize;
+        self.list.select(Some((current + delta).clamp(0, last) as usize));
+        self.detail_scroll = 0;
+    }
+
+    fn selected(&self) -> Option<&Entry<'a>> {
+        self.list
+            .selected()
+            .and_then(|i| self.visible.get(i))
+            .map(|&i| &self.entries[i])
+    }
+
+    /// Handles a key press; returns false when the user wants to quit.
+    fn handle_key(&mut self, key: KeyCode) -> bool {
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => return false,
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::Home | KeyCode::Char('g') => self.move_selection(isize::MIN / 2),
+            KeyCode::End | KeyCode::Char('G') => self.move_selection(isize::MAX / 2),
+            KeyCode::Char('s') => self.cycle_filter(),
+            KeyCode::Char('J') => self.detail_scroll = self.detail_scroll.saturating_add(1),
+            KeyCode::Char('K') => self.detail_scroll = self.detail_scroll.saturating_sub(1),
+            _ => {}
+        }
+        true
+    }
+}
+
+/// Shows the report in a full-screen browser until the user quits.
+pub fn run(report: &ScanReport) -> anyhow::Result<()> {
+    enable_raw_mode()?;
+    stdout().execute(EnterAlternateScreen)?;
+
+    let result = event_loop(report);
+
+    // Always hand the terminal back, even if drawing failed.
+    disable_raw_mode()?;
+    stdout().execute(LeaveAlternateScreen)?;
+    result
+}
+
+fn event_loop(report: &ScanReport) -> anyhow::Result<()> {
+    let mut terminal = Terminal::new(CrosstermBackend::new(stdout()))?;
+    let mut app = App::new(report);
+
+    loop {
+        terminal.draw(|frame| draw(frame, &mut app))?;
+
+        if let Event::Key(key) = event::read()? {
+            if key.kind == KeyEventKind::Press && !app.handle_key(key.code) {
+                return Ok(());
+            }
+        }
+    }
+}
+
+fn draw(frame: &mut Frame, app: &mut App) {
+    let rows = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(1), Constraint::Length(1)])
+        .split(frame.size());
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(rows[0]);
+
+    let items: Vec<ListItem> = app
+        .visible
+        .iter()
+        .map(|&i| list_item(&app.entries[i]))
+        .collect();
+    let title = match app.filter {
+        Some(threshold) => format!(" Dependencies ({}, {}+) ", app.visible.len(), threshold.as_str()),
+        None => format!(" Dependencies ({}) ", app.visible.len()),
+    };
+    let list = List::new(items)
+        .block(Block::default().borders(Borders::ALL).title(title))
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED))
+        .highlight_symbol("> ");
+    frame.render_stateful_widget(list, columns[0], &mut app.list);
+
+    let details = Paragraph::new(app.selected().map(detail_lines).unwrap_or_default())
+        .block(Block::default().borders(Borders::ALL).title(" Details "))
+        .wrap(Wrap { trim: false })
+        .scroll((app.detail_scroll, 0));
+    frame.render_widget(details, columns[1]);
+
+    let help = "q quit  j/k move  J/K scroll details  s severity filter  g/G first/last";
+    frame.render_widget(Paragraph::new(help).style(Style::default().fg(Color::DarkGray)), rows[1]);
+}
+
+fn list_item<'a>(entry: &Entry) -> ListItem<'a> {
+    let mut spans = Vec::new();
+
+    match entry.worst_severity() {
+        Some(severity) => spans.push(Span::styled(
+            format!("{:<8} ", severity.as_str()),
+            Style::default().fg(severity_color(severity)),
+        )),
+        None => spans.push(Span::raw(" ".repeat(9))),
+    }
+    spans.push(Span::styled(
+        if entry.update.is_some() { "^ " } else { "  " },
+        Style::default().fg(Color::Cyan),
+    ));
+    spans.push(Span::raw(entry.name.clone()));
+
+    ListItem::new(Line::from(spans))
+}
+
+fn detail_lines<'a>(entry: &Entry) -> Vec<Line<'a>> {
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut lines = vec![Line::from(Span::styled(entry.name.clone(), bold))];
+
+    if !entry.version.is_empty() {
+        lines.push(Line::from(format!("Version: {}", entry.version)));
+    }
+
+    match entry.update {
+        Some(update) => {
+            lines.push(Line::from(format!(
+                "Update: {} -> {}",
+                update.current_version, update.latest_version
+            )));
+            if let Some(compatible) = &update.latest_in_constraint {
+                lines.push(Line::from(format!("Latest within constraint: {}", compatible)));
+            }
+            if let Some(changelog) = &update.changelog {
+                lines.push(Line::from(format!("Changelog: {}", changelog)));
+            }
+            if !update.intermediate_releases.is_empty() {
+                lines.push(Line::from("Release notes:"));
+                for note in &update.intermediate_releases {
+                    lines.push(Line::from(format!("  {}: {}", note.version, note.url)));
+                }
+            }
+        }
+        None => lines.push(Line::from("Update: up to date")),
+    }
+
+    for vuln in &entry.vulnerabilities {
+        lines.push(Line::default());
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!("[{}] ", vuln.severity.as_str()),
+                Style::default().fg(severity_color(vuln.severity)),
+            ),
+            Span::styled(vuln.id.clone(), bold),
+        ]));
+        lines.push(Line::from(vuln.summary.clone()));
+        if !vuln.fixed_versions.is_empty() {
+            lines.push(Line::from(format!("Fixed in: {}", vuln.fixed_versions.join(", "))));
+        }
+        if !vuln.details.is_empty() {
+            lines.push(Line::default());
+            lines.extend(vuln.details.lines().map(|l| Line::from(l.to_string())));
+        }
+        for reference in &vuln.references {
+            lines.push(Line::from(format!("  {}", reference)));
+        }
+    }
+
+    lines
+}
+
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::Critical => Color::Magenta,
+        Severity::High => Color::Red,
+        Severity::Medium => Color::Yellow,
+        Severity::Low => Color::Blue,
+        Severity::Unknown => Color::Gray,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn vulnerability(package: &str, severity: Severity) -> Vulnerability {
+        Vulnerability {
+            id: format!("GHSA-{}", package),
+            summary: "Test advisory".to_string(),
+            details: String::new(),
+            severity,
+            affected_versions: Vec::new(),
+            fixed_versions: vec!["2.0.0".to_string()],
+            references: Vec::new(),
+            package: Some(package.to_string()),
+            related_to: None,
+            remediation: None,
+        }
+    }
+
+    fn report() -> ScanReport {
+        let mut report = ScanReport::new(PathBuf::from("."));
+        report.vulnerabilities.push(vulnerability("low/pkg", Severity::Low));
+        report.vulnerabilities.push(vulnerability("critical/pkg", Severity::Critical));
+        report.vulnerabilities.push(vulnerability("high/pkg", Severity::High));
+        report
+    }
+
+    #[test]
+    fn test_entries_sorted_by_severity() {
+        let report = report();
+        let app = App::new(&report);
+
+        let names: Vec<_> = app.entries.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["critical/pkg", "high/pkg", "low/pkg"]);
+        assert_eq!(app.selected().unwrap().name, "critical/pkg");
+    }
+
+    #[test]
+    fn test_severity_filter_cycles() {
+        let report = report();
+        let mut app = App::new(&report);
+
+        app.cycle_filter();
+        assert_eq!(app.visible.len(), 3);
+        app.cycle_filter();
+        assert_eq!(app.visible.len(), 2);
+        app.cycle_filter();
+        assert_eq!(app.visible.len(), 2);
+        app.cycle_filter();
+        assert_eq!(app.visible.len(), 1);
+        app.cycle_filter();
+        assert_eq!(app.filter, None);
+        assert_eq!(app.visible.len(), 3);
+    }
+
+    #[test]
+    fn test_navigation_is_clamped() {
+        let report = report();
+        let mut app = App::new(&report);
+
+        assert!(app.handle_key(KeyCode::Up));
+        assert_eq!(app.list.selected(), Some(0));
+        app.handle_key(KeyCode::Char('G'));
+        assert_eq!(app.selected().unwrap().name, "low/pkg");
+        app.handle_key(KeyCode::Down);
+        assert_eq!(app.list.selected(), Some(2));
+        assert!(!app.handle_key(KeyCode::Char('q')));
+    }
+}
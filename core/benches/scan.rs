@@ -0,0 +1,165 @@
+//! Scans a generated fixture tree with in-memory plugins, so the numbers
+//! only reflect rensa's own overhead and don't depend on the network.
+//!
+//! Run with `cargo bench -p rensa-core`.
+
+use async_trait::async_trait;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rensa_core::plugin::{Detector, Parser, PluginRegistry, RegistryClient, VulnerabilityScanner};
+use rensa_core::scanner::Scanner;
+use rensa_core::types::{Dependency, DependencyFile, DependencyScope, Ecosystem, Severity, VersionConstraint, Vulnerability};
+use rensa_core::Result;
+use std::path::Path;
+use tempfile::TempDir;
+
+const MANIFEST: &str = "composer.json";
+
+struct FixtureDetector;
+
+#[async_trait]
+impl Detector for FixtureDetector {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Composer
+    }
+
+    async fn detect(&self, path: &Path) -> Result<Vec<DependencyFile>> {
+        let mut files = Vec::new();
+        for entry in walkdir::WalkDir::new(path).into_iter().filter_map(|e| e.ok()) {
+            if entry.file_name() == MANIFEST {
+                files.push(DependencyFile {
+                    ecosystem: Ecosystem::Composer,
+                    path: entry.path().to_path_buf(),
+                    content: std::fs::read_to_string(entry.path())?,
+                });
+            }
+        }
+        Ok(files)
+    }
+}
+
+struct FixtureParser;
+
+#[async_trait]
+impl Parser for FixtureParser {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Composer
+    }
+
+    async fn parse(&self, file: &DependencyFile) -> Result<Vec<Dependency>> {
+        let manifest: serde_json::Value =
+            serde_json::from_str(&file.content).map_err(|e| rensa_core::RensaError::ParseError {
+                file: file.path.clone(),
+                source: e,
+            })?;
+
+        Ok(manifest["require"]
+            .as_object()
+            .into_iter()
+            .flatten()
+            .map(|(name, version)| {
+                let version = version.as_str().unwrap_or_default().to_string();
+                Dependency {
+                    name: name.clone(),
+                    ecosystem: Ecosystem::Composer,
+                    scope: DependencyScope::Runtime,
+                    constraint: VersionConstraint::Exact(version.clone()),
+                    version,
+                    file: file.path.clone(),
+                    repository: None,
+                    license: None,
+                    provenance: None,
+                    direct: true,
+                    trace: None,
+                }
+            })
+            .collect())
+    }
+}
+
+struct FixtureRegistry;
+
+#[async_trait]
+impl RegistryClient for FixtureRegistry {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Composer
+    }
+
+    async fn get_latest_version(&self, _dependency: &Dependency) -> Result<Option<String>> {
+        Ok(Some("2.0.0".to_string()))
+    }
+}
+
+struct FixtureScanner;
+
+#[async_trait]
+impl VulnerabilityScanner for FixtureScanner {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Composer
+    }
+
+    async fn scan(&self, dependency: &Dependency) -> Result<Vec<Vulnerability>> {
+        if !dependency.name.ends_with("/pkg0") {
+            return Ok(Vec::new());
+        }
+        Ok(vec![Vulnerability {
+            id: "GHSA-bench".to_string(),
+            summary: "Benchmark advisory".to_string(),
+            details: String::new(),
+            severity: Severity::High,
+            affected_versions: Vec::new(),
+            fixed_versions: vec!["2.0.0".to_string()],
+            references: Vec::new(),
+            package: None,
+            related_to: None,
+            remediation: None,
+        }])
+    }
+}
+
+/// Writes `projects` manifests with `deps` requirements each.
+fn fixture_tree(projects: usize, deps: usize) -> TempDir {
+    let dir = TempDir::new().unwrap();
+    for p in 0..projects {
+        let project = dir.path().join(format!("project{}", p));
+        std::fs::create_dir_all(&project).unwrap();
+
+        let require: serde_json::Map<_, _> = (0..deps)
+            .map(|d| (format!("vendor{}/pkg{}", p, d), serde_json::json!("1.0.0")))
+            .collect();
+        let manifest = serde_json::json!({ "name": format!("bench/project{}", p), "require": require });
+        std::fs::write(project.join(MANIFEST), manifest.to_string()).unwrap();
+    }
+    dir
+}
+
+fn plugins() -> PluginRegistry {
+    let mut registry = PluginRegistry::new();
+    registry.register_detector(Box::new(FixtureDetector));
+    registry.register_parser(Box::new(FixtureParser));
+    registry.register_registry_client(Box::new(FixtureRegistry));
+    registry.register_vulnerability_scanner(Box::new(FixtureScanner));
+    registry
+}
+
+fn bench_scan(c: &mut Criterion) {
+    let runtime = tokio::runtime::Runtime::new().unwrap();
+    let registry = plugins();
+    let mut group = c.benchmark_group("scan_path");
+
+    for (projects, deps) in [(1, 50), (10, 50), (50, 20)] {
+        let tree = fixture_tree(projects, deps);
+        group.bench_with_input(
+            BenchmarkId::from_parameter(format!("{}x{}", projects, deps)),
+            &tree,
+            |b, tree| {
+                b.to_async(&runtime)
+                    .iter(|| async { Scanner::new(&registry).scan(tree.path().to_path_buf()).await });
+            },
+        );
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, bench_scan);
+criterion_main!(benches);
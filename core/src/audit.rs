@@ -0,0 +1,75 @@
+use crate::error::{RensaError, Result};
+use crate::report::{ScanReport, ScanSummary};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+/// One line of the audit log: what was scanned, when, by which version of
+/// Rensa and against which data sources.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuditEntry {
+    pub timestamp: DateTime<Utc>,
+    pub rensa_version: String,
+    pub paths: Vec<PathBuf>,
+    pub total_dependencies: usize,
+    pub summary: ScanSummary,
+    /// Registry and vulnerability database URLs the scan could query.
+    pub endpoints: Vec<String>,
+}
+
+impl AuditEntry {
+    pub fn new(report: &ScanReport, paths: Vec<PathBuf>, endpoints: Vec<String>) -> Self {
+        Self {
+            timestamp: report.timestamp,
+            rensa_version: report.rensa_version.clone(),
+            paths,
+            total_dependencies: report.total_dependencies,
+            summary: report.summary.clone(),
+            endpoints,
+        }
+    }
+}
+
+/// Appends `entry` to the JSONL log at `path`, creating it if needed. The
+/// line goes out in a single write on a file opened for appending, so
+/// concurrent scans logging to the same file don't interleave.
+pub fn append(path: &Path, entry: &AuditEntry) -> Result<()> {
+    let mut line = serde_json::to_vec(entry).map_err(|e| RensaError::Plugin {
+        message: format!("failed to serialize audit entry: {}", e),
+    })?;
+    line.push(b'\n');
+
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(&line)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_each_scan_appends_one_line() {
+        let temp_dir = TempDir::new().unwrap();
+        let log = temp_dir.path().join("audit.jsonl");
+        let endpoints = vec!["https://api.osv.dev".to_string()];
+
+        let mut report = ScanReport::new(PathBuf::from("/app"));
+        report.total_dependencies = 12;
+        append(&log, &AuditEntry::new(&report, vec![PathBuf::from("/app")], endpoints.clone())).unwrap();
+        report.summary.vulnerabilities_found = 1;
+        append(&log, &AuditEntry::new(&report, vec![PathBuf::from("/app")], endpoints)).unwrap();
+
+        let content = std::fs::read_to_string(&log).unwrap();
+        let entries: Vec<AuditEntry> = content.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].total_dependencies, 12);
+        assert_eq!(entries[0].summary.vulnerabilities_found, 0);
+        assert_eq!(entries[1].summary.vulnerabilities_found, 1);
+        assert_eq!(entries[1].endpoints, vec!["https://api.osv.dev"]);
+        assert!(content.ends_with('\n'));
+    }
+}
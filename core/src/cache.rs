@@ -1,6 +1,11 @@
+use crate::error::RensaError;
+use futures::future::{BoxFuture, FutureExt, Shared};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
+use std::future::Future;
 use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 use std::time::{Duration, SystemTime};
 
 const DEFAULT_TTL: Duration = Duration::from_secs(24 * 60 * 60); // 24 hours
@@ -182,6 +187,51 @@ impl CacheManager {
     }
 }
 
+type SharedFetch = Shared<BoxFuture<'static, Result<serde_json::Value, Arc<RensaError>>>>;
+
+/// In-memory single-flight layer: concurrent lookups of the same key await
+/// one shared fetch instead of each hitting the network and the disk cache.
+#[derive(Clone, Default)]
+pub struct InFlight {
+    pending: Arc<Mutex<HashMap<String, SharedFetch>>>,
+}
+
+impl InFlight {
+    /// Runs `fetch` unless a fetch for `key` is already in flight, in which
+    /// case its result is awaited instead. The lock is never held across an
+    /// await point.
+    pub async fn run<F>(&self, key: &str, fetch: F) -> crate::Result<serde_json::Value>
+    where
+        F: Future<Output = crate::Result<serde_json::Value>> + Send + 'static,
+    {
+        let shared = {
+            let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            pending
+                .entry(key.to_string())
+                .or_insert_with(|| fetch.map(|r| r.map_err(Arc::new)).boxed().shared())
+                .clone()
+        };
+
+        let result = shared.clone().await;
+
+        // A later fetch may have taken the key once the first waiter to
+        // finish removed it; only this fetch's own entry is removed.
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        if pending.get(key).is_some_and(|current| current.ptr_eq(&shared)) {
+            pending.remove(key);
+        }
+
+        result.map_err(RensaError::Shared)
+    }
+}
+
+impl std::fmt::Debug for InFlight {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let pending = self.pending.lock().map(|p| p.len()).unwrap_or(0);
+        f.debug_struct("InFlight").field("pending", &pending).finish()
+    }
+}
+
 pub fn sanitize_cache_key(key: &str) -> String {
     key.replace('/', "-")
         .replace(':', "_")
@@ -250,4 +300,63 @@ mod tests {
         let result = cache.get::<TestData>("test", &key).unwrap();
         assert!(result.is_none());
     }
+
+    #[tokio::test]
+    async fn test_in_flight_deduplicates_concurrent_fetches() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let in_flight = InFlight::default();
+        let fetches = Arc::new(AtomicUsize::new(0));
+
+        let tasks: Vec<_> = (0..10)
+            .map(|_| {
+                let in_flight = in_flight.clone();
+                let fetches = fetches.clone();
+                tokio::spawn(async move {
+                    in_flight
+                        .run("osv-guzzlehttp-guzzle", async move {
+                            fetches.fetch_add(1, Ordering::SeqCst);
+                            tokio::time::sleep(Duration::from_millis(50)).await;
+                            Ok(serde_json::json!({"vulns": []}))
+                        })
+                        .await
+                })
+            })
+            .collect();
+
+        for task in tasks {
+            let value = task.await.unwrap().unwrap();
+            assert_eq!(value, serde_json::json!({"vulns": []}));
+        }
+
+        assert_eq!(fetches.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn test_late_waiter_keeps_a_newer_fetch_in_flight() {
+        let in_flight = InFlight::default();
+        let (first_tx, first_rx) = futures::channel::oneshot::channel::<()>();
+
+        let mut leader = Box::pin(in_flight.run("key", async move {
+            first_rx.await.unwrap();
+            Ok(serde_json::json!("first"))
+        }));
+        let mut follower = Box::pin(in_flight.run("key", async { unreachable!() }));
+        assert!(leader.as_mut().now_or_never().is_none());
+        assert!(follower.as_mut().now_or_never().is_none());
+
+        first_tx.send(()).unwrap();
+        assert_eq!(leader.await.unwrap(), serde_json::json!("first"));
+
+        // A new fetch starts before the follower gets to finish.
+        let (_second_tx, second_rx) = futures::channel::oneshot::channel::<()>();
+        let mut second = Box::pin(in_flight.run("key", async move {
+            second_rx.await.unwrap();
+            Ok(serde_json::json!("second"))
+        }));
+        assert!(second.as_mut().now_or_never().is_none());
+
+        assert_eq!(follower.await.unwrap(), serde_json::json!("first"));
+        assert!(in_flight.pending.lock().unwrap().contains_key("key"));
+    }
 }
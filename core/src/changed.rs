@@ -0,0 +1,123 @@
+use crate::error::{RensaError, Result};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Manifests and the lock files that pin them. A change to either one
+/// rescans both, since detectors prefer the lock file when it exists.
+const MANIFEST_PAIRS: &[(&str, &str)] = &[
+    ("composer.json", "composer.lock"),
+    ("package.json", "package-lock.json"),
+    ("pubspec.yaml", "pubspec.lock"),
+];
+
+/// Returns the manifests and lock files under `dir` that changed between
+/// `base` and `HEAD` (`git diff <base>...HEAD`), for scanning only what a
+/// pull request touched.
+pub fn changed_manifests(dir: &Path, base: &str) -> Result<Vec<PathBuf>> {
+    let toplevel = git(dir, &["rev-parse", "--show-toplevel"]).map_err(|_| RensaError::InvalidDirectory {
+        path: dir.display().to_string(),
+        reason: "--changed-only needs a git repository".to_string(),
+    })?;
+    let toplevel = PathBuf::from(toplevel.trim());
+    let dir = dir.canonicalize()?;
+
+    let diff = git(&toplevel, &["diff", "--name-only", &format!("{}...HEAD", base)])?;
+
+    let mut files = Vec::new();
+    for changed in diff.lines().map(|line| toplevel.join(line)) {
+        let name = changed.file_name().and_then(|n| n.to_str()).unwrap_or_default();
+        let pair = MANIFEST_PAIRS.iter().find(|(manifest, lock)| name == *manifest || name == *lock);
+        let (parent, (manifest, lock)) = match (changed.parent(), pair) {
+            (Some(parent), Some(pair)) if parent.starts_with(&dir) => (parent, pair),
+            _ => continue,
+        };
+
+        for file in [parent.join(manifest), parent.join(lock)] {
+            if file.is_file() && !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+
+    files.sort();
+    Ok(files)
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|e| RensaError::Git {
+            message: format!("failed to run git: {}", e),
+        })?;
+
+    if !output.status.success() {
+        return Err(RensaError::Git {
+            message: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .arg("-C")
+            .arg(dir)
+            .args(["-c", "user.name=rensa", "-c", "user.email=rensa@example.com"])
+            .args(args)
+            .output()
+            .unwrap()
+            .status;
+        assert!(status.success(), "git {:?}", args);
+    }
+
+    #[test]
+    fn test_only_changed_manifests_are_returned() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        for dir in ["app", "lib", "web"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        std::fs::write(root.join("app/composer.json"), "{}").unwrap();
+        std::fs::write(root.join("lib/composer.json"), "{}").unwrap();
+        std::fs::write(root.join("web/package.json"), "{}").unwrap();
+        std::fs::write(root.join("web/package-lock.json"), "{}").unwrap();
+
+        run(&root, &["init", "-q"]);
+        run(&root, &["add", "."]);
+        run(&root, &["commit", "-q", "-m", "initial"]);
+        run(&root, &["branch", "base"]);
+
+        std::fs::write(root.join("app/composer.json"), r#"{"require": {}}"#).unwrap();
+        std::fs::write(root.join("web/package.json"), r#"{"name": "web"}"#).unwrap();
+        std::fs::write(root.join("README.md"), "docs").unwrap();
+        run(&root, &["add", "."]);
+        run(&root, &["commit", "-q", "-m", "change"]);
+
+        assert_eq!(
+            changed_manifests(&root, "base").unwrap(),
+            vec![
+                root.join("app/composer.json"),
+                root.join("web/package-lock.json"),
+                root.join("web/package.json"),
+            ]
+        );
+        assert_eq!(changed_manifests(&root.join("lib"), "base").unwrap(), Vec::<PathBuf>::new());
+        assert!(matches!(changed_manifests(&root, "missing-ref"), Err(RensaError::Git { .. })));
+    }
+
+    #[test]
+    fn test_non_git_directory_is_rejected() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        assert!(matches!(
+            changed_manifests(temp_dir.path(), "origin/main"),
+            Err(RensaError::InvalidDirectory { .. })
+        ));
+    }
+}
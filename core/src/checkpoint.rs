@@ -0,0 +1,228 @@
+use crate::error::{RensaError, Result};
+use crate::types::{Dependency, PackageInfo, Vulnerability};
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Finished lookups are written out in batches of this many, so an
+/// interrupted scan loses at most this much work.
+const SAVE_INTERVAL: usize = 25;
+
+/// Bumped whenever what a checkpoint holds changes shape or meaning.
+const SCHEMA_VERSION: u32 = 1;
+
+/// Registry and vulnerability lookups a scan has finished, kept on disk so
+/// an interrupted scan can be resumed without repeating them. Entries are
+/// grouped by manifest along with a hash of its content; once a manifest
+/// changes, its entries no longer apply and are dropped.
+#[derive(Debug)]
+pub struct Checkpoint {
+    path: PathBuf,
+    state: Mutex<State>,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct State {
+    schema_version: u32,
+    manifests: HashMap<PathBuf, ManifestEntries>,
+    /// Content hashes of the manifests seen this run.
+    #[serde(skip)]
+    hashes: HashMap<PathBuf, Option<String>>,
+    #[serde(skip)]
+    unsaved: usize,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ManifestEntries {
+    hash: String,
+    /// Keyed by `name@version`.
+    lookups: HashMap<String, CheckpointEntry>,
+}
+
+/// What one dependency's lookups returned. A lookup that wasn't made is
+/// `None`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CheckpointEntry {
+    pub update: Option<PackageInfo>,
+    pub vulnerabilities: Option<Vec<Vulnerability>>,
+}
+
+impl Checkpoint {
+    /// Opens the checkpoint at `path`. A missing file, or one written in
+    /// another format, starts an empty checkpoint.
+    pub fn open(path: PathBuf) -> Result<Self> {
+        let state = match std::fs::read_to_string(&path) {
+            Ok(content) => {
+                let state: State = serde_json::from_str(&content).map_err(|e| RensaError::ParseError {
+                    file: path.clone(),
+                    source: e,
+                })?;
+                if state.schema_version == SCHEMA_VERSION {
+                    state
+                } else {
+                    State::default()
+                }
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => State::default(),
+            Err(e) => return Err(e.into()),
+        };
+
+        Ok(Self {
+            path,
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Number of lookups held for manifests that are unchanged.
+    pub fn len(&self) -> usize {
+        let mut state = self.state.lock().unwrap();
+        let files: Vec<PathBuf> = state.manifests.keys().cloned().collect();
+        let mut total = 0;
+        for file in files {
+            if state.current(&file) {
+                total += state.manifests[&file].lookups.len();
+            }
+        }
+        total
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// The recorded lookups for `dep`, unless its manifest changed since.
+    pub(crate) fn get(&self, dep: &Dependency) -> Option<CheckpointEntry> {
+        let mut state = self.state.lock().unwrap();
+        if !state.current(&dep.file) {
+            return None;
+        }
+        state.manifests.get(&dep.file)?.lookups.get(&key(dep)).cloned()
+    }
+
+    /// Records `dep`'s lookups, writing the checkpoint out every
+    /// [`SAVE_INTERVAL`] entries. A write that fails only costs a resumed
+    /// scan some of its head start, so it's retried with the next batch.
+    pub(crate) fn record(&self, dep: &Dependency, entry: CheckpointEntry) {
+        let mut state = self.state.lock().unwrap();
+        let Some(hash) = state.hash(&dep.file) else {
+            return;
+        };
+
+        let manifest = state.manifests.entry(dep.file.clone()).or_default();
+        if manifest.hash != hash {
+            *manifest = ManifestEntries {
+                hash,
+                lookups: HashMap::new(),
+            };
+        }
+        manifest.lookups.insert(key(dep), entry);
+
+        state.unsaved += 1;
+        if state.unsaved >= SAVE_INTERVAL {
+            let _ = self.write(&mut state);
+        }
+    }
+
+    /// Writes out every lookup recorded so far.
+    pub fn save(&self) -> Result<()> {
+        let mut state = self.state.lock().unwrap();
+        self.write(&mut state)
+    }
+
+    fn write(&self, state: &mut State) -> Result<()> {
+        state.schema_version = SCHEMA_VERSION;
+        let json = serde_json::to_vec(&*state).map_err(|e| RensaError::Plugin {
+            message: format!("failed to serialize checkpoint: {}", e),
+        })?;
+
+        // Replaced in one rename so an interruption mid-write leaves the
+        // previous checkpoint intact.
+        let partial = self.path.with_extension("partial");
+        std::fs::write(&partial, json)?;
+        std::fs::rename(&partial, &self.path)?;
+        state.unsaved = 0;
+        Ok(())
+    }
+}
+
+impl State {
+    /// Hash of `file`'s content, read once per run. `None` when it can't
+    /// be read.
+    fn hash(&mut self, file: &Path) -> Option<String> {
+        self.hashes
+            .entry(file.to_path_buf())
+            .or_insert_with(|| {
+                let content = std::fs::read(file).ok()?;
+                let mut hasher = DefaultHasher::new();
+                content.hash(&mut hasher);
+                Some(format!("{:016x}", hasher.finish()))
+            })
+            .clone()
+    }
+
+    /// Whether the entries recorded for `file` still match its content.
+    fn current(&mut self, file: &Path) -> bool {
+        let hash = self.hash(file);
+        matches!((self.manifests.get(file), hash), (Some(manifest), Some(hash)) if manifest.hash == hash)
+    }
+}
+
+fn key(dep: &Dependency) -> String {
+    format!("{}@{}", dep.name, dep.version)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DependencyScope, Ecosystem, VersionConstraint};
+    use tempfile::TempDir;
+
+    fn dependency(file: &Path, name: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            ecosystem: Ecosystem::Composer,
+            scope: DependencyScope::Runtime,
+            version: "1.0.0".to_string(),
+            constraint: VersionConstraint::Exact("1.0.0".to_string()),
+            file: file.to_path_buf(),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        }
+    }
+
+    fn entry() -> CheckpointEntry {
+        CheckpointEntry {
+            update: Some(PackageInfo::default()),
+            vulnerabilities: Some(Vec::new()),
+        }
+    }
+
+    #[test]
+    fn test_entries_survive_reopening_until_manifest_changes() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = temp_dir.path().join("composer.json");
+        let path = temp_dir.path().join("checkpoint.json");
+        std::fs::write(&manifest, r#"{"require": {"a/a": "^1.0"}}"#).unwrap();
+
+        let checkpoint = Checkpoint::open(path.clone()).unwrap();
+        assert!(checkpoint.is_empty());
+        checkpoint.record(&dependency(&manifest, "a/a"), entry());
+        checkpoint.save().unwrap();
+
+        let reopened = Checkpoint::open(path.clone()).unwrap();
+        assert_eq!(reopened.len(), 1);
+        assert!(reopened.get(&dependency(&manifest, "a/a")).is_some());
+        assert!(reopened.get(&dependency(&manifest, "b/b")).is_none());
+
+        std::fs::write(&manifest, r#"{"require": {"a/a": "^2.0"}}"#).unwrap();
+        let changed = Checkpoint::open(path.clone()).unwrap();
+        assert!(changed.get(&dependency(&manifest, "a/a")).is_none());
+        assert!(changed.is_empty());
+    }
+}
@@ -0,0 +1,576 @@
+use crate::error::{RensaError, Result};
+use crate::denylist::PackageDenylist;
+use crate::license::LicensePolicy;
+use crate::types::{DependencyScope, Ecosystem, Severity};
+use crate::version::ClassifyOptions;
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+pub const CONFIG_VERSION: u32 = 2;
+
+pub const CONFIG_FILE_NAMES: &[&str] = &["rensa.yaml", "rensa.yml", ".rensa.yaml", ".rensa.yml"];
+
+const SUPPORTED_ECOSYSTEMS: &[Ecosystem] = &[Ecosystem::Composer, Ecosystem::Npm, Ecosystem::Pub, Ecosystem::Terraform];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SeverityThreshold {
+    #[default]
+    Low,
+    Medium,
+    High,
+    Critical,
+}
+
+/// What to make of dependencies no registry knows at all.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum UnresolvedPolicy {
+    /// Report them as warnings.
+    #[default]
+    Warn,
+    /// Report them as errors and fail the scan, e.g. to catch typosquats
+    /// or dependency confusion in a strict supply-chain setup.
+    Error,
+}
+
+impl SeverityThreshold {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SeverityThreshold::Low => "low",
+            SeverityThreshold::Medium => "medium",
+            SeverityThreshold::High => "high",
+            SeverityThreshold::Critical => "critical",
+        }
+    }
+}
+
+impl std::str::FromStr for SeverityThreshold {
+    type Err = RensaError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "low" => Ok(SeverityThreshold::Low),
+            "medium" => Ok(SeverityThreshold::Medium),
+            "high" => Ok(SeverityThreshold::High),
+            "critical" => Ok(SeverityThreshold::Critical),
+            _ => Err(RensaError::InvalidConfig {
+                message: format!(
+                    "unknown severity threshold '{}' (expected low, medium, high or critical)",
+                    s
+                ),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct EcosystemConfig {
+    pub ecosystem: String,
+    #[serde(default = "default_directory")]
+    pub directory: String,
+    #[serde(default = "default_true")]
+    pub include_dev: bool,
+    #[serde(default)]
+    pub registry: Option<RegistryConfig>,
+    /// Set to false to leave this ecosystem's plugin out of scans.
+    #[serde(default = "default_true")]
+    pub enabled: bool,
+}
+
+/// Connection settings for a private registry.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct RegistryConfig {
+    #[serde(default)]
+    pub url: Option<String>,
+    /// PEM file with an extra root certificate to trust.
+    #[serde(default)]
+    pub ca_cert: Option<PathBuf>,
+    /// PEM client certificate for mutual TLS (requires `client_key`).
+    #[serde(default)]
+    pub client_cert: Option<PathBuf>,
+    /// PEM PKCS#8 private key matching `client_cert`.
+    #[serde(default)]
+    pub client_key: Option<PathBuf>,
+    /// PKCS#12 archive holding the client certificate and key.
+    #[serde(default)]
+    pub pkcs12: Option<PathBuf>,
+    #[serde(default)]
+    pub pkcs12_password: Option<String>,
+    /// Disables certificate validation. Never enable this for public registries.
+    #[serde(default)]
+    pub danger_accept_invalid_certs: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct Config {
+    pub version: u32,
+    #[serde(default)]
+    pub ecosystems: Vec<EcosystemConfig>,
+    #[serde(default)]
+    pub severity_threshold: SeverityThreshold,
+    /// Advisory ids left out of reports, e.g. ones reviewed and accepted.
+    #[serde(default)]
+    pub ignore: Vec<String>,
+    #[serde(default)]
+    pub cache_dir: Option<PathBuf>,
+    /// Timeout in seconds for package registry requests.
+    #[serde(default)]
+    pub registry_timeout: Option<u64>,
+    /// Timeout in seconds for OSV vulnerability queries.
+    #[serde(default)]
+    pub osv_timeout: Option<u64>,
+    /// Base URL of the OSV API, e.g. a self-hosted mirror (default
+    /// https://api.osv.dev).
+    #[serde(default)]
+    pub osv_url: Option<String>,
+    #[serde(default)]
+    pub eol: EolConfig,
+    /// Path scanned when none is given on the command line.
+    #[serde(default)]
+    pub scan_path: Option<String>,
+    /// Additional default paths or glob patterns, scanned alongside `scan_path`.
+    #[serde(default)]
+    pub scan_paths: Vec<String>,
+    /// Package name -> version treated as the latest release. Overridden
+    /// packages are not looked up in their registry.
+    #[serde(default)]
+    pub overrides: HashMap<String, String>,
+    /// Reports `0.x` minor bumps as major updates, since SemVer allows them
+    /// to break the API. Defaults to the strict SemVer classification.
+    #[serde(default)]
+    pub treat_zerover_minor_as_breaking: bool,
+    /// Manifests larger than this many bytes are skipped (default 10 MB).
+    #[serde(default)]
+    pub max_file_size: Option<u64>,
+    /// How many dependencies are looked up at once (default: the number of
+    /// CPUs).
+    #[serde(default)]
+    pub concurrency: Option<usize>,
+    /// Cap on network requests in flight across the whole scan (default
+    /// twice `concurrency`).
+    #[serde(default)]
+    pub max_concurrency: Option<usize>,
+    /// Dependency groups to scan: `runtime`, `dev`, `optional` and `peer`.
+    /// Empty scans every group.
+    #[serde(default)]
+    pub groups: Vec<String>,
+    /// SPDX licenses dependencies may use. Empty allows any license that
+    /// isn't denied.
+    #[serde(default)]
+    pub allowed_licenses: Vec<String>,
+    /// SPDX licenses to warn about, e.g. `GPL` in a proprietary product.
+    #[serde(default)]
+    pub denied_licenses: Vec<String>,
+    /// Packages that may not be used at all, by name or glob (`acme/*`).
+    /// Each one found is reported as a finding.
+    #[serde(default)]
+    pub denied_packages: Vec<String>,
+    /// Summary of denied package findings.
+    #[serde(default)]
+    pub denied_package_message: Option<String>,
+    /// Severity of denied package findings, for `--fail-on` (default high).
+    #[serde(default)]
+    pub denied_package_severity: Option<SeverityThreshold>,
+    /// Whether dependencies no registry knows are a warning (default) or
+    /// an error.
+    #[serde(default)]
+    pub unresolved: UnresolvedPolicy,
+    /// Advisories rated below this are dropped as soon as OSV returns them.
+    #[serde(default)]
+    pub min_severity: Option<SeverityThreshold>,
+    /// Names shown for severities in human-readable output, e.g.
+    /// `critical: P0`. Machine-readable formats keep the canonical names.
+    #[serde(default)]
+    pub severity_labels: HashMap<Severity, String>,
+    /// composer.json sections read for requirements (default `require` and
+    /// `require-dev`), e.g. only `require`, or a plugin's custom section.
+    #[serde(default)]
+    pub composer_sections: Option<Vec<String>>,
+}
+
+/// End-of-life checks against an endoflife.date compatible dataset.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct EolConfig {
+    #[serde(default)]
+    pub enabled: bool,
+    /// Base URL of the dataset; defaults to https://endoflife.date.
+    #[serde(default)]
+    pub api_url: Option<String>,
+    /// Extra dependency name -> product mappings, e.g. `laravel/framework: laravel`.
+    #[serde(default)]
+    pub products: HashMap<String, String>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            version: CONFIG_VERSION,
+            ecosystems: Vec::new(),
+            severity_threshold: SeverityThreshold::default(),
+            ignore: Vec::new(),
+            cache_dir: None,
+            registry_timeout: None,
+            osv_timeout: None,
+            osv_url: None,
+            eol: EolConfig::default(),
+            scan_path: None,
+            scan_paths: Vec::new(),
+            overrides: HashMap::new(),
+            treat_zerover_minor_as_breaking: false,
+            max_file_size: None,
+            concurrency: None,
+            max_concurrency: None,
+            groups: Vec::new(),
+            allowed_licenses: Vec::new(),
+            denied_licenses: Vec::new(),
+            denied_packages: Vec::new(),
+            denied_package_message: None,
+            denied_package_severity: None,
+            unresolved: UnresolvedPolicy::default(),
+            min_severity: None,
+            severity_labels: HashMap::new(),
+            composer_sections: None,
+        }
+    }
+}
+
+fn default_directory() -> String {
+    "/".to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
+
+impl Config {
+    pub fn classify_options(&self) -> ClassifyOptions {
+        ClassifyOptions {
+            treat_zerover_minor_as_breaking: self.treat_zerover_minor_as_breaking,
+        }
+    }
+
+    /// The configured `groups`; unknown names are rejected by validation.
+    pub fn scan_groups(&self) -> Vec<DependencyScope> {
+        self.groups.iter().filter_map(|g| DependencyScope::from_group(g)).collect()
+    }
+
+    /// Ecosystems whose `ecosystems` entry has `enabled: false`. Unknown
+    /// names are reported by validation and skipped here.
+    pub fn disabled_ecosystems(&self) -> Vec<Ecosystem> {
+        self.ecosystems
+            .iter()
+            .filter(|e| !e.enabled)
+            .filter_map(|e| parse_ecosystem(&e.ecosystem).ok())
+            .collect()
+    }
+
+    /// The license policy from `allowed_licenses` and `denied_licenses`.
+    pub fn license_policy(&self) -> LicensePolicy {
+        LicensePolicy {
+            allowed: self.allowed_licenses.clone(),
+            denied: self.denied_licenses.clone(),
+        }
+    }
+
+    /// The denylist from `denied_packages`. Patterns are checked by
+    /// validation, so this only fails for an unvalidated config.
+    pub fn package_denylist(&self) -> Result<PackageDenylist> {
+        let mut denylist = PackageDenylist::new(&self.denied_packages)?;
+        if let Some(message) = &self.denied_package_message {
+            denylist = denylist.with_message(message.clone());
+        }
+        if let Some(severity) = self.denied_package_severity {
+            denylist = denylist.with_severity(severity);
+        }
+        Ok(denylist)
+    }
+
+    /// Loads and validates a config file.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Err(RensaError::ConfigNotFound(path.to_path_buf()));
+        }
+
+        let content = std::fs::read_to_string(path)?;
+        Self::from_yaml(&content, path)
+    }
+
+    /// Looks for one of the well-known config file names in `dir`.
+    pub fn discover(dir: &Path) -> Result<Option<Self>> {
+        for name in CONFIG_FILE_NAMES {
+            let candidate = dir.join(name);
+            if candidate.is_file() {
+                return Self::load(&candidate).map(Some);
+            }
+        }
+        Ok(None)
+    }
+
+    pub fn from_yaml(content: &str, file: &Path) -> Result<Self> {
+        let config: Config = serde_yaml::from_str(content).map_err(|e| {
+            let (line, column) = e
+                .location()
+                .map(|l| (l.line(), l.column()))
+                .unwrap_or((0, 0));
+            RensaError::YamlSyntaxError {
+                file: file.to_path_buf(),
+                line,
+                column,
+                message: e.to_string(),
+            }
+        })?;
+
+        config.validate(file)?;
+        Ok(config)
+    }
+
+    /// Private registry settings from the `ecosystems` entries that have
+    /// a `registry`, keyed by ecosystem.
+    pub fn registry_configs(&self) -> HashMap<Ecosystem, RegistryConfig> {
+        self.ecosystems
+            .iter()
+            .filter_map(|e| Some((parse_ecosystem(&e.ecosystem).ok()?, e.registry.clone()?)))
+            .collect()
+    }
+
+    pub fn validate(&self, file: &Path) -> Result<()> {
+        if self.version < CONFIG_VERSION {
+            return Err(RensaError::DeprecatedConfig);
+        }
+
+        let mut errors = Vec::new();
+
+        if self.version != CONFIG_VERSION {
+            errors.push(format!(
+                "unsupported config version {} (expected {})",
+                self.version, CONFIG_VERSION
+            ));
+        }
+
+        if self.registry_timeout == Some(0) {
+            errors.push("registry_timeout must be greater than zero".to_string());
+        }
+        if self.osv_timeout == Some(0) {
+            errors.push("osv_timeout must be greater than zero".to_string());
+        }
+        if let Some(url) = self.osv_url.as_deref().filter(|u| !u.starts_with("http://") && !u.starts_with("https://")) {
+            errors.push(format!("osv_url must be an http or https URL, got '{}'", url));
+        }
+        if self.max_file_size == Some(0) {
+            errors.push("max_file_size must be greater than zero".to_string());
+        }
+        if self.concurrency == Some(0) {
+            errors.push("concurrency must be greater than zero".to_string());
+        }
+        if self.max_concurrency == Some(0) {
+            errors.push("max_concurrency must be greater than zero".to_string());
+        }
+        for (i, pattern) in self.denied_packages.iter().enumerate() {
+            if let Err(e) = glob::Pattern::new(pattern) {
+                errors.push(format!("denied_packages[{}]: invalid pattern '{}': {}", i, pattern, e));
+            }
+        }
+        for (severity, label) in &self.severity_labels {
+            if label.trim().is_empty() {
+                errors.push(format!("severity_labels: label for {} must not be empty", severity.as_str().to_lowercase()));
+            }
+        }
+        if self.composer_sections.as_ref().is_some_and(|s| s.is_empty()) {
+            errors.push("composer_sections must list at least one section".to_string());
+        }
+        for license in &self.denied_licenses {
+            if self.allowed_licenses.iter().any(|l| l.eq_ignore_ascii_case(license)) {
+                errors.push(format!("license '{}' is both allowed and denied", license));
+            }
+        }
+        for (i, group) in self.groups.iter().enumerate() {
+            if DependencyScope::from_group(group).is_none() {
+                errors.push(format!(
+                    "groups[{}]: unknown group '{}' (supported: runtime, dev, optional, peer)",
+                    i, group
+                ));
+            }
+        }
+
+        for (i, eco) in self.ecosystems.iter().enumerate() {
+            if parse_ecosystem(&eco.ecosystem).is_err() {
+                errors.push(format!(
+                    "ecosystems[{}]: unknown ecosystem '{}' (supported: {})",
+                    i,
+                    eco.ecosystem,
+                    supported_ecosystems()
+                ));
+            }
+            if eco.directory.is_empty() {
+                errors.push(format!("ecosystems[{}]: directory must not be empty", i));
+            }
+            if let Some(registry) = &eco.registry {
+                if registry.client_cert.is_some() != registry.client_key.is_some() {
+                    errors.push(format!(
+                        "ecosystems[{}].registry: client_cert and client_key must be set together",
+                        i
+                    ));
+                }
+                if let Some(url) = registry.url.as_deref().filter(|u| !u.starts_with("http://") && !u.starts_with("https://")) {
+                    errors.push(format!("ecosystems[{}].registry: url must be an http or https URL, got '{}'", i, url));
+                }
+            }
+        }
+
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(RensaError::Validation {
+                file: file.to_path_buf(),
+                errors,
+            })
+        }
+    }
+}
+
+/// Picks the paths to scan: command-line arguments win, then the config's
+/// `scan_path`/`scan_paths`, then the current directory.
+pub fn resolve_scan_paths(cli: &[String], config: Option<&Config>) -> Vec<String> {
+    if !cli.is_empty() {
+        return cli.to_vec();
+    }
+
+    let configured: Vec<String> = config
+        .map(|c| c.scan_path.iter().chain(&c.scan_paths).cloned().collect())
+        .unwrap_or_default();
+
+    if configured.is_empty() {
+        vec![".".to_string()]
+    } else {
+        configured
+    }
+}
+
+pub fn parse_ecosystem(name: &str) -> Result<Ecosystem> {
+    SUPPORTED_ECOSYSTEMS
+        .iter()
+        .find(|e| e.to_string() == name.to_lowercase())
+        .copied()
+        .ok_or_else(|| RensaError::UnknownEcosystem {
+            ecosystem: name.to_string(),
+            supported: supported_ecosystems(),
+        })
+}
+
+fn supported_ecosystems() -> String {
+    SUPPORTED_ECOSYSTEMS
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config_with(scan_path: Option<&str>, scan_paths: &[&str]) -> Config {
+        Config {
+            scan_path: scan_path.map(str::to_string),
+            scan_paths: scan_paths.iter().map(|p| p.to_string()).collect(),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn test_cli_paths_take_precedence() {
+        let config = config_with(Some("./src"), &["./lib"]);
+        let paths = resolve_scan_paths(&["./app".to_string()], Some(&config));
+        assert_eq!(paths, vec!["./app".to_string()]);
+    }
+
+    #[test]
+    fn test_config_paths_used_without_cli_paths() {
+        let config = config_with(Some("./src"), &["./packages/*"]);
+        let paths = resolve_scan_paths(&[], Some(&config));
+        assert_eq!(paths, vec!["./src".to_string(), "./packages/*".to_string()]);
+
+        let config = config_with(None, &["./lib"]);
+        assert_eq!(resolve_scan_paths(&[], Some(&config)), vec!["./lib".to_string()]);
+    }
+
+    #[test]
+    fn test_falls_back_to_current_directory() {
+        assert_eq!(resolve_scan_paths(&[], None), vec![".".to_string()]);
+        assert_eq!(resolve_scan_paths(&[], Some(&Config::default())), vec![".".to_string()]);
+    }
+
+    #[test]
+    fn test_disabled_ecosystems_from_yaml() {
+        let config = Config::from_yaml(
+            "version: 2\necosystems:\n  - ecosystem: composer\n    enabled: false\n  - ecosystem: npm\n",
+            Path::new("rensa.yaml"),
+        )
+        .unwrap();
+        assert_eq!(config.disabled_ecosystems(), vec![Ecosystem::Composer]);
+    }
+
+    #[test]
+    fn test_registry_configs_from_yaml() {
+        let yaml = "version: 2\necosystems:\n  - ecosystem: npm\n    registry:\n      url: https://npm.internal\n  - ecosystem: composer\n";
+        let config = Config::from_yaml(yaml, Path::new("rensa.yaml")).unwrap();
+        let registries = config.registry_configs();
+        assert_eq!(registries.len(), 1);
+        assert_eq!(registries[&Ecosystem::Npm].url.as_deref(), Some("https://npm.internal"));
+
+        let yaml = "version: 2\necosystems:\n  - ecosystem: npm\n    registry:\n      url: npm.internal\n";
+        let result = Config::from_yaml(yaml, Path::new("rensa.yaml"));
+        assert!(matches!(result, Err(RensaError::Validation { .. })));
+    }
+
+    #[test]
+    fn test_scan_path_from_yaml() {
+        let config = Config::from_yaml("version: 2\nscan_path: ./src\n", Path::new("rensa.yaml")).unwrap();
+        assert_eq!(config.scan_path.as_deref(), Some("./src"));
+        assert!(config.scan_paths.is_empty());
+    }
+
+    #[test]
+    fn test_zerover_classification_option() {
+        assert!(!Config::default().classify_options().treat_zerover_minor_as_breaking);
+
+        let config = Config::from_yaml(
+            "version: 2\ntreat_zerover_minor_as_breaking: true\n",
+            Path::new("rensa.yaml"),
+        )
+        .unwrap();
+        assert!(config.classify_options().treat_zerover_minor_as_breaking);
+    }
+
+    #[test]
+    fn test_groups_from_yaml() {
+        let config = Config::from_yaml("version: 2\ngroups: [runtime, dev]\n", Path::new("rensa.yaml")).unwrap();
+        assert_eq!(config.scan_groups(), vec![DependencyScope::Runtime, DependencyScope::Development]);
+
+        let result = Config::from_yaml("version: 2\ngroups: [peers]\n", Path::new("rensa.yaml"));
+        assert!(matches!(result, Err(RensaError::Validation { .. })));
+    }
+
+    #[test]
+    fn test_denied_packages_from_yaml() {
+        let yaml = "version: 2\ndenied_packages: [evil/package, 'abandoned/*']\ndenied_package_severity: critical\n";
+        let config = Config::from_yaml(yaml, Path::new("rensa.yaml")).unwrap();
+        assert!(!config.package_denylist().unwrap().is_empty());
+
+        let result = Config::from_yaml("version: 2\ndenied_packages: ['evil/[']\n", Path::new("rensa.yaml"));
+        assert!(matches!(result, Err(RensaError::Validation { .. })));
+    }
+
+    #[test]
+    fn test_license_policy_from_yaml() {
+        let config = Config::from_yaml("version: 2\ndenied_licenses: [GPL, AGPL]\n", Path::new("rensa.yaml")).unwrap();
+        assert_eq!(config.license_policy().denied, vec!["GPL", "AGPL"]);
+
+        let yaml = "version: 2\nallowed_licenses: [MIT]\ndenied_licenses: [mit]\n";
+        let result = Config::from_yaml(yaml, Path::new("rensa.yaml"));
+        assert!(matches!(result, Err(RensaError::Validation { .. })));
+    }
+}
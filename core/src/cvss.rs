@@ -0,0 +1,145 @@
+//! Base scores from CVSS v2 and v3.x vector strings, for advisories that
+//! publish a vector instead of a numeric score.
+
+/// The base score of a vector such as
+/// `CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H` or the prefix-less v2
+/// form `AV:N/AC:L/Au:N/C:P/I:P/A:P`. Returns `None` for other versions or
+/// when a base metric is missing or invalid.
+pub fn base_score(vector: &str) -> Option<f64> {
+    match vector.split_once('/') {
+        Some(("CVSS:3.0" | "CVSS:3.1", metrics)) => v3_base_score(metrics),
+        Some((prefix, _)) if prefix.starts_with("CVSS:") => None,
+        _ => v2_base_score(vector.strip_prefix('(').unwrap_or(vector).trim_end_matches(')')),
+    }
+}
+
+fn metric<'a>(metrics: &'a str, name: &str) -> Option<&'a str> {
+    metrics
+        .split('/')
+        .filter_map(|m| m.split_once(':'))
+        .find(|(key, _)| *key == name)
+        .map(|(_, value)| value)
+}
+
+fn v3_base_score(metrics: &str) -> Option<f64> {
+    let changed = match metric(metrics, "S")? {
+        "U" => false,
+        "C" => true,
+        _ => return None,
+    };
+    let attack_vector = match metric(metrics, "AV")? {
+        "N" => 0.85,
+        "A" => 0.62,
+        "L" => 0.55,
+        "P" => 0.2,
+        _ => return None,
+    };
+    let attack_complexity = match metric(metrics, "AC")? {
+        "L" => 0.77,
+        "H" => 0.44,
+        _ => return None,
+    };
+    let privileges = match (metric(metrics, "PR")?, changed) {
+        ("N", _) => 0.85,
+        ("L", false) => 0.62,
+        ("L", true) => 0.68,
+        ("H", false) => 0.27,
+        ("H", true) => 0.5,
+        _ => return None,
+    };
+    let interaction = match metric(metrics, "UI")? {
+        "N" => 0.85,
+        "R" => 0.62,
+        _ => return None,
+    };
+    let impact = |name| match metric(metrics, name)? {
+        "H" => Some(0.56),
+        "L" => Some(0.22),
+        "N" => Some(0.0),
+        _ => None,
+    };
+
+    let iss: f64 = 1.0 - (1.0 - impact("C")?) * (1.0 - impact("I")?) * (1.0 - impact("A")?);
+    let impact: f64 = if changed {
+        7.52 * (iss - 0.029) - 3.25 * (iss - 0.02).powi(15)
+    } else {
+        6.42 * iss
+    };
+    if impact <= 0.0 {
+        return Some(0.0);
+    }
+
+    let exploitability = 8.22 * attack_vector * attack_complexity * privileges * interaction;
+    let score: f64 = if changed { 1.08 * (impact + exploitability) } else { impact + exploitability };
+    Some(round_up(score.min(10.0)))
+}
+
+/// CVSS v3.1's round up to one decimal, which avoids floating point
+/// artefacts like 4.000001 rounding to 4.1.
+fn round_up(value: f64) -> f64 {
+    let scaled = (value * 100_000.0).round() as i64;
+    if scaled % 10_000 == 0 {
+        scaled as f64 / 100_000.0
+    } else {
+        ((scaled / 10_000) + 1) as f64 / 10.0
+    }
+}
+
+fn v2_base_score(metrics: &str) -> Option<f64> {
+    let access_vector = match metric(metrics, "AV")? {
+        "L" => 0.395,
+        "A" => 0.646,
+        "N" => 1.0,
+        _ => return None,
+    };
+    let access_complexity = match metric(metrics, "AC")? {
+        "H" => 0.35,
+        "M" => 0.61,
+        "L" => 0.71,
+        _ => return None,
+    };
+    let authentication = match metric(metrics, "Au")? {
+        "M" => 0.45,
+        "S" => 0.56,
+        "N" => 0.704,
+        _ => return None,
+    };
+    let impact = |name| match metric(metrics, name)? {
+        "N" => Some(0.0),
+        "P" => Some(0.275),
+        "C" => Some(0.66),
+        _ => None,
+    };
+
+    let impact: f64 = 10.41 * (1.0 - (1.0 - impact("C")?) * (1.0 - impact("I")?) * (1.0 - impact("A")?));
+    let exploitability = 20.0 * access_vector * access_complexity * authentication;
+    let f_impact = if impact == 0.0 { 0.0 } else { 1.176 };
+    let score: f64 = (0.6 * impact + 0.4 * exploitability - 1.5) * f_impact;
+    Some((score * 10.0).round() / 10.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_v3_base_scores() {
+        assert_eq!(base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H"), Some(9.8));
+        assert_eq!(base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:R/S:C/C:L/I:L/A:N"), Some(6.1));
+        assert_eq!(base_score("CVSS:3.0/AV:L/AC:L/PR:L/UI:N/S:U/C:H/I:N/A:N"), Some(5.5));
+        assert_eq!(base_score("CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:N/I:N/A:N"), Some(0.0));
+    }
+
+    #[test]
+    fn test_v2_base_scores() {
+        assert_eq!(base_score("AV:N/AC:L/Au:N/C:P/I:P/A:P"), Some(7.5));
+        assert_eq!(base_score("(AV:N/AC:M/Au:N/C:N/I:P/A:N)"), Some(4.3));
+    }
+
+    #[test]
+    fn test_unsupported_vectors() {
+        assert_eq!(base_score("CVSS:4.0/AV:N/AC:L/AT:N/PR:N/UI:N/VC:H/VI:H/VA:H/SC:N/SI:N/SA:N"), None);
+        assert_eq!(base_score("CVSS:3.1/AV:N/AC:L"), None);
+        assert_eq!(base_score("7.5"), None);
+    }
+}
@@ -0,0 +1,138 @@
+use crate::config::SeverityThreshold;
+use crate::error::{RensaError, Result};
+use crate::types::{Dependency, Severity, Vulnerability};
+
+/// Id of the finding reported for a denied package.
+pub const DENIED_PACKAGE_ID: &str = "RENSA-DENIED-PACKAGE";
+
+const DEFAULT_MESSAGE: &str = "Package is denied by policy";
+
+/// Packages that may not be used at all, e.g. known-malicious or abandoned
+/// ones. Each match is reported as a finding whatever its advisories say,
+/// so `--fail-on` treats it like a vulnerability of the configured severity.
+#[derive(Debug, Clone)]
+pub struct PackageDenylist {
+    patterns: Vec<glob::Pattern>,
+    message: String,
+    severity: Severity,
+}
+
+impl PackageDenylist {
+    /// Patterns are package names or globs like `acme/*`.
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let patterns = patterns
+            .iter()
+            .map(|p| {
+                glob::Pattern::new(p).map_err(|e| RensaError::Config {
+                    message: format!("invalid denied package pattern '{}': {}", p, e),
+                })
+            })
+            .collect::<Result<_>>()?;
+
+        Ok(Self {
+            patterns,
+            message: DEFAULT_MESSAGE.to_string(),
+            severity: Severity::High,
+        })
+    }
+
+    /// Replaces the finding's summary, e.g. to point at an internal policy.
+    pub fn with_message(mut self, message: String) -> Self {
+        self.message = message;
+        self
+    }
+
+    pub fn with_severity(mut self, threshold: SeverityThreshold) -> Self {
+        self.severity = match threshold {
+            SeverityThreshold::Low => Severity::Low,
+            SeverityThreshold::Medium => Severity::Medium,
+            SeverityThreshold::High => Severity::High,
+            SeverityThreshold::Critical => Severity::Critical,
+        };
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    /// The policy finding for `dependency`, if it is denied.
+    pub fn check(&self, dependency: &Dependency) -> Option<Vulnerability> {
+        let pattern = self.patterns.iter().find(|p| p.matches(&dependency.name))?;
+
+        Some(Vulnerability {
+            id: DENIED_PACKAGE_ID.to_string(),
+            summary: self.message.clone(),
+            details: format!("{} matches denied package pattern '{}'", dependency.name, pattern),
+            severity: self.severity,
+            affected_versions: Vec::new(),
+            fixed_versions: Vec::new(),
+            references: Vec::new(),
+            package: Some(dependency.name.clone()),
+            related_to: None,
+            remediation: None,
+        })
+    }
+}
+
+impl Default for PackageDenylist {
+    fn default() -> Self {
+        Self {
+            patterns: Vec::new(),
+            message: DEFAULT_MESSAGE.to_string(),
+            severity: Severity::High,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DependencyScope, Ecosystem, VersionConstraint};
+    use std::path::PathBuf;
+
+    fn dependency(name: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            ecosystem: Ecosystem::Composer,
+            scope: DependencyScope::Runtime,
+            version: "1.0.0".to_string(),
+            constraint: VersionConstraint::Exact("1.0.0".to_string()),
+            file: PathBuf::from("composer.json"),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn test_exact_denial() {
+        let denylist = PackageDenylist::new(&["evil/package".to_string()]).unwrap();
+
+        let finding = denylist.check(&dependency("evil/package")).unwrap();
+        assert_eq!(finding.id, DENIED_PACKAGE_ID);
+        assert_eq!(finding.severity, Severity::High);
+        assert_eq!(finding.package.as_deref(), Some("evil/package"));
+        assert!(denylist.check(&dependency("evil/package-utils")).is_none());
+    }
+
+    #[test]
+    fn test_glob_denial() {
+        let denylist = PackageDenylist::new(&["abandoned/*".to_string()])
+            .unwrap()
+            .with_message("Replace with acme/maintained".to_string())
+            .with_severity(SeverityThreshold::Critical);
+
+        let finding = denylist.check(&dependency("abandoned/http")).unwrap();
+        assert_eq!(finding.summary, "Replace with acme/maintained");
+        assert_eq!(finding.severity, Severity::Critical);
+        assert!(denylist.check(&dependency("maintained/http")).is_none());
+    }
+
+    #[test]
+    fn test_invalid_pattern() {
+        assert!(PackageDenylist::new(&["evil/[".to_string()]).is_err());
+    }
+}
@@ -0,0 +1,250 @@
+use crate::error::Result;
+use crate::types::Dependency;
+use crate::HttpClient;
+use chrono::{NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+
+pub const DEFAULT_EOL_API: &str = "https://endoflife.date";
+
+/// A release cycle as published by the endoflife.date API.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EolCycle {
+    pub cycle: String,
+    pub eol: EolStatus,
+}
+
+/// The `eol` field is either a date or a plain flag when no date is known.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum EolStatus {
+    Date(NaiveDate),
+    Flag(bool),
+}
+
+/// A dependency or runtime whose release cycle is past its support date.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EndOfLife {
+    pub name: String,
+    pub product: String,
+    pub cycle: String,
+    pub eol: Option<NaiveDate>,
+    pub file: PathBuf,
+}
+
+#[derive(Debug)]
+pub struct EolClient {
+    client: HttpClient,
+    base_url: String,
+}
+
+impl EolClient {
+    pub fn new(base_url: &str) -> Self {
+        Self {
+            client: HttpClient::new(),
+            base_url: base_url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    pub async fn cycles(&self, product: &str) -> Result<Vec<EolCycle>> {
+        self.client.get(&format!("{}/api/{}.json", self.base_url, product)).await
+    }
+}
+
+impl Default for EolClient {
+    fn default() -> Self {
+        Self::new(DEFAULT_EOL_API)
+    }
+}
+
+/// Flags dependencies whose current release cycle has reached end-of-life,
+/// regardless of whether a newer version exists.
+///
+/// Only names mapped to an endoflife.date product are checked. PHP is mapped
+/// out of the box; composer reports it from `require.php`.
+#[derive(Debug)]
+pub struct EolChecker {
+    client: EolClient,
+    products: HashMap<String, String>,
+    cycles: Mutex<HashMap<String, Vec<EolCycle>>>,
+}
+
+impl EolChecker {
+    pub fn new(client: EolClient) -> Self {
+        let mut products = HashMap::new();
+        products.insert("php".to_string(), "php".to_string());
+
+        Self {
+            client,
+            products,
+            cycles: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Maps a dependency name onto an endoflife.date product.
+    pub fn with_product(mut self, name: &str, product: &str) -> Self {
+        self.products.insert(name.to_string(), product.to_string());
+        self
+    }
+
+    pub fn tracks(&self, name: &str) -> bool {
+        self.products.contains_key(name)
+    }
+
+    pub async fn check(&self, dependency: &Dependency) -> Result<Option<EndOfLife>> {
+        let product = match self.products.get(&dependency.name) {
+            Some(p) => p,
+            None => return Ok(None),
+        };
+
+        let (major, minor) = match base_version(&dependency.version) {
+            Some(v) => v,
+            None => return Ok(None),
+        };
+
+        let cycles = self.cycles_for(product).await?;
+        let candidates = [
+            minor.map(|minor| format!("{}.{}", major, minor)),
+            Some(major.to_string()),
+        ];
+
+        let cycle = candidates
+            .iter()
+            .flatten()
+            .find_map(|c| cycles.iter().find(|cycle| &cycle.cycle == c));
+
+        let cycle = match cycle {
+            Some(c) => c,
+            None => return Ok(None),
+        };
+
+        let today = Utc::now().date_naive();
+        let eol = match cycle.eol {
+            EolStatus::Date(date) if date <= today => Some(date),
+            EolStatus::Flag(true) => None,
+            _ => return Ok(None),
+        };
+
+        Ok(Some(EndOfLife {
+            name: dependency.name.clone(),
+            product: product.clone(),
+            cycle: cycle.cycle.clone(),
+            eol,
+            file: dependency.file.clone(),
+        }))
+    }
+
+    async fn cycles_for(&self, product: &str) -> Result<Vec<EolCycle>> {
+        if let Some(cycles) = self.cycles.lock().unwrap().get(product) {
+            return Ok(cycles.clone());
+        }
+
+        let cycles = self.client.cycles(product).await?;
+        self.cycles
+            .lock()
+            .unwrap()
+            .insert(product.to_string(), cycles.clone());
+        Ok(cycles)
+    }
+}
+
+/// Extracts the lowest `major.minor` a version or constraint allows, e.g.
+/// `^7.4 || ^8.0` -> `(7, Some(4))`.
+fn base_version(version: &str) -> Option<(u64, Option<u64>)> {
+    let start = version.find(|c: char| c.is_ascii_digit())?;
+    let rest = &version[start..];
+    let end = rest
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(rest.len());
+
+    let mut parts = rest[..end].split('.').filter(|p| !p.is_empty());
+    let major = parts.next()?.parse().ok()?;
+    let minor = parts.next().and_then(|p| p.parse().ok());
+    Some((major, minor))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::VersionConstraint;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const PHP_CYCLES: &str = r#"[
+        {"cycle": "8.3", "releaseDate": "2023-11-23", "eol": "2999-12-31", "latest": "8.3.12"},
+        {"cycle": "8.0", "releaseDate": "2020-11-26", "eol": "2023-11-26", "latest": "8.0.30"},
+        {"cycle": "7.4", "releaseDate": "2019-11-28", "eol": "2022-11-28", "latest": "7.4.33"},
+        {"cycle": "5.6", "releaseDate": "2014-08-28", "eol": true, "latest": "5.6.40"}
+    ]"#;
+
+    fn php(constraint: &str) -> Dependency {
+        Dependency {
+            ecosystem: crate::Ecosystem::Composer,
+            scope: crate::DependencyScope::Runtime,
+            name: "php".to_string(),
+            version: constraint.to_string(),
+            constraint: VersionConstraint::Range(constraint.to_string()),
+            file: PathBuf::from("composer.json"),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        }
+    }
+
+    async fn checker() -> (MockServer, EolChecker) {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/api/php.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(PHP_CYCLES))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let checker = EolChecker::new(EolClient::new(&server.uri()));
+        (server, checker)
+    }
+
+    #[test]
+    fn test_base_version() {
+        assert_eq!(base_version("^7.4 || ^8.0"), Some((7, Some(4))));
+        assert_eq!(base_version(">=8.1.2"), Some((8, Some(1))));
+        assert_eq!(base_version("~5"), Some((5, None)));
+        assert_eq!(base_version("*"), None);
+    }
+
+    #[tokio::test]
+    async fn test_flags_eol_php() {
+        let (_server, checker) = checker().await;
+
+        let eol = checker.check(&php("^7.4")).await.unwrap().unwrap();
+        assert_eq!(eol.product, "php");
+        assert_eq!(eol.cycle, "7.4");
+        assert_eq!(eol.eol, NaiveDate::from_ymd_opt(2022, 11, 28));
+
+        let flagged = checker.check(&php(">=5.6")).await.unwrap().unwrap();
+        assert_eq!(flagged.cycle, "5.6");
+        assert_eq!(flagged.eol, None);
+    }
+
+    #[tokio::test]
+    async fn test_supported_php_is_not_flagged() {
+        let (_server, checker) = checker().await;
+
+        assert!(checker.check(&php("^8.3")).await.unwrap().is_none());
+        assert!(checker.check(&php("^9.0")).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_untracked_dependency_is_skipped() {
+        let server = MockServer::start().await;
+        let checker = EolChecker::new(EolClient::new(&server.uri()));
+
+        let mut dep = php("^7.4");
+        dep.name = "monolog/monolog".to_string();
+        assert!(checker.check(&dep).await.unwrap().is_none());
+    }
+}
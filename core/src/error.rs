@@ -1,4 +1,5 @@
 use std::path::PathBuf;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Debug, Error)]
@@ -67,8 +68,62 @@ pub enum RensaError {
     #[error("Invalid directory path '{path}': {reason}")]
     InvalidDirectory { path: String, reason: String },
 
+    #[error("{path} is {size} bytes, which exceeds the {limit} byte limit")]
+    FileTooLarge { path: PathBuf, size: u64, limit: u64 },
+
+    #[error("git: {message}")]
+    Git { message: String },
+
     #[error("Deprecated configuration format detected. Please migrate to version 2 format.")]
     DeprecatedConfig,
+
+    /// Every retry was answered with HTTP 429. `retry_after` is taken from
+    /// the last response.
+    #[error("rate limited by {registry}; {}", match .retry_after {
+        Some(d) => format!("try again in {}s", d.as_secs()),
+        None => "try again later".to_string(),
+    })]
+    RateLimited {
+        registry: String,
+        retry_after: Option<Duration>,
+    },
+
+    /// An error from a fetch whose result was shared between concurrent callers.
+    #[error(transparent)]
+    Shared(std::sync::Arc<RensaError>),
+}
+
+impl RensaError {
+    /// Whether the error is a request that ran out of time.
+    pub fn is_timeout(&self) -> bool {
+        match self {
+            RensaError::RegistryError { source, .. } | RensaError::VulnerabilityError { source } => {
+                source.is_timeout()
+            }
+            RensaError::Shared(inner) => inner.is_timeout(),
+            _ => false,
+        }
+    }
+
+    /// Whether the registry answered that the package doesn't exist, as
+    /// opposed to failing to answer.
+    pub fn is_not_found(&self) -> bool {
+        match self {
+            RensaError::DependencyNotFound { .. } => true,
+            RensaError::RegistryError { source, .. } => source.status() == Some(reqwest::StatusCode::NOT_FOUND),
+            RensaError::Shared(inner) => inner.is_not_found(),
+            _ => false,
+        }
+    }
+
+    /// Whether the registry kept rejecting requests with HTTP 429.
+    pub fn is_rate_limited(&self) -> bool {
+        match self {
+            RensaError::RateLimited { .. } => true,
+            RensaError::Shared(inner) => inner.is_rate_limited(),
+            _ => false,
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, RensaError>;
@@ -0,0 +1,68 @@
+use crate::error::{RensaError, Result};
+
+/// Restricts a scan to the dependencies whose names match, e.g. `symfony/*`.
+/// Patterns starting with `!` exclude names instead; without any include
+/// pattern every name not excluded is kept.
+#[derive(Debug, Clone, Default)]
+pub struct DependencyFilter {
+    include: Vec<glob::Pattern>,
+    exclude: Vec<glob::Pattern>,
+}
+
+impl DependencyFilter {
+    /// Patterns are package names or globs, optionally prefixed with `!`.
+    pub fn new(patterns: &[String]) -> Result<Self> {
+        let mut filter = Self::default();
+        for pattern in patterns {
+            let (list, glob) = match pattern.strip_prefix('!') {
+                Some(glob) => (&mut filter.exclude, glob),
+                None => (&mut filter.include, pattern.as_str()),
+            };
+            list.push(glob::Pattern::new(glob).map_err(|e| RensaError::Config {
+                message: format!("invalid dependency filter '{}': {}", pattern, e),
+            })?);
+        }
+        Ok(filter)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.include.is_empty() && self.exclude.is_empty()
+    }
+
+    pub fn matches(&self, name: &str) -> bool {
+        (self.include.is_empty() || self.include.iter().any(|p| p.matches(name)))
+            && !self.exclude.iter().any(|p| p.matches(name))
+    }
+
+    /// The patterns as given, for keys of results that depend on them.
+    pub(crate) fn fingerprint(&self) -> String {
+        let include = self.include.iter().map(|p| p.as_str().to_string());
+        let exclude = self.exclude.iter().map(|p| format!("!{}", p.as_str()));
+        include.chain(exclude).collect::<Vec<_>>().join(",")
+    }
+}
+
+/// Checks a single `--filter` pattern, for use as a clap value parser.
+pub fn parse_pattern(pattern: &str) -> Result<String> {
+    DependencyFilter::new(&[pattern.to_string()])?;
+    Ok(pattern.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_include_and_exclude_patterns() {
+        let filter = DependencyFilter::new(&["symfony/*".to_string(), "!symfony/polyfill-*".to_string()]).unwrap();
+
+        assert!(filter.matches("symfony/console"));
+        assert!(!filter.matches("symfony/polyfill-mbstring"));
+        assert!(!filter.matches("monolog/monolog"));
+
+        let exclude_only = DependencyFilter::new(&["!symfony/*".to_string()]).unwrap();
+        assert!(exclude_only.matches("monolog/monolog"));
+        assert!(!exclude_only.matches("symfony/console"));
+        assert!(DependencyFilter::new(&["symfony/[".to_string()]).is_err());
+    }
+}
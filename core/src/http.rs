@@ -1,17 +1,99 @@
-use crate::cache::CacheManager;
+use crate::cache::{CacheManager, InFlight};
+use crate::config::RegistryConfig;
 use crate::error::{RensaError, Result};
 use serde::{Deserialize, Serialize};
 use std::time::Duration;
 
 const DEFAULT_RETRIES: u32 = 3;
 const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Upper bound on how long a single `Retry-After` is honoured between retries.
+const MAX_RETRY_AFTER: Duration = Duration::from_secs(60);
+/// Idle connections kept per host. Scans talk to a handful of registries, so
+/// this comfortably covers the scanner's lookup concurrency.
+const DEFAULT_POOL_MAX_IDLE_PER_HOST: usize = 32;
+/// How long an idle connection is kept before it is closed; reqwest's default.
+const DEFAULT_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+/// Redirects followed per request. Registries rarely chain more than one.
+const DEFAULT_MAX_REDIRECTS: usize = 5;
 
 #[derive(Debug, Clone)]
 pub struct HttpClient {
     client: reqwest::Client,
     retries: u32,
     timeout: Duration,
+    tls: TlsSettings,
+    pool: PoolSettings,
+    redirects: RedirectSettings,
     cache: Option<CacheManager>,
+    in_flight: InFlight,
+}
+
+/// Client certificate presented for mutual TLS.
+#[derive(Debug, Clone)]
+pub enum ClientIdentity {
+    /// PEM-encoded certificate chain and PKCS#8 private key.
+    Pem { cert: Vec<u8>, key: Vec<u8> },
+    /// DER-encoded PKCS#12 archive and its password.
+    Pkcs12 { der: Vec<u8>, password: String },
+}
+
+#[derive(Debug, Clone, Default)]
+struct TlsSettings {
+    root_certs: Vec<reqwest::Certificate>,
+    identity: Option<reqwest::Identity>,
+    accept_invalid_certs: bool,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct PoolSettings {
+    max_idle_per_host: usize,
+    idle_timeout: Duration,
+}
+
+impl Default for PoolSettings {
+    fn default() -> Self {
+        Self {
+            max_idle_per_host: DEFAULT_POOL_MAX_IDLE_PER_HOST,
+            idle_timeout: DEFAULT_POOL_IDLE_TIMEOUT,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct RedirectSettings {
+    max: usize,
+    /// Hosts redirects may lead to; any host when empty.
+    allowed_hosts: Vec<String>,
+}
+
+impl Default for RedirectSettings {
+    fn default() -> Self {
+        Self {
+            max: DEFAULT_MAX_REDIRECTS,
+            allowed_hosts: Vec::new(),
+        }
+    }
+}
+
+impl RedirectSettings {
+    fn policy(&self) -> reqwest::redirect::Policy {
+        if self.max == 0 {
+            return reqwest::redirect::Policy::none();
+        }
+
+        let max = self.max;
+        let allowed_hosts = self.allowed_hosts.clone();
+        reqwest::redirect::Policy::custom(move |attempt| {
+            let host = attempt.url().host_str().unwrap_or_default().to_string();
+            if attempt.previous().len() > max {
+                attempt.error(format!("more than {} redirects", max))
+            } else if !allowed_hosts.is_empty() && !allowed_hosts.iter().any(|h| h.eq_ignore_ascii_case(&host)) {
+                attempt.error(format!("redirect to disallowed host '{}'", host))
+            } else {
+                attempt.follow()
+            }
+        })
+    }
 }
 
 impl Default for HttpClient {
@@ -22,17 +104,114 @@ impl Default for HttpClient {
 
 impl HttpClient {
     pub fn new() -> Self {
-        let client = reqwest::Client::builder()
-            .timeout(DEFAULT_TIMEOUT)
-            .build()
-            .expect("Failed to build HTTP client");
+        let tls = TlsSettings::default();
+        let pool = PoolSettings::default();
+        let redirects = RedirectSettings::default();
+        let client =
+            Self::build_client(DEFAULT_TIMEOUT, &tls, pool, &redirects).expect("Failed to build HTTP client");
 
         Self {
             client,
             retries: DEFAULT_RETRIES,
             timeout: DEFAULT_TIMEOUT,
+            tls,
+            pool,
+            redirects,
             cache: None,
+            in_flight: InFlight::default(),
+        }
+    }
+
+    fn build_client(
+        timeout: Duration,
+        tls: &TlsSettings,
+        pool: PoolSettings,
+        redirects: &RedirectSettings,
+    ) -> Result<reqwest::Client> {
+        let mut builder = reqwest::Client::builder()
+            .timeout(timeout)
+            .pool_max_idle_per_host(pool.max_idle_per_host)
+            .pool_idle_timeout(pool.idle_timeout)
+            .redirect(redirects.policy());
+
+        for cert in &tls.root_certs {
+            builder = builder.add_root_certificate(cert.clone());
+        }
+        if let Some(identity) = &tls.identity {
+            builder = builder.identity(identity.clone());
+        }
+        if tls.accept_invalid_certs {
+            builder = builder.danger_accept_invalid_certs(true);
+        }
+
+        builder.build().map_err(|e| RensaError::Config {
+            message: format!("failed to build HTTP client: {}", e),
+        })
+    }
+
+    fn rebuild(mut self) -> Result<Self> {
+        self.client = Self::build_client(self.timeout, &self.tls, self.pool, &self.redirects)?;
+        Ok(self)
+    }
+
+    /// Trusts an additional PEM-encoded root certificate, e.g. an internal CA.
+    pub fn with_root_cert(mut self, pem: &[u8]) -> Result<Self> {
+        let cert = reqwest::Certificate::from_pem(pem).map_err(tls_error)?;
+        self.tls.root_certs.push(cert);
+        self.rebuild()
+    }
+
+    /// Presents a client certificate for registries that require mutual TLS.
+    pub fn with_client_identity(mut self, identity: ClientIdentity) -> Result<Self> {
+        let identity = match identity {
+            ClientIdentity::Pem { cert, key } => reqwest::Identity::from_pkcs8_pem(&cert, &key),
+            ClientIdentity::Pkcs12 { der, password } => reqwest::Identity::from_pkcs12_der(&der, &password),
+        }
+        .map_err(tls_error)?;
+        self.tls.identity = Some(identity);
+        self.rebuild()
+    }
+
+    /// Disables TLS certificate validation entirely.
+    ///
+    /// **This is dangerous.** Any certificate, including expired, self-signed
+    /// or hostname-mismatched ones, will be accepted, leaving the connection
+    /// open to interception. Prefer [`HttpClient::with_root_cert`] and only
+    /// use this as a last resort against trusted internal hosts.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Result<Self> {
+        if accept {
+            eprintln!("Warning: TLS certificate validation is disabled");
+        }
+        self.tls.accept_invalid_certs = accept;
+        self.rebuild()
+    }
+
+    /// Applies the TLS settings of a registry config, reading any referenced
+    /// certificate files.
+    pub fn with_registry_config(self, config: &RegistryConfig) -> Result<Self> {
+        let mut client = self;
+
+        if let Some(path) = &config.ca_cert {
+            client = client.with_root_cert(&std::fs::read(path)?)?;
         }
+
+        if let Some(path) = &config.pkcs12 {
+            client = client.with_client_identity(ClientIdentity::Pkcs12 {
+                der: std::fs::read(path)?,
+                password: config.pkcs12_password.clone().unwrap_or_default(),
+            })?;
+        } else if let (Some(cert), Some(key)) = (&config.client_cert, &config.client_key) {
+            client = client.with_client_identity(ClientIdentity::Pem {
+                cert: std::fs::read(cert)?,
+                key: std::fs::read(key)?,
+            })?;
+        }
+
+        if config.danger_accept_invalid_certs {
+            client = client.danger_accept_invalid_certs(true)?;
+        }
+
+        Ok(client)
     }
 
     pub fn with_cache(mut self, cache: CacheManager) -> Self {
@@ -47,7 +226,43 @@ impl HttpClient {
 
     pub fn with_timeout(mut self, timeout: Duration) -> Self {
         self.timeout = timeout;
-        self
+        self.rebuild().expect("Failed to build HTTP client")
+    }
+
+    /// Caps the idle connections kept open to any one host. Lower it for
+    /// registries that limit connections per client. Defaults to 32.
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.pool.max_idle_per_host = max;
+        self.rebuild().expect("Failed to build HTTP client")
+    }
+
+    /// Closes connections that have been idle for `timeout`. Defaults to 90
+    /// seconds.
+    pub fn with_pool_idle_timeout(mut self, timeout: Duration) -> Self {
+        self.pool.idle_timeout = timeout;
+        self.rebuild().expect("Failed to build HTTP client")
+    }
+
+    /// Follows at most `max` redirects per request; 0 follows none.
+    /// Defaults to 5.
+    pub fn with_redirect_policy(mut self, max: usize) -> Self {
+        self.redirects.max = max;
+        self.rebuild().expect("Failed to build HTTP client")
+    }
+
+    /// Only follows redirects to `hosts`, so a registry can't bounce
+    /// requests to internal addresses. Any host is allowed by default.
+    pub fn with_allowed_redirect_hosts(mut self, hosts: Vec<String>) -> Self {
+        self.redirects.allowed_hosts = hosts;
+        self.rebuild().expect("Failed to build HTTP client")
+    }
+
+    pub fn timeout(&self) -> Duration {
+        self.timeout
+    }
+
+    pub fn pool_max_idle_per_host(&self) -> usize {
+        self.pool.max_idle_per_host
     }
 
     fn cache_key_from_url(&self, url: &str) -> String {
@@ -63,33 +278,7 @@ impl HttpClient {
     where
         T: for<'a> Deserialize<'a>,
     {
-        for attempt in 0..=self.retries {
-            match self.client.get(url).send().await {
-                Ok(response) if response.status().is_success() => {
-                    return Ok(response.json().await.map_err(|e| RensaError::RegistryError {
-                        registry: url.to_string(),
-                        source: e,
-                    })?);
-                }
-                Ok(_response) => {
-                    if attempt < self.retries {
-                        let delay = Duration::from_secs(2u64.pow(attempt));
-                        tokio::time::sleep(delay).await;
-                    }
-                }
-                Err(_e) if attempt < self.retries => {
-                    let delay = Duration::from_secs(2u64.pow(attempt));
-                    tokio::time::sleep(delay).await;
-                }
-                Err(e) => {
-                    return Err(RensaError::RegistryError {
-                        registry: url.to_string(),
-                        source: e,
-                    });
-                }
-            }
-        }
-        unreachable!()
+        self.send_with_retries(url, || self.client.get(url)).await
     }
 
     async fn fetch_post<T, B>(&self, url: &str, body: &B) -> Result<T>
@@ -97,33 +286,113 @@ impl HttpClient {
         T: for<'a> Deserialize<'a>,
         B: serde::Serialize,
     {
-        for attempt in 0..=self.retries {
-            match self.client.post(url).json(body).send().await {
+        self.send_with_retries(url, || self.client.post(url).json(body)).await
+    }
+
+    async fn send_with_retries<T, F>(&self, url: &str, request: F) -> Result<T>
+    where
+        T: for<'a> Deserialize<'a>,
+        F: Fn() -> reqwest::RequestBuilder,
+    {
+        let mut attempt = 0;
+        loop {
+            let last_attempt = attempt >= self.retries;
+            let backoff = Duration::from_secs(2u64.pow(attempt));
+            attempt += 1;
+
+            match request().send().await {
                 Ok(response) if response.status().is_success() => {
-                    return Ok(response.json().await.map_err(|e| RensaError::RegistryError {
+                    return response.json().await.map_err(|e| RensaError::RegistryError {
                         registry: url.to_string(),
                         source: e,
-                    })?);
+                    });
                 }
-                Ok(_response) => {
-                    if attempt < self.retries {
-                        let delay = Duration::from_secs(2u64.pow(attempt));
-                        tokio::time::sleep(delay).await;
+                Ok(response) if response.status() == reqwest::StatusCode::TOO_MANY_REQUESTS => {
+                    let retry_after = retry_after(response.headers());
+                    if last_attempt {
+                        return Err(RensaError::RateLimited {
+                            registry: registry_host(url),
+                            retry_after,
+                        });
                     }
+                    tokio::time::sleep(retry_after.map_or(backoff, |d| d.min(MAX_RETRY_AFTER))).await;
                 }
-                Err(_e) if attempt < self.retries => {
-                    let delay = Duration::from_secs(2u64.pow(attempt));
-                    tokio::time::sleep(delay).await;
+                // A missing resource won't appear on retry.
+                Ok(response) if last_attempt || response.status() == reqwest::StatusCode::NOT_FOUND => {
+                    let source = match response.error_for_status() {
+                        Err(e) => e,
+                        Ok(response) => match response.json().await {
+                            Ok(value) => return Ok(value),
+                            Err(e) => e,
+                        },
+                    };
+                    return Err(RensaError::RegistryError {
+                        registry: url.to_string(),
+                        source,
+                    });
                 }
-                Err(e) => {
+                Ok(_response) => tokio::time::sleep(backoff).await,
+                Err(e) if last_attempt => {
                     return Err(RensaError::RegistryError {
                         registry: url.to_string(),
                         source: e,
                     });
                 }
+                Err(_e) => tokio::time::sleep(backoff).await,
             }
         }
-        unreachable!()
+    }
+
+    /// POSTs `body` as JSON without retries or caching and ignores the
+    /// response body, for endpoints like webhooks that don't answer in JSON.
+    pub async fn post_json<B>(&self, url: &str, body: &B) -> Result<()>
+    where
+        B: Serialize,
+    {
+        self.client
+            .post(url)
+            .json(body)
+            .send()
+            .await
+            .and_then(|response| response.error_for_status())
+            .map_err(|e| RensaError::RegistryError {
+                registry: url.to_string(),
+                source: e,
+            })?;
+
+        Ok(())
+    }
+
+    /// Sends a single HEAD request to check that `url` can be reached. Any
+    /// HTTP response counts, since endpoints often don't answer HEAD with a
+    /// success status.
+    pub async fn ping(&self, url: &str) -> Result<()> {
+        self.client
+            .head(url)
+            .send()
+            .await
+            .map(|_| ())
+            .map_err(|e| RensaError::RegistryError {
+                registry: url.to_string(),
+                source: e,
+            })
+    }
+
+    /// Whether a fresh response to a GET of `url` is cached, so fetching it
+    /// sends no request. Always false without a cache.
+    pub fn is_cached(&self, url: &str) -> bool {
+        self.cache.as_ref().is_some_and(|cache| {
+            let key = self.cache_key_from_url(url);
+            cache.get::<serde_json::Value>("api", &key).ok().flatten().is_some()
+        })
+    }
+
+    /// Like [`HttpClient::is_cached`], for a POST of `body` to `url`.
+    pub fn is_cached_post<B: std::fmt::Debug>(&self, url: &str, body: &B) -> bool {
+        self.cache.as_ref().is_some_and(|cache| {
+            let key = format!("{}-{:?}", url, body);
+            cache.get::<serde_json::Value>("api", &key).ok().flatten().is_some()
+        })
     }
 
     pub async fn get<T>(&self, url: &str) -> Result<T>
@@ -137,13 +406,23 @@ impl HttpClient {
                 return Ok(entry.data().clone());
             }
 
-            let result = self.fetch::<T>(url).await?;
-
-            if let Err(e) = cache.set("api", &key, &result) {
-                eprintln!("Warning: Failed to cache API response: {}", e);
-            }
+            // Concurrent requests for the same URL share one fetch, which
+            // also writes the cache entry exactly once.
+            let client = self.clone();
+            let cache = cache.clone();
+            let owned_url = url.to_string();
+            let value = self
+                .in_flight
+                .run(url, async move {
+                    let result = client.fetch::<serde_json::Value>(&owned_url).await?;
+                    if let Err(e) = cache.set("api", &key, &result) {
+                        eprintln!("Warning: Failed to cache API response: {}", e);
+                    }
+                    Ok(result)
+                })
+                .await?;
 
-            Ok(result)
+            from_shared_value(value)
         } else {
             self.fetch::<T>(url).await
         }
@@ -161,15 +440,290 @@ impl HttpClient {
                 return Ok(entry.data().clone());
             }
 
-            let result = self.fetch_post::<T, B>(url, body).await?;
-
-            if let Err(e) = cache.set("api", &key, &result) {
-                eprintln!("Warning: Failed to cache API response: {}", e);
-            }
+            let body = serde_json::to_value(body).map_err(|e| RensaError::Cache {
+                message: format!("failed to encode request body: {}", e),
+            })?;
+            let client = self.clone();
+            let cache = cache.clone();
+            let owned_url = url.to_string();
+            let cache_key = key.clone();
+            let value = self
+                .in_flight
+                .run(&key, async move {
+                    let result = client
+                        .fetch_post::<serde_json::Value, serde_json::Value>(&owned_url, &body)
+                        .await?;
+                    if let Err(e) = cache.set("api", &cache_key, &result) {
+                        eprintln!("Warning: Failed to cache API response: {}", e);
+                    }
+                    Ok(result)
+                })
+                .await?;
 
-            Ok(result)
+            from_shared_value(value)
         } else {
             self.fetch_post::<T, B>(url, body).await
         }
     }
 }
+
+fn from_shared_value<T>(value: serde_json::Value) -> Result<T>
+where
+    T: for<'a> Deserialize<'a>,
+{
+    serde_json::from_value(value).map_err(|e| RensaError::Cache {
+        message: format!("failed to decode shared response: {}", e),
+    })
+}
+
+/// Reads a `Retry-After` header given in seconds. HTTP dates are rare from
+/// package registries and are ignored.
+fn retry_after(headers: &reqwest::header::HeaderMap) -> Option<Duration> {
+    headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim()
+        .parse()
+        .ok()
+        .map(Duration::from_secs)
+}
+
+fn registry_host(url: &str) -> String {
+    reqwest::Url::parse(url)
+        .ok()
+        .and_then(|u| u.host_str().map(str::to_string))
+        .unwrap_or_else(|| url.to_string())
+}
+
+fn tls_error(e: reqwest::Error) -> RensaError {
+    RensaError::Config {
+        message: format!("invalid TLS configuration: {}", e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const TEST_CA_PEM: &str = "-----BEGIN CERTIFICATE-----
+MIIBiDCCAS2gAwIBAgIUIpuWt2bfe1msAstGjEuQI9QN40YwCgYIKoZIzj0EAwIw
+GDEWMBQGA1UEAwwNcmVuc2EtdGVzdC1jYTAgFw0yNjEwMTUwMzI4MDJaGA8yMTI2
+MDkyMTAzMjgwMlowGDEWMBQGA1UEAwwNcmVuc2EtdGVzdC1jYTBZMBMGByqGSM49
+AgEGCCqGSM49AwEHA0IABF3fg8mlfsdGDQfVho3i6jHhEZUuzQlMca8eK9pLKwki
+V3pxLRexAyu/EhgMwb5sVHLlYJfF5bnu08hGSqp6e+mjUzBRMB0GA1UdDgQWBBQE
+P0npVvBcwqBvhnvQiJhoT5QzZzAfBgNVHSMEGDAWgBQEP0npVvBcwqBvhnvQiJho
+T5QzZzAPBgNVHRMBAf8EBTADAQH/MAoGCCqGSM49BAMCA0kAMEYCIQDfnvNrVVH8
+FT1du/2p3ya4aGEAecEEWQPJ4Ix2tevT0wIhAP9k4GJwiGBhKUA9PhXwauMmkJDH
+3xox2dscjuPhgT/g
+-----END CERTIFICATE-----
+";
+
+    #[tokio::test]
+    async fn test_pool_settings_survive_rebuilds() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .mount(&server)
+            .await;
+
+        let client = HttpClient::new()
+            .with_pool_max_idle_per_host(4)
+            .with_pool_idle_timeout(Duration::from_secs(5))
+            .with_timeout(Duration::from_secs(10))
+            .with_root_cert(TEST_CA_PEM.as_bytes())
+            .unwrap();
+        assert_eq!(client.pool.max_idle_per_host, 4);
+        assert_eq!(client.pool.idle_timeout, Duration::from_secs(5));
+
+        let body: serde_json::Value = client.get(&server.uri()).await.unwrap();
+        assert_eq!(body["ok"], true);
+    }
+
+    #[tokio::test]
+    async fn test_redirect_to_disallowed_host_is_rejected() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/internal"))
+            .respond_with(ResponseTemplate::new(302).insert_header("Location", "http://metadata.internal/latest"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/moved"))
+            .respond_with(ResponseTemplate::new(302).insert_header("Location", "/package.json"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/package.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({"ok": true})))
+            .mount(&server)
+            .await;
+
+        let client = HttpClient::new()
+            .with_retries(0)
+            .with_allowed_redirect_hosts(vec!["127.0.0.1".to_string()]);
+        let followed: serde_json::Value = client.get(&format!("{}/moved", server.uri())).await.unwrap();
+        assert_eq!(followed["ok"], true);
+
+        let rejected = client.get::<serde_json::Value>(&format!("{}/internal", server.uri())).await;
+        let message = match rejected {
+            Err(RensaError::RegistryError { source, .. }) => format!("{:?}", source),
+            other => panic!("expected a registry error, got {:?}", other.map(|_| ())),
+        };
+        assert!(message.contains("disallowed host"), "{}", message);
+
+        let no_redirects = HttpClient::new().with_retries(0).with_redirect_policy(0);
+        assert!(no_redirects.get::<serde_json::Value>(&format!("{}/moved", server.uri())).await.is_err());
+    }
+
+    #[test]
+    fn test_with_root_cert() {
+        let client = HttpClient::new().with_root_cert(TEST_CA_PEM.as_bytes());
+        assert!(client.is_ok());
+    }
+
+    #[test]
+    fn test_with_invalid_root_cert() {
+        let client = HttpClient::new().with_root_cert(b"not a certificate");
+        assert!(matches!(client, Err(RensaError::Config { .. })));
+    }
+
+    #[test]
+    fn test_with_registry_config_ca_cert() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let ca_path = temp_dir.path().join("ca.pem");
+        std::fs::write(&ca_path, TEST_CA_PEM).unwrap();
+
+        let config = RegistryConfig {
+            ca_cert: Some(ca_path),
+            ..Default::default()
+        };
+
+        assert!(HttpClient::new().with_registry_config(&config).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_cached_gets_fetch_once() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(
+                ResponseTemplate::new(200)
+                    .set_body_json(serde_json::json!({"name": "pkg"}))
+                    .set_delay(Duration::from_millis(100)),
+            )
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let client = HttpClient::new().with_cache(CacheManager::new(temp_dir.path().to_path_buf()));
+        let url = format!("{}/packages/pkg.json", server.uri());
+
+        let requests = (0..10).map(|_| client.get::<serde_json::Value>(&url));
+        let results = futures::future::join_all(requests).await;
+
+        for result in results {
+            assert_eq!(result.unwrap()["name"], "pkg");
+        }
+        assert!(client.cache.as_ref().unwrap().exists("api", "pkg.json"));
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_rate_limit_returns_rate_limited() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(429).insert_header("Retry-After", "0"))
+            .expect(3)
+            .mount(&server)
+            .await;
+
+        let client = HttpClient::new().with_retries(2);
+        let url = format!("{}/packages/pkg.json", server.uri());
+
+        match client.get::<serde_json::Value>(&url).await {
+            Err(RensaError::RateLimited { registry, retry_after }) => {
+                assert_eq!(registry, "127.0.0.1");
+                assert_eq!(retry_after, Some(Duration::from_secs(0)));
+            }
+            other => panic!("expected RateLimited, got {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_exhausted_server_errors_return_registry_error() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = HttpClient::new().with_retries(0);
+        let url = format!("{}/packages/missing.json", server.uri());
+
+        let result = client.get::<serde_json::Value>(&url).await;
+        assert!(matches!(result, Err(RensaError::RegistryError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_not_found_is_not_retried() {
+        use wiremock::matchers::method;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .respond_with(ResponseTemplate::new(404))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let url = format!("{}/p2/acme/missing.json", server.uri());
+        let result = HttpClient::new().get::<serde_json::Value>(&url).await;
+        assert!(matches!(result, Err(RensaError::RegistryError { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_ping_accepts_any_response() {
+        let server = wiremock::MockServer::start().await;
+        assert!(HttpClient::new().ping(&server.uri()).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_ping_fails_on_closed_port() {
+        // Wiremock pools its servers, so a dropped one keeps listening.
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let closed = format!("http://{}", listener.local_addr().unwrap());
+        drop(listener);
+        assert!(matches!(
+            HttpClient::new().ping(&closed).await,
+            Err(RensaError::RegistryError { .. })
+        ));
+    }
+
+    #[test]
+    fn test_retry_after_header() {
+        let mut headers = reqwest::header::HeaderMap::new();
+        assert_eq!(retry_after(&headers), None);
+
+        headers.insert(reqwest::header::RETRY_AFTER, "120".parse().unwrap());
+        assert_eq!(retry_after(&headers), Some(Duration::from_secs(120)));
+
+        headers.insert(reqwest::header::RETRY_AFTER, "Wed, 21 Oct 2015 07:28:00 GMT".parse().unwrap());
+        assert_eq!(retry_after(&headers), None);
+    }
+}
@@ -0,0 +1,261 @@
+use crate::report::{EcosystemScanResult, ScanReport};
+use crate::{Dependency, Ecosystem};
+use std::fmt::Write;
+
+/// One `<testcase>`: a dependency and why it fails, if it does.
+struct Case<'a> {
+    dependency: &'a Dependency,
+    failures: Vec<Failure>,
+}
+
+struct Failure {
+    kind: String,
+    message: String,
+    details: String,
+}
+
+impl ScanReport {
+    /// JUnit XML with one `<testsuite>` per ecosystem and one `<testcase>`
+    /// per dependency, failing for each vulnerability at or above the
+    /// scan's severity threshold, so CI test views list the findings.
+    pub fn to_junit(&self) -> String {
+        self.to_junit_with(false)
+    }
+
+    /// Like [`ScanReport::to_junit`], with `fail_on_updates` also failing
+    /// the dependencies that have an update available.
+    pub fn to_junit_with(&self, fail_on_updates: bool) -> String {
+        let mut ecosystems: Vec<&Ecosystem> = self.ecosystem_results.keys().collect();
+        ecosystems.sort();
+
+        let suites: Vec<(Ecosystem, Vec<Case>)> = ecosystems
+            .into_iter()
+            .map(|ecosystem| {
+                let result = &self.ecosystem_results[ecosystem];
+                (*ecosystem, self.cases(result, fail_on_updates))
+            })
+            .collect();
+        let tests: usize = suites.iter().map(|(_, cases)| cases.len()).sum();
+        let failures: usize = suites.iter().flat_map(|(_, cases)| cases).filter(|c| !c.failures.is_empty()).count();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"rensa\" tests=\"{}\" failures=\"{}\" time=\"{:.3}\">",
+            tests,
+            failures,
+            self.elapsed as f64 / 1000.0
+        );
+        for (ecosystem, cases) in &suites {
+            let _ = writeln!(
+                xml,
+                "  <testsuite name=\"{}\" tests=\"{}\" failures=\"{}\">",
+                ecosystem,
+                cases.len(),
+                cases.iter().filter(|c| !c.failures.is_empty()).count()
+            );
+            for case in cases {
+                write_case(&mut xml, case);
+            }
+            xml.push_str("  </testsuite>\n");
+        }
+        xml.push_str("</testsuites>\n");
+        xml
+    }
+
+    fn cases<'a>(&self, result: &'a EcosystemScanResult, fail_on_updates: bool) -> Vec<Case<'a>> {
+        let threshold = self.scan_config.severity_threshold;
+        let mut dependencies: Vec<&Dependency> = result.dependencies.iter().collect();
+        dependencies.sort_by(|a, b| a.name.cmp(&b.name).then_with(|| a.file.cmp(&b.file)));
+
+        dependencies
+            .into_iter()
+            .map(|dependency| {
+                let vulnerabilities = result
+                    .vulnerabilities
+                    .iter()
+                    .filter(|v| v.package.as_deref() == Some(dependency.name.as_str()))
+                    .filter(|v| v.severity.meets_threshold(threshold))
+                    .map(|v| Failure {
+                        kind: v.severity.as_str().to_string(),
+                        message: format!("{}: {}", v.id, v.summary),
+                        details: v.details.clone(),
+                    });
+                let updates = result
+                    .updates
+                    .iter()
+                    .filter(|_| fail_on_updates)
+                    .filter(|u| u.dependency.name == dependency.name && u.dependency.file == dependency.file)
+                    .map(|u| Failure {
+                        kind: "UPDATE".to_string(),
+                        message: format!("{} -> {} available", u.current_version, u.latest_version),
+                        details: String::new(),
+                    });
+
+                Case {
+                    dependency,
+                    failures: vulnerabilities.chain(updates).collect(),
+                }
+            })
+            .collect()
+    }
+}
+
+fn write_case(xml: &mut String, case: &Case) {
+    let dependency = case.dependency;
+    let _ = write!(
+        xml,
+        "    <testcase name=\"{}@{}\" classname=\"{}\"",
+        escape(&dependency.name),
+        escape(&dependency.version),
+        escape(&dependency.file.display().to_string())
+    );
+    if case.failures.is_empty() {
+        xml.push_str("/>\n");
+        return;
+    }
+
+    xml.push_str(">\n");
+    for failure in &case.failures {
+        let _ = writeln!(
+            xml,
+            "      <failure type=\"{}\" message=\"{}\">{}</failure>",
+            escape(&failure.kind),
+            escape(&failure.message),
+            escape(&failure.details)
+        );
+    }
+    xml.push_str("    </testcase>\n");
+}
+
+/// Escapes text for use in XML content and attribute values, dropping
+/// control characters XML 1.0 can't represent.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            c if c.is_control() && c != '\t' && c != '\r' => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DependencyScope, Severity, SeverityThreshold, UpdateInfo, VersionConstraint, Vulnerability};
+    use std::path::PathBuf;
+
+    fn dependency(name: &str) -> Dependency {
+        Dependency {
+            ecosystem: Ecosystem::Composer,
+            scope: DependencyScope::Runtime,
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            constraint: VersionConstraint::Exact("1.0.0".to_string()),
+            file: PathBuf::from("composer.json"),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        }
+    }
+
+    fn vulnerability(package: &str, severity: Severity) -> Vulnerability {
+        Vulnerability {
+            id: format!("GHSA-{}", package),
+            summary: "Injection via <script> & \"quotes\"".to_string(),
+            details: "Upgrade to 2.0.0".to_string(),
+            severity,
+            affected_versions: Vec::new(),
+            fixed_versions: Vec::new(),
+            references: Vec::new(),
+            package: Some(package.to_string()),
+            related_to: None,
+            remediation: None,
+        }
+    }
+
+    fn report() -> ScanReport {
+        let mut report = ScanReport::new(PathBuf::from("."));
+        report.scan_config.severity_threshold = SeverityThreshold::Medium;
+        report.add_ecosystem_result(Ecosystem::Composer, EcosystemScanResult {
+            ecosystem: Ecosystem::Composer,
+            files_found: vec![PathBuf::from("composer.json")],
+            dependencies: vec![
+                dependency("a/outdated"),
+                dependency("b/vulnerable"),
+                dependency("c/clean"),
+                dependency("d/minor-issue"),
+            ],
+            updates: vec![UpdateInfo {
+                dependency: dependency("a/outdated"),
+                current_version: "1.0.0".to_string(),
+                latest_version: "2.0.0".to_string(),
+                changelog: None,
+                latest_in_constraint: None,
+                versions_behind: None,
+                age_behind_days: None,
+                intermediate_releases: Vec::new(),
+            }],
+            vulnerabilities: vec![
+                vulnerability("b/vulnerable", Severity::High),
+                vulnerability("d/minor-issue", Severity::Low),
+            ],
+            errors: Vec::new(),
+            unresolved: Vec::new(),
+            unchecked: Vec::new(),
+        });
+        report
+    }
+
+    /// Checks every element is closed in order and returns the number of
+    /// `<testcase>` and `<failure>` elements.
+    fn counts(xml: &str) -> (usize, usize) {
+        let mut reader = quick_xml::Reader::from_str(xml);
+        let mut open = Vec::new();
+        let (mut cases, mut failures) = (0, 0);
+        loop {
+            match reader.read_event().expect("well-formed XML") {
+                quick_xml::events::Event::Start(e) => {
+                    open.push(e.name().as_ref().to_vec());
+                    cases += usize::from(e.name().as_ref() == b"testcase");
+                    failures += usize::from(e.name().as_ref() == b"failure");
+                }
+                quick_xml::events::Event::Empty(e) => cases += usize::from(e.name().as_ref() == b"testcase"),
+                quick_xml::events::Event::End(e) => assert_eq!(open.pop().as_deref(), Some(e.name().as_ref())),
+                quick_xml::events::Event::Eof => break,
+                _ => {}
+            }
+        }
+        assert!(open.is_empty());
+        (cases, failures)
+    }
+
+    #[test]
+    fn test_vulnerabilities_at_threshold_fail() {
+        let xml = report().to_junit();
+
+        assert_eq!(counts(&xml), (4, 1));
+        assert!(xml.contains("<testsuites name=\"rensa\" tests=\"4\" failures=\"1\""));
+        assert!(xml.contains("message=\"GHSA-b/vulnerable: Injection via &lt;script&gt; &amp; &quot;quotes&quot;\""));
+        assert!(xml.contains("<testcase name=\"c/clean@1.0.0\" classname=\"composer.json\"/>"));
+    }
+
+    #[test]
+    fn test_updates_fail_when_requested() {
+        let xml = report().to_junit_with(true);
+
+        assert_eq!(counts(&xml), (4, 2));
+        assert!(xml.contains("<testsuite name=\"composer\" tests=\"4\" failures=\"2\">"));
+        assert!(xml.contains("message=\"1.0.0 -&gt; 2.0.0 available\""));
+    }
+}
@@ -11,16 +11,35 @@ pub mod config;
 pub mod job;
 pub mod version;
 pub mod report;
+mod junit;
+mod spdx;
 pub mod scanner;
 pub mod osv;
+pub mod cvss;
+pub mod sbom;
+pub mod eol;
+pub mod manifest;
+pub mod submodules;
+pub mod changed;
+pub mod license;
+pub mod denylist;
+pub mod filter;
+pub mod progress;
+pub mod result_cache;
+pub mod checkpoint;
+pub mod webhook;
+pub mod audit;
+pub mod remote;
+#[cfg(any(test, feature = "test-utils"))]
+pub mod testing;
 
 pub use types::*;
 pub use error::{RensaError, Result};
 pub use plugin::{Plugin, Detector, Parser, RegistryClient, VulnerabilityScanner, PluginRegistry};
 pub use report::ScanReport;
-pub use scanner::scan_path;
+pub use scanner::{scan_path, scan_sbom};
 pub use http::HttpClient;
 pub use cache::{CacheManager, CacheEntry};
-pub use config::{Config, EcosystemConfig, SeverityThreshold};
+pub use config::{Config, EcosystemConfig, EolConfig, SeverityThreshold};
 pub use job::{JobDescription, JobConfig, ScheduleConfig, ScheduleInterval};
-pub use version::{VersionComparator, UpdateType};
+pub use version::{ClassifyOptions, VersionComparator, UpdateType};
@@ -0,0 +1,110 @@
+use crate::types::Dependency;
+
+/// Licenses a project accepts from its dependencies, matched against the
+/// SPDX identifiers registries publish.
+///
+/// A pattern matches an identifier exactly or as a prefix before a `-`, so
+/// `GPL-3.0` covers `GPL-3.0-only` and `GPL` covers every GPL version (but
+/// not `LGPL-2.1`). Matching ignores case.
+#[derive(Debug, Clone, Default)]
+pub struct LicensePolicy {
+    /// When non-empty, only these licenses are accepted.
+    pub allowed: Vec<String>,
+    pub denied: Vec<String>,
+}
+
+impl LicensePolicy {
+    pub fn is_empty(&self) -> bool {
+        self.allowed.is_empty() && self.denied.is_empty()
+    }
+
+    /// A warning when none of the dependency's licenses is acceptable.
+    /// Multiple licenses are alternatives, as in composer's `license`
+    /// array. Dependencies without license data are not flagged.
+    pub fn check(&self, dependency: &Dependency) -> Option<String> {
+        let licenses = dependency.license.as_ref().filter(|l| !l.is_empty())?;
+        if self.is_empty() || licenses.iter().any(|l| self.accepts(l)) {
+            return None;
+        }
+
+        let reason = if licenses.iter().any(|l| matches_any(&self.denied, l)) {
+            "denied by license policy"
+        } else {
+            "not in allowed_licenses"
+        };
+        Some(format!(
+            "{} {} is licensed {}, which is {}",
+            dependency.name,
+            dependency.version,
+            licenses.join(" OR "),
+            reason
+        ))
+    }
+
+    fn accepts(&self, license: &str) -> bool {
+        !matches_any(&self.denied, license) && (self.allowed.is_empty() || matches_any(&self.allowed, license))
+    }
+}
+
+fn matches_any(patterns: &[String], license: &str) -> bool {
+    let license = license.to_ascii_lowercase();
+    patterns.iter().any(|pattern| {
+        let pattern = pattern.to_ascii_lowercase();
+        license == pattern || license.strip_prefix(&pattern).is_some_and(|rest| rest.starts_with('-'))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DependencyScope, Ecosystem, VersionConstraint};
+    use std::path::PathBuf;
+
+    fn licensed(licenses: &[&str]) -> Dependency {
+        Dependency {
+            name: "acme/widget".to_string(),
+            ecosystem: Ecosystem::Composer,
+            scope: DependencyScope::Runtime,
+            version: "1.0.0".to_string(),
+            constraint: VersionConstraint::Exact("1.0.0".to_string()),
+            file: PathBuf::from("composer.json"),
+            repository: None,
+            license: Some(licenses.iter().map(|l| l.to_string()).collect()),
+            provenance: None,
+            direct: true,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn test_denied_license() {
+        let policy = LicensePolicy {
+            allowed: Vec::new(),
+            denied: vec!["GPL".to_string()],
+        };
+
+        assert_eq!(
+            policy.check(&licensed(&["GPL-3.0-or-later"])).as_deref(),
+            Some("acme/widget 1.0.0 is licensed GPL-3.0-or-later, which is denied by license policy")
+        );
+        assert!(policy.check(&licensed(&["LGPL-2.1-only"])).is_none());
+        // Dual-licensed packages can be used under the acceptable license.
+        assert!(policy.check(&licensed(&["GPL-2.0-only", "MIT"])).is_none());
+    }
+
+    #[test]
+    fn test_allowed_licenses() {
+        let policy = LicensePolicy {
+            allowed: vec!["mit".to_string(), "BSD-3-Clause".to_string()],
+            denied: Vec::new(),
+        };
+
+        assert!(policy.check(&licensed(&["MIT"])).is_none());
+        assert!(policy.check(&licensed(&["Apache-2.0"])).unwrap().ends_with("not in allowed_licenses"));
+
+        let mut unknown = licensed(&[]);
+        unknown.license = None;
+        assert!(policy.check(&unknown).is_none());
+        assert!(LicensePolicy::default().check(&licensed(&["proprietary"])).is_none());
+    }
+}
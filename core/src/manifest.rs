@@ -0,0 +1,61 @@
+use crate::error::{RensaError, Result};
+use std::io::Read;
+use std::path::Path;
+
+/// Manifests larger than this are skipped unless a detector is configured
+/// otherwise. Real manifests and lockfiles are far smaller.
+pub const DEFAULT_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Reads a manifest into memory, refusing files larger than `max_size`
+/// bytes so a huge or hostile file can't exhaust memory.
+pub fn read_manifest(path: &Path, max_size: u64) -> Result<String> {
+    let file = std::fs::File::open(path)?;
+    let size = file.metadata()?.len();
+    let too_large = || RensaError::FileTooLarge {
+        path: path.to_path_buf(),
+        size,
+        limit: max_size,
+    };
+
+    if size > max_size {
+        return Err(too_large());
+    }
+
+    // The file may grow between the size check and the read.
+    let mut content = String::new();
+    file.take(max_size + 1).read_to_string(&mut content)?;
+    if content.len() as u64 > max_size {
+        return Err(too_large());
+    }
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reads_small_manifest() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("composer.json");
+        std::fs::write(&path, r#"{"require": {}}"#).unwrap();
+
+        assert_eq!(read_manifest(&path, DEFAULT_MAX_FILE_SIZE).unwrap(), r#"{"require": {}}"#);
+    }
+
+    #[test]
+    fn test_rejects_oversized_manifest() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("composer.json");
+        // Sparse, so the test doesn't actually write 10 MB
+        std::fs::File::create(&path)
+            .unwrap()
+            .set_len(DEFAULT_MAX_FILE_SIZE + 1)
+            .unwrap();
+
+        let err = read_manifest(&path, DEFAULT_MAX_FILE_SIZE).unwrap_err();
+        assert!(matches!(err, RensaError::FileTooLarge { size, .. } if size == DEFAULT_MAX_FILE_SIZE + 1));
+        assert!(err.to_string().contains("exceeds the 10485760 byte limit"));
+    }
+}
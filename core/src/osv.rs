@@ -1,10 +1,46 @@
-use crate::HttpClient;
+use crate::{HttpClient, SeverityThreshold};
+use futures::stream::{self, StreamExt, TryStreamExt};
 use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+pub const DEFAULT_OSV_API: &str = "https://api.osv.dev";
+
+/// Cache namespace for advisory bodies fetched by id.
+pub const ADVISORY_CACHE_DIR: &str = "osv-advisories";
+
+/// Advisories rarely change once published, so they outlive query results.
+const ADVISORY_TTL: Duration = Duration::from_secs(7 * 24 * 60 * 60);
+
+/// Cache namespace for whole `/v1/querybatch` responses.
+pub const BATCH_CACHE_DIR: &str = "osv-batches";
+
+/// A batch answers "which advisories affect these versions", which changes
+/// whenever one is published, so it expires well before the advisories do.
+const BATCH_TTL: Duration = Duration::from_secs(60 * 60);
+
+/// Queries OSV accepts in one `/v1/querybatch` request.
+const MAX_BATCH_QUERIES: usize = 1000;
+
+/// Advisories fetched at once for one query's results, unless the client
+/// is given a concurrency.
+const DEFAULT_CONCURRENCY: usize = 4;
 
 #[derive(Debug, Clone)]
 pub struct OsvClient {
     client: HttpClient,
     base_url: String,
+    advisories: HttpClient,
+    advisory_cache: Option<crate::CacheManager>,
+    batch_cache: Option<crate::CacheManager>,
+    include_related: bool,
+    severity_floor: Option<SeverityThreshold>,
+    concurrency: usize,
+    /// Advisory ids found by [`prefetch`](Self::prefetch), by serialized query.
+    batched: Arc<Mutex<HashMap<String, Vec<String>>>>,
 }
 
 impl OsvClient {
@@ -12,14 +48,74 @@ impl OsvClient {
         Self {
             client: HttpClient::new(),
             base_url: base_url.to_string(),
+            advisories: HttpClient::new(),
+            advisory_cache: None,
+            batch_cache: None,
+            include_related: false,
+            severity_floor: None,
+            concurrency: DEFAULT_CONCURRENCY,
+            batched: Arc::default(),
         }
     }
 
+    /// Points the client at another OSV-compatible API, e.g. a self-hosted
+    /// mirror.
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url.trim_end_matches('/').to_string();
+        self
+    }
+
+    /// Also reports the advisories each result lists as `related`, marked
+    /// with [`related_to`](crate::types::Vulnerability::related_to).
+    pub fn with_related(mut self, include: bool) -> Self {
+        self.include_related = include;
+        self
+    }
+
+    /// Drops advisories rated below `floor` as soon as they arrive, before
+    /// their related advisories are fetched. Advisories without a rating
+    /// are kept.
+    pub fn with_severity_floor(mut self, floor: SeverityThreshold) -> Self {
+        self.severity_floor = Some(floor);
+        self
+    }
+
     pub fn with_cache(mut self, cache: crate::CacheManager) -> Self {
+        self.advisory_cache = Some(cache.clone().with_ttl(ADVISORY_TTL));
+        self.batch_cache = Some(cache.clone().with_ttl(BATCH_TTL));
         self.client = HttpClient::with_cache(self.client, cache);
         self
     }
 
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.client = self.client.with_timeout(timeout);
+        self.advisories = self.advisories.with_timeout(timeout);
+        self
+    }
+
+    pub fn timeout(&self) -> std::time::Duration {
+        self.client.timeout()
+    }
+
+    /// Sizes the connection pools, and how many related or batched
+    /// advisories are fetched at once, for `concurrency` requests in flight.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self.client = self.client.with_pool_max_idle_per_host(concurrency);
+        self.advisories = self.advisories.with_pool_max_idle_per_host(concurrency);
+        self
+    }
+
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// The settings that change which advisories are reported, for keying
+    /// cached scan results.
+    pub fn fingerprint(&self) -> String {
+        format!("url={};related={};floor={:?}", self.base_url, self.include_related, self.severity_floor)
+    }
+
     pub async fn query(&self, query: &OsvQuery) -> crate::Result<Vec<OsvVulnerability>> {
         #[derive(Deserialize, Clone, Serialize)]
         #[serde(from = "OsvResponseHelper")]
@@ -41,17 +137,296 @@ impl OsvClient {
         let response: OsvResponse = self.client.post(&format!("{}/v1/query", self.base_url), query).await?;
         Ok(response.vulns)
     }
+
+    /// Where `query` is sent and whether its response is already cached.
+    /// A prefetched query was answered by its batch.
+    pub fn trace(&self, query: &OsvQuery) -> crate::types::LookupTrace {
+        let description = match query {
+            OsvQuery::Package { package, version } => format!("{} {}@{}", package.ecosystem, package.name, version),
+            OsvQuery::Commit { commit } => format!("commit {}", commit),
+        };
+        if self.batched(query).is_some() {
+            let url = format!("{}/v1/querybatch", self.base_url);
+            return crate::types::LookupTrace::new(url).with_query(description).with_cached(true);
+        }
+        let url = format!("{}/v1/query", self.base_url);
+        let cached = self.client.is_cached_post(&url, query);
+        crate::types::LookupTrace::new(url).with_query(description).with_cached(cached)
+    }
+
+    /// Runs `queries` as batches, so that [`vulnerabilities`](Self::vulnerabilities)
+    /// only has to hydrate the advisory ids found for each of them.
+    pub async fn prefetch(&self, queries: &[OsvQuery]) -> crate::Result<()> {
+        for chunk in queries.chunks(MAX_BATCH_QUERIES) {
+            let results = self.query_batch(chunk).await?;
+            let mut batched = self.batched.lock().unwrap_or_else(|e| e.into_inner());
+            for (query, ids) in chunk.iter().zip(results) {
+                batched.insert(serde_json::to_string(query).unwrap_or_default(), ids);
+            }
+        }
+        Ok(())
+    }
+
+    fn batched(&self, query: &OsvQuery) -> Option<Vec<String>> {
+        let key = serde_json::to_string(query).unwrap_or_default();
+        self.batched.lock().unwrap_or_else(|e| e.into_inner()).get(&key).cloned()
+    }
+
+    /// Queries OSV, or hydrates the ids a prefetched batch found, and
+    /// converts the advisories, along with their related advisories when
+    /// enabled.
+    pub async fn vulnerabilities(&self, query: &OsvQuery) -> crate::Result<Vec<crate::types::Vulnerability>> {
+        let mut advisories = match self.batched(query) {
+            Some(ids) => self.hydrate(&ids).await?,
+            None => self.query(query).await?,
+        };
+        let mut vulnerabilities = Vec::with_capacity(advisories.len());
+        advisories.retain(|advisory| {
+            let vulnerability = advisory.to_vulnerability();
+            let keep = self.above_floor(&vulnerability);
+            if keep {
+                vulnerabilities.push(vulnerability);
+            }
+            keep
+        });
+
+        if self.include_related {
+            let related = self.related(&advisories).await;
+            vulnerabilities.extend(related.into_iter().filter(|v| self.above_floor(v)));
+        }
+        Ok(vulnerabilities)
+    }
+
+    fn above_floor(&self, vulnerability: &crate::types::Vulnerability) -> bool {
+        match self.severity_floor {
+            Some(floor) => {
+                vulnerability.severity == crate::types::Severity::Unknown || vulnerability.severity.meets_threshold(floor)
+            }
+            None => true,
+        }
+    }
+
+    /// Hydrates the `related` ids of `advisories` that aren't results
+    /// themselves. One that can't be fetched is skipped with a warning
+    /// rather than failing the dependency's scan.
+    async fn related(&self, advisories: &[OsvVulnerability]) -> Vec<crate::types::Vulnerability> {
+        let mut seen: HashSet<&str> = advisories.iter().map(|a| a.id.as_str()).collect();
+        let mut wanted: Vec<(String, String)> = Vec::new();
+        for advisory in advisories {
+            for id in &advisory.related {
+                if seen.insert(id.as_str()) {
+                    wanted.push((advisory.id.clone(), id.clone()));
+                }
+            }
+        }
+        drop(seen);
+
+        stream::iter(wanted)
+            .map(|(primary, id)| async move {
+                match self.get_vulnerability(&id).await {
+                    Ok(advisory) => {
+                        let mut vulnerability = advisory.to_vulnerability();
+                        vulnerability.related_to = Some(primary);
+                        Some(vulnerability)
+                    }
+                    Err(e) => {
+                        eprintln!("Warning: Failed to fetch related advisory {}: {}", id, e);
+                        None
+                    }
+                }
+            })
+            .buffered(self.concurrency)
+            .filter_map(|vulnerability| async move { vulnerability })
+            .collect()
+            .await
+    }
+}
+
+impl OsvClient {
+    /// Fetches a full advisory by id from `/v1/vulns/{id}`.
+    ///
+    /// Advisories are cached by id alone in [`ADVISORY_CACHE_DIR`], so the
+    /// same advisory reached through different packages or scans is only
+    /// downloaded once a week.
+    pub async fn get_vulnerability(&self, id: &str) -> crate::Result<OsvVulnerability> {
+        let key = advisory_cache_key(id);
+
+        if let Some(cache) = &self.advisory_cache {
+            if let Some(entry) = cache.get::<OsvVulnerability>(ADVISORY_CACHE_DIR, &key).ok().flatten() {
+                return Ok(entry.data);
+            }
+        }
+
+        let advisory: OsvVulnerability = self
+            .advisories
+            .get(&format!("{}/v1/vulns/{}", self.base_url, id))
+            .await?;
+
+        if let Some(cache) = &self.advisory_cache {
+            if let Err(e) = cache.set(ADVISORY_CACHE_DIR, &key, &advisory) {
+                eprintln!("Warning: Failed to cache OSV advisory {}: {}", id, e);
+            }
+        }
+
+        Ok(advisory)
+    }
+
+    /// Advisory ids affecting each query, in the order given, from a single
+    /// `/v1/querybatch` request.
+    ///
+    /// The whole response is cached in [`BATCH_CACHE_DIR`] under a hash of
+    /// the API URL and the sorted queries, so repeating a scan of the same
+    /// dependency set, in any order, makes no request until the entry
+    /// expires. Results split over pages are followed to the last page.
+    pub async fn query_batch(&self, queries: &[OsvQuery]) -> crate::Result<Vec<Vec<String>>> {
+        #[derive(Serialize, Debug)]
+        struct BatchRequest<'a> {
+            queries: Vec<PagedQuery<'a>>,
+        }
+
+        #[derive(Serialize, Debug)]
+        struct PagedQuery<'a> {
+            #[serde(flatten)]
+            query: &'a OsvQuery,
+            #[serde(skip_serializing_if = "Option::is_none")]
+            page_token: Option<String>,
+        }
+
+        #[derive(Deserialize, Clone, Serialize)]
+        struct BatchResponse {
+            results: Vec<BatchResult>,
+        }
+
+        #[derive(Deserialize, Clone, Serialize)]
+        struct BatchResult {
+            #[serde(default)]
+            vulns: Vec<BatchVuln>,
+            #[serde(default)]
+            next_page_token: Option<String>,
+        }
+
+        #[derive(Deserialize, Clone, Serialize)]
+        struct BatchVuln {
+            id: String,
+        }
+
+        let mut keyed: Vec<(String, usize)> = queries
+            .iter()
+            .enumerate()
+            .map(|(i, q)| (serde_json::to_string(q).unwrap_or_default(), i))
+            .collect();
+        keyed.sort();
+        keyed.dedup_by(|a, b| a.0 == b.0);
+        let key = batch_cache_key(&self.base_url, keyed.iter().map(|(k, _)| k.as_str()));
+
+        let cached = self
+            .batch_cache
+            .as_ref()
+            .and_then(|cache| cache.get::<Vec<Vec<String>>>(BATCH_CACHE_DIR, &key).ok().flatten())
+            .map(|entry| entry.data)
+            .filter(|results| results.len() == keyed.len());
+
+        let sorted_results = match cached {
+            Some(results) => results,
+            None => {
+                let mut results: Vec<Vec<String>> = vec![Vec::new(); keyed.len()];
+                // Positions in `keyed` still to fetch, with the page to fetch.
+                let mut pages: Vec<(usize, Option<String>)> = (0..keyed.len()).map(|i| (i, None)).collect();
+                while !pages.is_empty() {
+                    let request = BatchRequest {
+                        queries: pages
+                            .iter()
+                            .map(|(i, token)| PagedQuery {
+                                query: &queries[keyed[*i].1],
+                                page_token: token.clone(),
+                            })
+                            .collect(),
+                    };
+                    let response: BatchResponse = self
+                        .advisories
+                        .post(&format!("{}/v1/querybatch", self.base_url), &request)
+                        .await?;
+                    if response.results.len() != pages.len() {
+                        return Err(crate::RensaError::Plugin {
+                            message: format!(
+                                "OSV returned {} batch results for {} queries",
+                                response.results.len(),
+                                pages.len()
+                            ),
+                        });
+                    }
+                    pages = pages
+                        .into_iter()
+                        .zip(response.results)
+                        .filter_map(|((i, _), result)| {
+                            results[i].extend(result.vulns.into_iter().map(|v| v.id));
+                            result.next_page_token.map(|token| (i, Some(token)))
+                        })
+                        .collect();
+                }
+
+                if let Some(cache) = &self.batch_cache {
+                    if let Err(e) = cache.set(BATCH_CACHE_DIR, &key, &results) {
+                        eprintln!("Warning: Failed to cache OSV batch response: {}", e);
+                    }
+                }
+                results
+            }
+        };
+
+        let by_query: std::collections::HashMap<&str, &Vec<String>> =
+            keyed.iter().map(|(k, _)| k.as_str()).zip(&sorted_results).collect();
+        Ok(queries
+            .iter()
+            .map(|q| {
+                let k = serde_json::to_string(q).unwrap_or_default();
+                by_query.get(k.as_str()).map(|ids| ids.to_vec()).unwrap_or_default()
+            })
+            .collect())
+    }
+
+    /// Hydrates a list of advisory ids, e.g. from a batch query.
+    pub async fn hydrate(&self, ids: &[String]) -> crate::Result<Vec<OsvVulnerability>> {
+        stream::iter(ids.to_vec())
+            .map(|id| async move { self.get_vulnerability(&id).await })
+            .buffered(self.concurrency)
+            .try_collect()
+            .await
+    }
+}
+
+/// Hashes the API URL and the serialized queries, which must already be
+/// sorted.
+fn batch_cache_key<'a>(base_url: &str, queries: impl Iterator<Item = &'a str>) -> String {
+    let mut hasher = DefaultHasher::new();
+    base_url.hash(&mut hasher);
+    for query in queries {
+        query.hash(&mut hasher);
+    }
+    format!("batch-{:016x}", hasher.finish())
 }
 
+fn advisory_cache_key(id: &str) -> String {
+    id.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '-' || c == '_' || c == '.' { c } else { '_' })
+        .collect()
+}
+
+/// A `/v1/query` request: a package at a version, or a git commit, which
+/// OSV matches against the commit ranges of every advisory, e.g. for
+/// dependencies installed from a repository rather than a registry.
 #[derive(Serialize, Debug, Clone)]
-pub struct OsvQuery {
-    pub package: OsvPackage,
-    pub version: String,
+#[serde(untagged)]
+pub enum OsvQuery {
+    Package { package: OsvPackage, version: String },
+    Commit { commit: String },
 }
 
 #[derive(Serialize, Debug, Clone, Deserialize)]
 pub struct OsvPackage {
     pub name: String,
+    /// OSV's ecosystem name, scoped to a release where OSV expects one,
+    /// e.g. `Debian:11` or `Alpine:v3.18`.
     pub ecosystem: String,
 }
 
@@ -60,9 +435,24 @@ pub struct OsvVulnerability {
     pub id: String,
     pub summary: String,
     pub details: String,
-    pub severity: Option<OsvSeverity>,
+    /// One entry per scoring system, e.g. both CVSS v2 and v3.1.
+    #[serde(default, deserialize_with = "null_as_empty")]
+    pub severity: Vec<OsvSeverity>,
     pub affected: Vec<OsvAffected>,
     pub references: Vec<OsvReference>,
+    /// Ids of advisories about the same issue, e.g. in another database.
+    #[serde(default)]
+    pub related: Vec<String>,
+    #[serde(default)]
+    pub database_specific: Option<OsvDatabaseSpecific>,
+}
+
+/// Fields the source database adds on top of the OSV schema.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OsvDatabaseSpecific {
+    /// Textual rating like GitHub's `"HIGH"` or `"MODERATE"`.
+    #[serde(default)]
+    pub severity: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +461,32 @@ pub struct OsvSeverity {
     pub score: String,
 }
 
+impl OsvSeverity {
+    /// Preference between scoring systems: CVSS 3.1, then 3.0, then 2.0.
+    fn rank(&self) -> u8 {
+        match self.r#type.as_str() {
+            "CVSS_V3" if self.score.starts_with("CVSS:3.1/") => 3,
+            "CVSS_V3" => 2,
+            "CVSS_V2" => 1,
+            _ => 0,
+        }
+    }
+
+    /// The numeric score, computed from the vector when that's all the
+    /// advisory gives.
+    fn base_score(&self) -> Option<f64> {
+        self.score.parse().ok().or_else(|| crate::cvss::base_score(&self.score))
+    }
+}
+
+fn null_as_empty<'de, D, T>(deserializer: D) -> std::result::Result<Vec<T>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Ok(Option::<Vec<T>>::deserialize(deserializer)?.unwrap_or_default())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OsvAffected {
     #[serde(default)]
@@ -103,13 +519,11 @@ impl OsvVulnerability {
     pub fn to_vulnerability(&self) -> crate::types::Vulnerability {
         let severity = self
             .severity
-            .as_ref()
-            .and_then(|s| {
-                s.score
-                    .parse::<f64>()
-                    .ok()
-                    .map(crate::types::Severity::from_cvss_score)
-            })
+            .iter()
+            .filter_map(|s| Some((s.rank(), s.base_score()?)))
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, score)| crate::types::Severity::from_cvss_score(score))
+            .or_else(|| self.database_severity())
             .unwrap_or(crate::types::Severity::Unknown);
 
         let fixed_versions: Vec<String> = self
@@ -128,6 +542,375 @@ impl OsvVulnerability {
             affected_versions: Vec::new(),
             fixed_versions,
             references: self.references.iter().map(|r| r.url.clone()).collect(),
+            package: None,
+            related_to: None,
+            remediation: None,
         }
     }
+
+    /// Falls back on the database's own rating for advisories, mostly
+    /// GHSA-sourced, that carry no CVSS score.
+    fn database_severity(&self) -> Option<crate::types::Severity> {
+        let severity = self.database_specific.as_ref()?.severity.as_deref()?;
+        match severity.to_ascii_uppercase().as_str() {
+            "CRITICAL" => Some(crate::types::Severity::Critical),
+            "HIGH" => Some(crate::types::Severity::High),
+            "MODERATE" | "MEDIUM" => Some(crate::types::Severity::Medium),
+            "LOW" => Some(crate::types::Severity::Low),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::CacheManager;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    const ADVISORY: &str = r#"{
+        "id": "GHSA-aaaa-bbbb-cccc",
+        "summary": "Prototype pollution",
+        "details": "Details",
+        "severity": null,
+        "affected": [],
+        "references": []
+    }"#;
+
+    #[tokio::test]
+    async fn test_hydration_is_cached_by_id() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/v1/vulns/GHSA-aaaa-bbbb-cccc"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(ADVISORY))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::new(temp_dir.path().to_path_buf());
+
+        let client = OsvClient::new(&server.uri()).with_cache(cache.clone());
+        let first = client.get_vulnerability("GHSA-aaaa-bbbb-cccc").await.unwrap();
+        assert_eq!(first.summary, "Prototype pollution");
+
+        // A later scan with a fresh client reads the same entry.
+        let rescan = OsvClient::new(&server.uri()).with_cache(cache.clone());
+        let ids = vec!["GHSA-aaaa-bbbb-cccc".to_string(), "GHSA-aaaa-bbbb-cccc".to_string()];
+        let hydrated = rescan.hydrate(&ids).await.unwrap();
+        assert_eq!(hydrated.len(), 2);
+        assert_eq!(hydrated[1].id, "GHSA-aaaa-bbbb-cccc");
+
+        let entry = cache
+            .get::<OsvVulnerability>(ADVISORY_CACHE_DIR, "GHSA-aaaa-bbbb-cccc")
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.ttl_seconds, ADVISORY_TTL.as_secs());
+    }
+
+    #[tokio::test]
+    async fn test_related_advisories_only_with_flag() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/query"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "vulns": [{
+                    "id": "GHSA-pppp-qqqq-rrrr",
+                    "summary": "Primary",
+                    "details": "",
+                    "affected": [],
+                    "references": [],
+                    "related": ["GHSA-aaaa-bbbb-cccc", "GHSA-pppp-qqqq-rrrr"]
+                }]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/vulns/GHSA-aaaa-bbbb-cccc"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(ADVISORY))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let query = OsvQuery::Package {
+            package: OsvPackage {
+                name: "lodash".to_string(),
+                ecosystem: "npm".to_string(),
+            },
+            version: "4.17.0".to_string(),
+        };
+
+        let without = OsvClient::new(&server.uri()).vulnerabilities(&query).await.unwrap();
+        assert_eq!(without.len(), 1);
+
+        let with = OsvClient::new(&server.uri()).with_related(true).vulnerabilities(&query).await.unwrap();
+        assert_eq!(with.len(), 2);
+        assert_eq!(with[0].related_to, None);
+        assert_eq!(with[1].id, "GHSA-aaaa-bbbb-cccc");
+        assert_eq!(with[1].related_to.as_deref(), Some("GHSA-pppp-qqqq-rrrr"));
+    }
+
+    #[tokio::test]
+    async fn test_severity_floor_drops_low_advisories_before_hydration() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/query"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "vulns": [
+                    {
+                        "id": "GHSA-low",
+                        "summary": "Low",
+                        "details": "",
+                        "affected": [],
+                        "references": [],
+                        "database_specific": { "severity": "LOW" },
+                        "related": ["CVE-low"]
+                    },
+                    {
+                        "id": "GHSA-high",
+                        "summary": "High",
+                        "details": "",
+                        "affected": [],
+                        "references": [],
+                        "database_specific": { "severity": "HIGH" }
+                    },
+                    {
+                        "id": "GHSA-unrated",
+                        "summary": "Unrated",
+                        "details": "",
+                        "affected": [],
+                        "references": []
+                    }
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/vulns/CVE-low"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(ADVISORY))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let query = OsvQuery::Package {
+            package: OsvPackage {
+                name: "lodash".to_string(),
+                ecosystem: "npm".to_string(),
+            },
+            version: "4.17.0".to_string(),
+        };
+
+        let all = OsvClient::new(&server.uri()).vulnerabilities(&query).await.unwrap();
+        assert_eq!(all.len(), 3);
+
+        let floored = OsvClient::new(&server.uri())
+            .with_related(true)
+            .with_severity_floor(SeverityThreshold::Medium)
+            .vulnerabilities(&query)
+            .await
+            .unwrap();
+        let ids: Vec<&str> = floored.iter().map(|v| v.id.as_str()).collect();
+        assert_eq!(ids, vec!["GHSA-high", "GHSA-unrated"]);
+    }
+
+    fn package_query(name: &str, version: &str) -> OsvQuery {
+        OsvQuery::Package {
+            package: OsvPackage {
+                name: name.to_string(),
+                ecosystem: "npm".to_string(),
+            },
+            version: version.to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_repeated_batch_is_served_from_cache() {
+        let server = MockServer::start().await;
+        // Queries are sent sorted: express before lodash.
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [
+                    {},
+                    { "vulns": [{ "id": "GHSA-aaaa-bbbb-cccc", "modified": "2024-01-01T00:00:00Z" }] }
+                ]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let cache = CacheManager::new(temp_dir.path().to_path_buf());
+
+        let client = OsvClient::new(&server.uri()).with_cache(cache.clone());
+        let queries = vec![package_query("lodash", "4.17.20"), package_query("express", "4.18.2")];
+        let first = client.query_batch(&queries).await.unwrap();
+        assert_eq!(first, vec![vec!["GHSA-aaaa-bbbb-cccc".to_string()], Vec::new()]);
+
+        let rescan = OsvClient::new(&server.uri()).with_cache(cache);
+        let reordered = vec![package_query("express", "4.18.2"), package_query("lodash", "4.17.20")];
+        let second = rescan.query_batch(&reordered).await.unwrap();
+        assert_eq!(second, vec![Vec::new(), vec!["GHSA-aaaa-bbbb-cccc".to_string()]]);
+    }
+
+    #[tokio::test]
+    async fn test_batch_results_are_followed_across_pages() {
+        use wiremock::matchers::body_string_contains;
+
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .and(body_string_contains(r#""page_token":"page-2""#))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [{ "vulns": [{ "id": "GHSA-2222-2222-2222" }] }]
+            })))
+            .with_priority(1)
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [{ "vulns": [{ "id": "GHSA-1111-1111-1111" }], "next_page_token": "page-2" }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let client = OsvClient::new(&server.uri());
+        let results = client.query_batch(&[package_query("lodash", "4.17.20")]).await.unwrap();
+        assert_eq!(results, vec![vec!["GHSA-1111-1111-1111".to_string(), "GHSA-2222-2222-2222".to_string()]]);
+    }
+
+    #[test]
+    fn test_batch_cache_key_covers_the_api_url() {
+        let queries = ["{\"commit\":\"abc\"}"];
+        assert_ne!(
+            batch_cache_key("https://api.osv.dev", queries.into_iter()),
+            batch_cache_key("https://osv.internal", queries.into_iter())
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prefetched_queries_only_hydrate_their_advisories() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/querybatch"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "results": [{ "vulns": [{ "id": "GHSA-aaaa-bbbb-cccc" }] }]
+            })))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/v1/vulns/GHSA-aaaa-bbbb-cccc"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(ADVISORY))
+            .expect(1)
+            .mount(&server)
+            .await;
+        Mock::given(method("POST"))
+            .and(path("/v1/query"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = OsvClient::new(&server.uri());
+        let query = package_query("lodash", "4.17.20");
+        client.prefetch(std::slice::from_ref(&query)).await.unwrap();
+
+        let vulnerabilities = client.vulnerabilities(&query).await.unwrap();
+        assert_eq!(vulnerabilities.len(), 1);
+        assert_eq!(vulnerabilities[0].id, "GHSA-aaaa-bbbb-cccc");
+        assert!(client.trace(&query).url.unwrap().ends_with("/v1/querybatch"));
+    }
+
+    #[test]
+    fn test_query_shapes() {
+        let commit = OsvQuery::Commit {
+            commit: "6879efc2c1596d11a6a6ad296f80063b558d5e0f".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&commit).unwrap(),
+            serde_json::json!({ "commit": "6879efc2c1596d11a6a6ad296f80063b558d5e0f" })
+        );
+
+        let package = OsvQuery::Package {
+            package: OsvPackage {
+                name: "openssl".to_string(),
+                ecosystem: "Debian:11".to_string(),
+            },
+            version: "1.1.1n-0+deb11u3".to_string(),
+        };
+        assert_eq!(
+            serde_json::to_value(&package).unwrap(),
+            serde_json::json!({
+                "package": { "name": "openssl", "ecosystem": "Debian:11" },
+                "version": "1.1.1n-0+deb11u3"
+            })
+        );
+    }
+
+    #[test]
+    fn test_severity_prefers_newest_cvss_version() {
+        let advisory: OsvVulnerability = serde_json::from_str(
+            r#"{
+                "id": "GHSA-xxxx-yyyy-zzzz",
+                "summary": "SQL injection",
+                "details": "",
+                "severity": [
+                    { "type": "CVSS_V2", "score": "AV:N/AC:M/Au:N/C:N/I:P/A:N" },
+                    { "type": "CVSS_V3", "score": "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H" },
+                    { "type": "CVSS_V3", "score": "CVSS:3.0/AV:N/AC:L/PR:L/UI:N/S:U/C:L/I:N/A:N" }
+                ],
+                "affected": [],
+                "references": []
+            }"#,
+        )
+        .unwrap();
+        assert_eq!(advisory.severity.len(), 3);
+        assert_eq!(advisory.to_vulnerability().severity, crate::types::Severity::Critical);
+
+        // Only the v2 vector is usable.
+        let mut v2_only = advisory.clone();
+        v2_only.severity.retain(|s| s.r#type == "CVSS_V2");
+        assert_eq!(v2_only.to_vulnerability().severity, crate::types::Severity::Medium);
+    }
+
+    #[test]
+    fn test_textual_database_severity_fallback() {
+        let advisory = |severity: &str| -> OsvVulnerability {
+            serde_json::from_value(serde_json::json!({
+                "id": "GHSA-xxxx-yyyy-zzzz",
+                "summary": "Open redirect",
+                "details": "",
+                "affected": [],
+                "references": [],
+                "database_specific": { "severity": severity, "github_reviewed": true }
+            }))
+            .unwrap()
+        };
+
+        assert_eq!(advisory("CRITICAL").to_vulnerability().severity, crate::types::Severity::Critical);
+        assert_eq!(advisory("HIGH").to_vulnerability().severity, crate::types::Severity::High);
+        assert_eq!(advisory("MODERATE").to_vulnerability().severity, crate::types::Severity::Medium);
+        assert_eq!(advisory("LOW").to_vulnerability().severity, crate::types::Severity::Low);
+        assert_eq!(advisory("SEVERE").to_vulnerability().severity, crate::types::Severity::Unknown);
+
+        // A CVSS score still wins over the textual rating.
+        let mut scored = advisory("LOW");
+        scored.severity.push(OsvSeverity {
+            r#type: "CVSS_V3".to_string(),
+            score: "CVSS:3.1/AV:N/AC:L/PR:N/UI:N/S:U/C:H/I:H/A:H".to_string(),
+        });
+        assert_eq!(scored.to_vulnerability().severity, crate::types::Severity::Critical);
+    }
+
+    #[test]
+    fn test_missing_severity_is_unknown() {
+        let advisory: OsvVulnerability = serde_json::from_str(ADVISORY).unwrap();
+        assert!(advisory.severity.is_empty());
+        assert_eq!(advisory.to_vulnerability().severity, crate::types::Severity::Unknown);
+    }
 }
@@ -15,12 +15,71 @@ pub trait Parser: Send + Sync {
     fn ecosystem(&self) -> Ecosystem;
     
     async fn parse(&self, file: &DependencyFile) -> Result<Vec<Dependency>>;
+
+    /// Runtimes the file requires (e.g. composer's `require.php`). These are
+    /// not scanned as dependencies but are checked for end-of-life.
+    fn runtime_requirements(&self, _file: &DependencyFile) -> Result<Vec<Dependency>> {
+        Ok(Vec::new())
+    }
+
+    /// Problems with the file worth surfacing as report warnings, such as a
+    /// lock file that no longer matches its manifest.
+    fn warnings(&self, _file: &DependencyFile) -> Vec<String> {
+        Vec::new()
+    }
+
+    /// Package versions the file declares itself incompatible with, such as
+    /// composer's `conflict` section. `version` holds the declared range.
+    fn conflicts(&self, _file: &DependencyFile) -> Vec<Dependency> {
+        Vec::new()
+    }
+
+    /// Parser options that change what `parse` returns. Cached scan results
+    /// are only reused when this matches the options they were made with.
+    fn options_fingerprint(&self) -> String {
+        String::new()
+    }
+
+    /// Whether `parse` returns dev dependencies at all, e.g. not when
+    /// composer is told to skip `require-dev`.
+    fn includes_dev(&self) -> bool {
+        true
+    }
+
+    /// The package the file itself declares, e.g. composer.json's `name`.
+    fn project(&self, _file: &DependencyFile) -> Option<crate::report::ProjectInfo> {
+        None
+    }
 }
 
 #[async_trait]
 pub trait RegistryClient: Send + Sync {
     fn ecosystem(&self) -> Ecosystem;
     
+    /// Whether `name` is a well-formed package name for this registry.
+    /// Invalid names are skipped by the scanner instead of being requested.
+    fn is_valid_name(&self, name: &str) -> bool {
+        !name.is_empty() && !name.chars().any(|c| c.is_whitespace() || c.is_control())
+    }
+
+    /// Base URL of the registry, used by `rensa doctor` to check it can be
+    /// reached.
+    fn endpoint(&self) -> Option<&str> {
+        None
+    }
+
+    /// Where `dependency` is looked up and whether the answer is already
+    /// cached, for trace mode. Called just before the lookup.
+    fn trace(&self, _dependency: &Dependency) -> Option<crate::types::LookupTrace> {
+        None
+    }
+
+    /// Client options that change what lookups return. Cached scan results
+    /// are only reused when this matches; by default it is the endpoint.
+    fn options_fingerprint(&self) -> String {
+        self.endpoint().unwrap_or_default().to_string()
+    }
+
     async fn get_latest_version(&self, dependency: &Dependency) -> Result<Option<String>>;
     
     async fn get_update_info(&self, dependency: &Dependency) -> Result<Option<crate::types::UpdateInfo>> {
@@ -34,14 +93,51 @@ pub trait RegistryClient: Send + Sync {
             current_version: dependency.version.clone(),
             latest_version: latest,
             changelog: None,
+            latest_in_constraint: None,
+            versions_behind: None,
+            age_behind_days: None,
+            intermediate_releases: Vec::new(),
         }))
     }
+
+    /// Update info plus package metadata such as the source repository.
+    /// Registries that publish metadata override this to fetch both at once.
+    async fn get_package_info(&self, dependency: &Dependency) -> Result<crate::types::PackageInfo> {
+        Ok(crate::types::PackageInfo {
+            update: self.get_update_info(dependency).await?,
+            repository: None,
+            license: None,
+            provenance: None,
+            deprecated: None,
+            versions: Vec::new(),
+            released: std::collections::HashMap::new(),
+        })
+    }
 }
 
 #[async_trait]
 pub trait VulnerabilityScanner: Send + Sync {
     fn ecosystem(&self) -> Ecosystem;
-    
+
+    /// What is queried for `dependency` and whether the answer is already
+    /// cached, for trace mode. Called just before the scan.
+    fn trace(&self, _dependency: &Dependency) -> Option<crate::types::LookupTrace> {
+        None
+    }
+
+    /// Scanner options that change what `scan` returns, such as the
+    /// database queried. Cached scan results are only reused when this
+    /// matches.
+    fn options_fingerprint(&self) -> String {
+        String::new()
+    }
+
+    /// Looks `dependencies` up ahead of their `scan` calls, e.g. in one
+    /// batch request, so each `scan` is answered from what was fetched.
+    async fn prefetch(&self, _dependencies: &[Dependency]) -> Result<()> {
+        Ok(())
+    }
+
     async fn scan(&self, dependency: &Dependency) -> Result<Vec<crate::types::Vulnerability>>;
 }
 
@@ -110,12 +206,22 @@ impl PluginRegistry {
             .map(|(_, d)| d.as_ref())
     }
 
+    /// All registered detectors, in registration order.
+    pub fn detectors(&self) -> impl Iterator<Item = &dyn Detector> {
+        self.detectors.iter().map(|(_, d)| d.as_ref())
+    }
+
     pub fn get_parser(&self, ecosystem: &Ecosystem) -> Option<&dyn Parser> {
         self.parsers.iter()
             .find(|(e, _)| e == ecosystem)
             .map(|(_, p)| p.as_ref())
     }
 
+    /// All registered registry clients, in registration order.
+    pub fn registry_clients(&self) -> impl Iterator<Item = &dyn RegistryClient> {
+        self.registry_clients.iter().map(|(_, c)| c.as_ref())
+    }
+
     pub fn get_registry_client(&self, ecosystem: &Ecosystem) -> Option<&dyn RegistryClient> {
         self.registry_clients.iter()
             .find(|(e, _)| e == ecosystem)
@@ -0,0 +1,92 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+/// Dependency counts for a running scan, shared by every lookup across
+/// ecosystems so one progress bar can cover the whole scan. Readers take a
+/// [`ScanProgress::snapshot`] from any thread while the scan updates it.
+#[derive(Debug, Default)]
+pub struct ScanProgress {
+    total: AtomicUsize,
+    completed: AtomicUsize,
+    /// Sources whose manifests are still being parsed, and may still add
+    /// to `total`.
+    parsing: AtomicUsize,
+}
+
+/// The counts at one point during a scan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ProgressSnapshot {
+    /// Dependencies queued for lookup so far.
+    pub total: usize,
+    /// Dependencies whose lookups have finished.
+    pub completed: usize,
+    /// Whether every manifest is parsed, so `total` won't grow any more.
+    pub total_known: bool,
+}
+
+impl ScanProgress {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn snapshot(&self) -> ProgressSnapshot {
+        // Read `completed` first so it never appears ahead of `total`.
+        let completed = self.completed.load(Ordering::Acquire);
+        ProgressSnapshot {
+            total: self.total.load(Ordering::Acquire),
+            completed,
+            total_known: self.parsing.load(Ordering::Acquire) == 0,
+        }
+    }
+
+    /// Announces `sources` paths whose manifests are about to be parsed.
+    pub(crate) fn start_parsing(&self, sources: usize) {
+        self.parsing.fetch_add(sources, Ordering::AcqRel);
+    }
+
+    pub(crate) fn finish_parsing(&self) {
+        let _ = self.parsing.fetch_update(Ordering::AcqRel, Ordering::Acquire, |n| n.checked_sub(1));
+    }
+
+    pub(crate) fn add_total(&self, dependencies: usize) {
+        self.total.fetch_add(dependencies, Ordering::AcqRel);
+    }
+
+    pub(crate) fn complete_one(&self) {
+        self.completed.fetch_add(1, Ordering::AcqRel);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    #[test]
+    fn test_counts_from_many_threads_add_up() {
+        let progress = Arc::new(ScanProgress::new());
+        progress.start_parsing(1);
+        assert!(!progress.snapshot().total_known);
+
+        let threads: Vec<_> = (0..8)
+            .map(|_| {
+                let progress = Arc::clone(&progress);
+                std::thread::spawn(move || {
+                    for _ in 0..100 {
+                        progress.add_total(1);
+                        progress.complete_one();
+                    }
+                })
+            })
+            .collect();
+        for thread in threads {
+            thread.join().unwrap();
+        }
+        progress.finish_parsing();
+
+        assert_eq!(progress.snapshot(), ProgressSnapshot {
+            total: 800,
+            completed: 800,
+            total_known: true,
+        });
+    }
+}
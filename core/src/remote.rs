@@ -0,0 +1,95 @@
+use crate::error::{RensaError, Result};
+use crate::http::HttpClient;
+use std::process::Command;
+use tempfile::TempDir;
+
+/// Downloads a raw composer.json, e.g. from a repository host at a tag,
+/// into a fresh directory that can be scanned like a checkout.
+pub async fn fetch_manifest(client: &HttpClient, url: &str) -> Result<TempDir> {
+    let manifest: serde_json::Value = client.get(url).await?;
+    if !manifest.is_object() {
+        return Err(RensaError::Plugin {
+            message: format!("{} did not return a composer.json object", url),
+        });
+    }
+
+    let dir = TempDir::new()?;
+    let content = serde_json::to_string_pretty(&manifest).map_err(|e| RensaError::Plugin {
+        message: format!("failed to write manifest from {}: {}", url, e),
+    })?;
+    std::fs::write(dir.path().join("composer.json"), content)?;
+    Ok(dir)
+}
+
+/// Shallow-clones `repo` at `git_ref` (a branch or tag; the default branch
+/// when `None`) into a temporary directory, removed when it is dropped.
+pub fn clone_repository(repo: &str, git_ref: Option<&str>) -> Result<TempDir> {
+    let dir = TempDir::new()?;
+
+    let mut command = Command::new("git");
+    command.args(["clone", "--quiet", "--depth", "1"]);
+    if let Some(git_ref) = git_ref {
+        command.args(["--branch", git_ref]);
+    }
+    let output = command
+        .arg("--")
+        .arg(repo)
+        .arg(dir.path())
+        .output()
+        .map_err(|e| RensaError::Git {
+            message: format!("failed to run git: {}", e),
+        })?;
+
+    if !output.status.success() {
+        return Err(RensaError::Git {
+            message: format!("failed to clone {}: {}", repo, String::from_utf8_lossy(&output.stderr).trim()),
+        });
+    }
+    Ok(dir)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_fetched_manifest_is_written_to_a_scannable_dir() {
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/acme/app/v1.2.0/composer.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "name": "acme/app",
+                "require": {"monolog/monolog": "^2.0"}
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/acme/app/v1.2.0/README.md"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!(["not", "a", "manifest"])))
+            .mount(&server)
+            .await;
+        let client = HttpClient::new().with_retries(0);
+
+        let dir = fetch_manifest(&client, &format!("{}/acme/app/v1.2.0/composer.json", server.uri()))
+            .await
+            .unwrap();
+        let written: serde_json::Value =
+            serde_json::from_str(&std::fs::read_to_string(dir.path().join("composer.json")).unwrap()).unwrap();
+        assert_eq!(written["require"]["monolog/monolog"], "^2.0");
+
+        assert!(fetch_manifest(&client, &format!("{}/acme/app/v1.2.0/README.md", server.uri())).await.is_err());
+        assert!(fetch_manifest(&client, &format!("{}/missing/composer.json", server.uri())).await.is_err());
+    }
+
+    #[test]
+    fn test_clone_failure_is_a_git_error() {
+        let missing = TempDir::new().unwrap();
+        let repo = missing.path().join("no-such-repo");
+        assert!(matches!(
+            clone_repository(repo.to_str().unwrap(), Some("v1.0.0")),
+            Err(RensaError::Git { .. })
+        ));
+    }
+}
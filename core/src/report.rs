@@ -1,16 +1,44 @@
-use crate::{Dependency, Ecosystem, Severity, UpdateInfo, UpdateType, VersionComparator, Vulnerability};
+use crate::eol::EndOfLife;
+use crate::error::RensaError;
+use crate::{ClassifyOptions, Dependency, Ecosystem, Severity, SeverityThreshold, UpdateInfo, UpdateType, VersionComparator, Vulnerability};
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
-use std::{collections::HashMap, path::PathBuf};
+use std::{collections::{BTreeMap, HashMap, HashSet}, path::PathBuf};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanReport {
+    #[serde(default)]
+    pub rensa_version: String,
+
+    #[serde(default)]
+    pub scan_config: ScanConfigSnapshot,
+
     pub timestamp: DateTime<Utc>,
 
     pub scanned_path: PathBuf,
 
+    /// The package the root manifest declares itself as, if it names one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub project: Option<ProjectInfo>,
+
     pub elapsed: u64,
 
+    /// Wall time in milliseconds spent finding manifests.
+    #[serde(default)]
+    pub detect_ms: u64,
+
+    /// Wall time in milliseconds spent parsing manifests.
+    #[serde(default)]
+    pub parse_ms: u64,
+
+    /// Wall time in milliseconds spent on registry and end-of-life lookups.
+    #[serde(default)]
+    pub registry_ms: u64,
+
+    /// Wall time in milliseconds spent querying vulnerability databases.
+    #[serde(default)]
+    pub vuln_ms: u64,
+
     pub total_dependency_files: usize,
 
     pub total_dependencies: usize,
@@ -26,9 +54,112 @@ pub struct ScanReport {
     pub warnings: Vec<String>,
 
     pub errors: Vec<String>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial: Option<PartialScan>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub end_of_life: Vec<EndOfLife>,
+
+    #[serde(default)]
+    pub sources: Vec<SourceStatus>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unresolved_dependencies: Vec<UnresolvedDependency>,
+
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub effective_constraints: Vec<EffectiveConstraint>,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceKind {
+    Registry,
+    VulnerabilityDatabase,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Reachability {
+    /// Responded successfully at least once.
+    Reachable,
+    /// Never responded; the last failure was a timeout.
+    TimedOut,
+    /// Never responded; the last failure was an error.
+    Unreachable,
+}
+
+/// Whether a registry or vulnerability database answered during the scan,
+/// so an empty result can be told apart from an unreachable source.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SourceStatus {
+    pub ecosystem: Ecosystem,
+    pub source: SourceKind,
+    pub status: Reachability,
+    pub successes: usize,
+    pub failures: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub last_error: Option<String>,
+}
+
+/// The scanned project's own identity, e.g. composer.json's `name` and
+/// `version`. Applications often leave these out.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProjectInfo {
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl std::fmt::Display for ProjectInfo {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match &self.version {
+            Some(version) => write!(f, "{} {}", self.name, version),
+            None => write!(f, "{}", self.name),
+        }
+    }
+}
+
+/// The effective settings a report was produced with.
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct ScanConfigSnapshot {
+    pub ecosystems: Vec<Ecosystem>,
+    pub severity_threshold: SeverityThreshold,
+    /// Dev dependencies are always scanned today; recorded so reports stay
+    /// comparable once filtering is configurable.
+    pub include_dev: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub limit: Option<usize>,
+}
+
+/// What changed between a baseline report and a newer one.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReportDiff {
+    pub new_vulnerabilities: Vec<Vulnerability>,
+    pub resolved_vulnerabilities: Vec<Vulnerability>,
+    pub new_updates: Vec<UpdateInfo>,
+}
+
+impl ReportDiff {
+    pub fn is_empty(&self) -> bool {
+        self.new_vulnerabilities.is_empty() && self.resolved_vulnerabilities.is_empty() && self.new_updates.is_empty()
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string_pretty(self)
+    }
+}
+
+/// Set when a scan was capped and only a subset of dependencies was checked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PartialScan {
+    pub scanned: usize,
+    pub total: usize,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ScanSummary {
     pub updates_available: usize,
     pub vulnerabilities_found: usize,
@@ -38,6 +169,57 @@ pub struct ScanSummary {
     pub low_vulnerabilities: usize,
     pub outdated_dependencies: usize,
     pub up_to_date_dependencies: usize,
+    /// Vulnerabilities in dependencies the project requires itself.
+    #[serde(default)]
+    pub direct_vulnerabilities: usize,
+    /// Vulnerabilities only found in dependencies of dependencies.
+    #[serde(default)]
+    pub transitive_vulnerabilities: usize,
+    /// Dependencies that are unresolved or couldn't be fully checked.
+    #[serde(default)]
+    pub unchecked_dependencies: usize,
+    /// Fraction of dependencies that were fully checked, from 0 to 1.
+    #[serde(default = "full_coverage")]
+    pub coverage: f32,
+}
+
+fn full_coverage() -> f32 {
+    1.0
+}
+
+impl Default for ScanSummary {
+    fn default() -> Self {
+        Self {
+            updates_available: 0,
+            vulnerabilities_found: 0,
+            critical_vulnerabilities: 0,
+            high_vulnerabilities: 0,
+            medium_vulnerabilities: 0,
+            low_vulnerabilities: 0,
+            outdated_dependencies: 0,
+            up_to_date_dependencies: 0,
+            direct_vulnerabilities: 0,
+            transitive_vulnerabilities: 0,
+            unchecked_dependencies: 0,
+            coverage: full_coverage(),
+        }
+    }
+}
+
+impl ScanSummary {
+    /// How many vulnerabilities meet `threshold`, from the counters alone.
+    pub fn vulnerabilities_at_or_above(&self, threshold: SeverityThreshold) -> usize {
+        [
+            (Severity::Low, self.low_vulnerabilities),
+            (Severity::Medium, self.medium_vulnerabilities),
+            (Severity::High, self.high_vulnerabilities),
+            (Severity::Critical, self.critical_vulnerabilities),
+        ]
+        .iter()
+        .filter(|(severity, _)| severity.meets_threshold(threshold))
+        .map(|(_, count)| count)
+        .sum()
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -48,14 +230,56 @@ pub struct EcosystemScanResult {
     pub updates: Vec<UpdateInfo>,
     pub vulnerabilities: Vec<Vulnerability>,
     pub errors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unresolved: Vec<UnresolvedDependency>,
+    /// Dependencies that couldn't be fully checked, e.g. because a lookup
+    /// failed or the name is invalid.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unchecked: Vec<String>,
+}
+
+/// A dependency its registry has never heard of, as opposed to one whose
+/// lookup failed. Often a typo, a removed package or a private package
+/// looked up in the public registry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UnresolvedDependency {
+    pub name: String,
+    pub ecosystem: Ecosystem,
+    pub file: PathBuf,
+    pub reason: String,
+}
+
+/// A package declared with differing constraints, e.g. by several manifests
+/// in a monorepo or through composer's merge-plugin, and the range that
+/// satisfies all of them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EffectiveConstraint {
+    pub name: String,
+    pub ecosystem: Ecosystem,
+    pub declared: Vec<DeclaredConstraint>,
+    /// `None` when no version satisfies every declared constraint.
+    pub effective: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeclaredConstraint {
+    pub constraint: String,
+    pub file: PathBuf,
 }
 
 impl ScanReport {
     pub fn new(scanned_path: PathBuf) -> Self {
         Self {
-            timestamp: Utc::now(),
+            rensa_version: env!("CARGO_PKG_VERSION").to_string(),
+            scan_config: ScanConfigSnapshot::default(),
+            timestamp: report_timestamp(),
             scanned_path,
+            project: None,
             elapsed: 0,
+            detect_ms: 0,
+            parse_ms: 0,
+            registry_ms: 0,
+            vuln_ms: 0,
             total_dependency_files: 0,
             total_dependencies: 0,
             summary: ScanSummary::default(),
@@ -64,17 +288,66 @@ impl ScanReport {
             vulnerabilities: Vec::new(),
             warnings: Vec::new(),
             errors: Vec::new(),
+            partial: None,
+            end_of_life: Vec::new(),
+            sources: Vec::new(),
+            unresolved_dependencies: Vec::new(),
+            effective_constraints: Vec::new(),
         }
     }
 
+    /// Intersects the constraints of every package declared more than one
+    /// way, ordered by ecosystem and name.
+    pub fn intersect_constraints(&self) -> Vec<EffectiveConstraint> {
+        let mut declared: BTreeMap<(Ecosystem, &str), Vec<&Dependency>> = BTreeMap::new();
+        for result in self.ecosystem_results.values() {
+            for dep in &result.dependencies {
+                declared.entry((dep.ecosystem, dep.name.as_str())).or_default().push(dep);
+            }
+        }
+
+        declared
+            .into_iter()
+            .filter(|(_, deps)| deps.iter().any(|d| d.constraint != deps[0].constraint))
+            .map(|((ecosystem, name), deps)| {
+                let effective = deps[1..]
+                    .iter()
+                    .try_fold(deps[0].constraint.clone(), |acc, d| acc.intersect(&d.constraint));
+                EffectiveConstraint {
+                    name: name.to_string(),
+                    ecosystem,
+                    declared: deps
+                        .iter()
+                        .map(|d| DeclaredConstraint {
+                            constraint: d.constraint.to_string(),
+                            file: d.file.clone(),
+                        })
+                        .collect(),
+                    effective: effective.map(|c| c.to_string()),
+                }
+            })
+            .collect()
+    }
+
     pub fn add_ecosystem_result(&mut self, ecosystem: Ecosystem, result: EcosystemScanResult) {
         self.total_dependency_files += result.files_found.len();
         self.total_dependencies += result.dependencies.len();
 
-        self.summary.updates_available += result.updates.len(); 
-        self.summary.low_vulnerabilities += result.vulnerabilities.len(); 
+        self.summary.updates_available += result.updates.len();
+        self.summary.vulnerabilities_found += result.vulnerabilities.len();
+
+        // A package counts as direct if any manifest requires it itself.
+        let direct: HashSet<&str> = result.dependencies.iter().filter(|d| d.direct).map(|d| d.name.as_str()).collect();
+        let transitive: HashSet<&str> =
+            result.dependencies.iter().filter(|d| !d.direct).map(|d| d.name.as_str()).collect();
 
         for vuln in &result.vulnerabilities {
+            let package = vuln.package.as_deref();
+            if package.is_some_and(|p| transitive.contains(p) && !direct.contains(p)) {
+                self.summary.transitive_vulnerabilities += 1;
+            } else {
+                self.summary.direct_vulnerabilities += 1;
+            }
             match vuln.severity {
                 Severity::Low => self.summary.low_vulnerabilities += 1,
                 Severity::Medium => self.summary.medium_vulnerabilities += 1,
@@ -84,33 +357,528 @@ impl ScanReport {
             }
         }
 
+        self.summary.unchecked_dependencies += result.unchecked.len() + result.unresolved.len();
+        self.summary.coverage = match self.total_dependencies {
+            0 => 1.0,
+            total => total.saturating_sub(self.summary.unchecked_dependencies) as f32 / total as f32,
+        };
+
         self.summary.outdated_dependencies += result.updates.len();
         self.summary.up_to_date_dependencies += 
             result.dependencies.len().saturating_sub(result.updates.len());
 
-        self.ecosystem_results.insert(ecosystem, result);
+        self.updates.extend(result.updates.iter().cloned());
+        self.vulnerabilities.extend(result.vulnerabilities.iter().cloned());
+        self.unresolved_dependencies.extend(result.unresolved.iter().cloned());
+
+        match self.ecosystem_results.get_mut(&ecosystem) {
+            Some(existing) => {
+                existing.files_found.extend(result.files_found);
+                existing.dependencies.extend(result.dependencies);
+                existing.updates.extend(result.updates);
+                existing.vulnerabilities.extend(result.vulnerabilities);
+                existing.errors.extend(result.errors);
+                existing.unresolved.extend(result.unresolved);
+                existing.unchecked.extend(result.unchecked);
+            }
+            None => {
+                self.ecosystem_results.insert(ecosystem, result);
+            }
+        }
+    }
+
+    /// Folds another report's counters, warnings and errors into this one,
+    /// but not its dependencies, updates or vulnerabilities, for scans that
+    /// write those out as they go.
+    pub fn merge_summary(&mut self, other: ScanReport) {
+        self.merge(other);
+        self.ecosystem_results.clear();
+        self.updates.clear();
+        self.vulnerabilities.clear();
+        self.effective_constraints.clear();
+    }
+
+    /// Folds another report into this one, e.g. when several paths are
+    /// scanned in one invocation. The scanned path of `self` is kept.
+    pub fn merge(&mut self, other: ScanReport) {
+        let partial = match (&self.partial, &other.partial) {
+            (None, None) => None,
+            _ => {
+                let (mine, theirs) = (self.coverage(), other.coverage());
+                Some(PartialScan {
+                    scanned: mine.scanned + theirs.scanned,
+                    total: mine.total + theirs.total,
+                })
+            }
+        };
+
+        let mut ecosystems: Vec<_> = other.ecosystem_results.into_iter().collect();
+        ecosystems.sort_by_key(|(ecosystem, _)| *ecosystem);
+        for (ecosystem, result) in ecosystems {
+            self.add_ecosystem_result(ecosystem, result);
+        }
+
+        for ecosystem in other.scan_config.ecosystems {
+            if !self.scan_config.ecosystems.contains(&ecosystem) {
+                self.scan_config.ecosystems.push(ecosystem);
+            }
+        }
+        self.scan_config.ecosystems.sort();
+        self.effective_constraints = self.intersect_constraints();
+
+        self.partial = partial;
+        self.project = self.project.take().or(other.project);
+        self.timestamp = self.timestamp.min(other.timestamp);
+        self.elapsed += other.elapsed;
+        self.detect_ms += other.detect_ms;
+        self.parse_ms += other.parse_ms;
+        self.registry_ms += other.registry_ms;
+        self.vuln_ms += other.vuln_ms;
+        self.end_of_life.extend(other.end_of_life);
+        for theirs in other.sources {
+            match self
+                .sources
+                .iter_mut()
+                .find(|s| s.ecosystem == theirs.ecosystem && s.source == theirs.source)
+            {
+                Some(mine) => {
+                    if mine.successes == 0 {
+                        mine.status = theirs.status;
+                    }
+                    mine.successes += theirs.successes;
+                    mine.failures += theirs.failures;
+                    mine.last_error = theirs.last_error.or(mine.last_error.take());
+                }
+                None => self.sources.push(theirs),
+            }
+        }
+        self.warnings.extend(other.warnings);
+        self.errors.extend(other.errors);
+    }
+
+    fn coverage(&self) -> PartialScan {
+        self.partial.clone().unwrap_or(PartialScan {
+            scanned: self.total_dependencies,
+            total: self.total_dependencies,
+        })
+    }
+
+    /// Records the outcome of one request to a registry or vulnerability database.
+    pub fn record_source(&mut self, ecosystem: Ecosystem, source: SourceKind, outcome: Result<(), &RensaError>) {
+        let index = match self.sources.iter().position(|s| s.ecosystem == ecosystem && s.source == source) {
+            Some(i) => i,
+            None => {
+                self.sources.push(SourceStatus {
+                    ecosystem,
+                    source,
+                    status: Reachability::Unreachable,
+                    successes: 0,
+                    failures: 0,
+                    last_error: None,
+                });
+                self.sources.len() - 1
+            }
+        };
+        let status = &mut self.sources[index];
+
+        match outcome {
+            Ok(()) => {
+                status.successes += 1;
+                status.status = Reachability::Reachable;
+            }
+            Err(e) => {
+                status.failures += 1;
+                status.last_error = Some(e.to_string());
+                if status.successes == 0 {
+                    status.status = if e.is_timeout() { Reachability::TimedOut } else { Reachability::Unreachable };
+                }
+            }
+        }
+    }
+
+    /// Vulnerability databases that never answered, so "no vulnerabilities"
+    /// in their ecosystem means it couldn't be checked, not that it's clean.
+    pub fn unreachable_vulnerability_databases(&self) -> Vec<&SourceStatus> {
+        self.sources
+            .iter()
+            .filter(|s| s.source == SourceKind::VulnerabilityDatabase && s.status != Reachability::Reachable)
+            .collect()
     }
 
     pub fn has_critical_vulnerabilities(&self) -> bool {
         self.summary.critical_vulnerabilities > 0
     }
 
+    pub fn vulnerabilities_at_or_above(&self, threshold: SeverityThreshold) -> Vec<&Vulnerability> {
+        self.vulnerabilities
+            .iter()
+            .filter(|v| v.severity.meets_threshold(threshold))
+            .collect()
+    }
+
     pub fn has_updates(&self) -> bool {
         self.summary.updates_available > 0
     }
 
     pub fn updates_by_type(&self) -> HashMap<UpdateType, Vec<&UpdateInfo>> {
+        self.updates_by_type_with(ClassifyOptions::default())
+    }
+
+    pub fn updates_by_type_with(&self, options: ClassifyOptions) -> HashMap<UpdateType, Vec<&UpdateInfo>> {
         let mut grouped: HashMap<UpdateType, Vec<&UpdateInfo>> = HashMap::new();
 
         for update in &self.updates {
-            let update_type = VersionComparator::classify_update(&update.current_version, &update.latest_version);
+            let update_type =
+                VersionComparator::classify_update_with(&update.current_version, &update.latest_version, options);
             grouped.entry(update_type).or_default().push(update);
         }
 
         grouped
     }
 
+    /// Returns a copy of the report whose detail lists only contain
+    /// dependencies with an available update or a vulnerability. Totals and
+    /// the summary still describe the full scan.
+    pub fn actionable(&self) -> ScanReport {
+        let mut report = self.clone();
+
+        for result in report.ecosystem_results.values_mut() {
+            let flagged: HashSet<&str> = result
+                .updates
+                .iter()
+                .map(|u| u.dependency.name.as_str())
+                .chain(result.vulnerabilities.iter().filter_map(|v| v.package.as_deref()))
+                .collect();
+
+            let dependencies = result
+                .dependencies
+                .iter()
+                .filter(|d| flagged.contains(d.name.as_str()))
+                .cloned()
+                .collect();
+            result.dependencies = dependencies;
+        }
+
+        report
+    }
+
+    /// Compares this report against an earlier `baseline`. Vulnerabilities
+    /// are matched by package and advisory id, updates by package and the
+    /// version offered, so a newer release of an already-outdated package
+    /// counts as a new update.
+    pub fn diff(&self, baseline: &ScanReport) -> ReportDiff {
+        let vuln_key = |v: &Vulnerability| (v.package.clone(), v.id.clone());
+        let update_key = |u: &UpdateInfo| (u.dependency.name.clone(), u.latest_version.clone());
+
+        let before: HashSet<_> = baseline.vulnerabilities.iter().map(vuln_key).collect();
+        let after: HashSet<_> = self.vulnerabilities.iter().map(vuln_key).collect();
+        let known_updates: HashSet<_> = baseline.updates.iter().map(update_key).collect();
+
+        ReportDiff {
+            new_vulnerabilities: self
+                .vulnerabilities
+                .iter()
+                .filter(|v| !before.contains(&vuln_key(v)))
+                .cloned()
+                .collect(),
+            resolved_vulnerabilities: baseline
+                .vulnerabilities
+                .iter()
+                .filter(|v| !after.contains(&vuln_key(v)))
+                .cloned()
+                .collect(),
+            new_updates: self
+                .updates
+                .iter()
+                .filter(|u| !known_updates.contains(&update_key(u)))
+                .cloned()
+                .collect(),
+        }
+    }
+
     pub fn to_json(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string_pretty(self)
     }
+
+    pub fn to_yaml(&self) -> Result<String, serde_yaml::Error> {
+        serde_yaml::to_string(self)
+    }
+
+    /// TOML has no null and only string table keys, so `ecosystem_results`
+    /// becomes an array (each entry already names its ecosystem) and unset
+    /// values are dropped.
+    pub fn to_toml(&self) -> Result<String, toml::ser::Error> {
+        let mut value = serde_json::to_value(self).map_err(serde::ser::Error::custom)?;
+
+        if let Some(results) = value.get_mut("ecosystem_results") {
+            let mut entries: Vec<serde_json::Value> = match results.take() {
+                serde_json::Value::Object(map) => map.into_iter().map(|(_, v)| v).collect(),
+                other => vec![other],
+            };
+            entries.sort_by_key(|e| e["ecosystem"].as_str().map(str::to_string));
+            *results = serde_json::Value::Array(entries);
+        }
+
+        strip_nulls(&mut value);
+        toml::to_string_pretty(&value)
+    }
+}
+
+fn strip_nulls(value: &mut serde_json::Value) {
+    match value {
+        serde_json::Value::Object(map) => {
+            map.retain(|_, v| !v.is_null());
+            map.values_mut().for_each(strip_nulls);
+        }
+        serde_json::Value::Array(items) => {
+            items.retain(|v| !v.is_null());
+            items.iter_mut().for_each(strip_nulls);
+        }
+        _ => {}
+    }
+}
+
+/// `SOURCE_DATE_EPOCH` when set, so reproducible builds get identical
+/// reports; otherwise the current time.
+fn report_timestamp() -> DateTime<Utc> {
+    timestamp_from_epoch(std::env::var("SOURCE_DATE_EPOCH").ok().as_deref()).unwrap_or_else(Utc::now)
+}
+
+/// The time a `SOURCE_DATE_EPOCH` value names, if it is a valid one.
+fn timestamp_from_epoch(epoch: Option<&str>) -> Option<DateTime<Utc>> {
+    epoch
+        .and_then(|epoch| epoch.trim().parse::<i64>().ok())
+        .and_then(|seconds| DateTime::from_timestamp(seconds, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{DependencyScope, VersionConstraint};
+
+    fn dependency(name: &str) -> Dependency {
+        Dependency {
+            ecosystem: Ecosystem::Composer,
+            scope: DependencyScope::Runtime,
+            name: name.to_string(),
+            version: "1.0.0".to_string(),
+            constraint: VersionConstraint::Exact("1.0.0".to_string()),
+            file: PathBuf::from("composer.json"),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        }
+    }
+
+    fn vulnerability(package: &str, severity: Severity) -> Vulnerability {
+        Vulnerability {
+            id: format!("GHSA-{}", package),
+            summary: String::new(),
+            details: String::new(),
+            severity,
+            affected_versions: Vec::new(),
+            fixed_versions: Vec::new(),
+            references: Vec::new(),
+            package: Some(package.to_string()),
+            related_to: None,
+            remediation: None,
+        }
+    }
+
+    fn sample_report() -> ScanReport {
+        let mut report = ScanReport::new(PathBuf::from("."));
+        report.add_ecosystem_result(Ecosystem::Composer, EcosystemScanResult {
+            ecosystem: Ecosystem::Composer,
+            files_found: vec![PathBuf::from("composer.json")],
+            dependencies: vec![dependency("a/outdated"), dependency("b/vulnerable"), dependency("c/clean")],
+            updates: vec![UpdateInfo {
+                dependency: dependency("a/outdated"),
+                current_version: "1.0.0".to_string(),
+                latest_version: "2.0.0".to_string(),
+                changelog: None,
+                latest_in_constraint: None,
+                versions_behind: None,
+                age_behind_days: None,
+                intermediate_releases: Vec::new(),
+            }],
+            vulnerabilities: vec![vulnerability("b/vulnerable", Severity::High)],
+            errors: Vec::new(),
+            unresolved: Vec::new(),
+            unchecked: Vec::new(),
+        });
+        report
+    }
+
+    #[test]
+    fn test_timestamp_honors_source_date_epoch() {
+        let timestamp = timestamp_from_epoch(Some(" 1700000000\n")).unwrap();
+        assert_eq!(timestamp.to_rfc3339(), "2023-11-14T22:13:20+00:00");
+
+        assert_eq!(timestamp_from_epoch(Some("yesterday")), None);
+        assert_eq!(timestamp_from_epoch(None), None);
+    }
+
+    #[test]
+    fn test_diff_reports_only_new_vulnerability() {
+        let baseline = sample_report();
+        let mut current = sample_report();
+        current.vulnerabilities.push(vulnerability("c/clean", Severity::Critical));
+
+        let diff = current.diff(&baseline);
+
+        assert_eq!(diff.new_vulnerabilities.len(), 1);
+        assert_eq!(diff.new_vulnerabilities[0].package.as_deref(), Some("c/clean"));
+        assert!(diff.resolved_vulnerabilities.is_empty());
+        assert!(diff.new_updates.is_empty());
+    }
+
+    #[test]
+    fn test_diff_resolved_and_new_updates() {
+        let baseline = sample_report();
+        let mut current = sample_report();
+        current.vulnerabilities.clear();
+        current.updates[0].latest_version = "3.0.0".to_string();
+
+        let diff = current.diff(&baseline);
+
+        assert!(diff.new_vulnerabilities.is_empty());
+        assert_eq!(diff.resolved_vulnerabilities.len(), 1);
+        assert_eq!(diff.new_updates.len(), 1);
+        assert_eq!(diff.new_updates[0].latest_version, "3.0.0");
+        assert!(sample_report().diff(&sample_report()).is_empty());
+    }
+
+    #[test]
+    fn test_summary_splits_direct_and_transitive_vulnerabilities() {
+        let transitive = |name: &str| Dependency {
+            direct: false,
+            trace: None,
+            ..dependency(name)
+        };
+        let mut report = ScanReport::new(PathBuf::from("."));
+        report.add_ecosystem_result(Ecosystem::Composer, EcosystemScanResult {
+            ecosystem: Ecosystem::Composer,
+            files_found: vec![PathBuf::from("composer.json")],
+            dependencies: vec![dependency("a/direct"), transitive("b/deep"), transitive("c/deep"), dependency("c/deep")],
+            updates: Vec::new(),
+            vulnerabilities: vec![
+                vulnerability("a/direct", Severity::High),
+                vulnerability("b/deep", Severity::Critical),
+                vulnerability("b/deep", Severity::Low),
+                vulnerability("c/deep", Severity::Medium),
+            ],
+            errors: Vec::new(),
+            unresolved: Vec::new(),
+            unchecked: Vec::new(),
+        });
+
+        // c/deep is also required directly, so its finding is a direct one.
+        assert_eq!(report.summary.direct_vulnerabilities, 2);
+        assert_eq!(report.summary.transitive_vulnerabilities, 2);
+        assert_eq!(report.summary.vulnerabilities_found, 4);
+    }
+
+    #[test]
+    fn test_actionable_drops_clean_dependencies_from_detail() {
+        let report = sample_report().actionable();
+
+        let names: Vec<&str> = report.ecosystem_results[&Ecosystem::Composer]
+            .dependencies
+            .iter()
+            .map(|d| d.name.as_str())
+            .collect();
+        assert_eq!(names, vec!["a/outdated", "b/vulnerable"]);
+    }
+
+    #[test]
+    fn test_actionable_json_omits_clean_dependencies() {
+        let json = serde_json::to_value(sample_report().actionable()).unwrap();
+
+        let dependencies = json["ecosystem_results"]["composer"]["dependencies"].as_array().unwrap();
+        let names: Vec<&str> = dependencies.iter().map(|d| d["name"].as_str().unwrap()).collect();
+        assert_eq!(names, vec!["a/outdated", "b/vulnerable"]);
+        assert_eq!(json["total_dependencies"], 3);
+        assert_eq!(json["summary"]["up_to_date_dependencies"], 2);
+    }
+
+    #[test]
+    fn test_actionable_keeps_full_summary() {
+        let report = sample_report().actionable();
+
+        assert_eq!(report.total_dependencies, 3);
+        assert_eq!(report.summary.up_to_date_dependencies, 2);
+        assert_eq!(report.summary.updates_available, 1);
+        assert_eq!(report.summary.vulnerabilities_found, 1);
+        assert_eq!(report.summary.high_vulnerabilities, 1);
+    }
+
+    #[test]
+    fn test_report_records_rensa_version() {
+        let report = ScanReport::new(PathBuf::from("."));
+        assert_eq!(report.rensa_version, env!("CARGO_PKG_VERSION"));
+
+        let json: serde_json::Value = serde_json::from_str(&report.to_json().unwrap()).unwrap();
+        assert_eq!(json["rensa_version"], env!("CARGO_PKG_VERSION"));
+        assert!(json["scan_config"].is_object());
+    }
+
+    #[test]
+    fn test_merge_combines_reports() {
+        let mut report = sample_report();
+        let mut other = ScanReport::new(PathBuf::from("other"));
+        other.add_ecosystem_result(Ecosystem::Npm, EcosystemScanResult {
+            ecosystem: Ecosystem::Npm,
+            files_found: vec![PathBuf::from("package-lock.json")],
+            dependencies: vec![dependency("lodash")],
+            updates: Vec::new(),
+            vulnerabilities: vec![vulnerability("lodash", Severity::Critical)],
+            errors: Vec::new(),
+            unresolved: Vec::new(),
+            unchecked: Vec::new(),
+        });
+        other.warnings.push("npm warning".to_string());
+
+        report.merge(other);
+
+        assert_eq!(report.scanned_path, PathBuf::from("."));
+        assert_eq!(report.total_dependency_files, 2);
+        assert_eq!(report.total_dependencies, 4);
+        assert_eq!(report.summary.vulnerabilities_found, 2);
+        assert_eq!(report.summary.critical_vulnerabilities, 1);
+        assert_eq!(report.vulnerabilities.len(), 2);
+        assert_eq!(report.ecosystem_results.len(), 2);
+        assert_eq!(report.warnings, vec!["npm warning".to_string()]);
+        assert!(report.partial.is_none());
+    }
+
+    #[test]
+    fn test_yaml_round_trip() {
+        let report = sample_report();
+        let yaml = report.to_yaml().unwrap();
+
+        let parsed: ScanReport = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.total_dependencies, report.total_dependencies);
+        assert_eq!(parsed.summary.high_vulnerabilities, 1);
+        assert_eq!(parsed.updates[0].latest_version, "2.0.0");
+        assert_eq!(
+            parsed.ecosystem_results[&Ecosystem::Composer].dependencies.len(),
+            3
+        );
+    }
+
+    #[test]
+    fn test_toml_output_parses_back() {
+        let report = sample_report();
+        let output = report.to_toml().unwrap();
+
+        let parsed: toml::Value = toml::from_str(&output).unwrap();
+        assert_eq!(parsed["total_dependencies"].as_integer(), Some(3));
+
+        let results = parsed["ecosystem_results"].as_array().unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0]["ecosystem"].as_str(), Some("composer"));
+        assert_eq!(results[0]["dependencies"].as_array().unwrap().len(), 3);
+    }
 }
@@ -0,0 +1,140 @@
+use crate::cache::CacheManager;
+use crate::eol::EndOfLife;
+use crate::report::EcosystemScanResult;
+use crate::types::DependencyFile;
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::time::Duration;
+
+/// Cache subdirectory holding whole-manifest results.
+pub const RESULT_CACHE_DIR: &str = "results";
+
+/// Advisories are published all the time, so results go stale sooner than
+/// registry responses do.
+const RESULT_TTL: Duration = Duration::from_secs(6 * 60 * 60);
+
+/// Bumped whenever what a cached result holds changes shape or meaning.
+const SCHEMA_VERSION: u32 = 4;
+
+/// Whole scan results for manifests with a lockfile. While the manifest and
+/// its lockfile are unchanged the resolved dependency set is too, so the
+/// previous result is reused without parsing or any network lookups.
+#[derive(Debug, Clone)]
+pub struct ResultCache {
+    cache: CacheManager,
+}
+
+/// What a manifest contributed to the report, minus anything derived from
+/// local policy, which is re-applied on every scan.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub(crate) struct CachedResult {
+    schema_version: u32,
+    rensa_version: String,
+    pub result: EcosystemScanResult,
+    pub warnings: Vec<String>,
+    pub end_of_life: Vec<EndOfLife>,
+}
+
+impl CachedResult {
+    pub fn new(result: EcosystemScanResult, warnings: Vec<String>, end_of_life: Vec<EndOfLife>) -> Self {
+        Self {
+            schema_version: SCHEMA_VERSION,
+            rensa_version: env!("CARGO_PKG_VERSION").to_string(),
+            result,
+            warnings,
+            end_of_life,
+        }
+    }
+}
+
+impl ResultCache {
+    pub fn new(cache: CacheManager) -> Self {
+        Self {
+            cache: cache.with_ttl(RESULT_TTL),
+        }
+    }
+
+    /// Hashes the manifest, its lockfile and `settings` (whatever scanner
+    /// options shape the result). `None` when the ecosystem has no lockfile
+    /// or the manifest has none next to it.
+    pub(crate) fn key(file: &DependencyFile, settings: &str) -> Option<String> {
+        let lockfile = file.path.with_file_name(file.ecosystem.lockfile_name()?);
+        let lock = std::fs::read(lockfile).ok()?;
+
+        let mut hasher = DefaultHasher::new();
+        file.ecosystem.hash(&mut hasher);
+        file.path.hash(&mut hasher);
+        file.content.hash(&mut hasher);
+        lock.hash(&mut hasher);
+        settings.hash(&mut hasher);
+        Some(format!("{}-{:016x}", file.ecosystem, hasher.finish()))
+    }
+
+    /// A fresh result written by this version of Rensa, if there is one.
+    pub(crate) fn get(&self, key: &str) -> Option<CachedResult> {
+        let entry = self.cache.get::<CachedResult>(RESULT_CACHE_DIR, key).ok()??;
+        let cached = entry.data;
+        (cached.schema_version == SCHEMA_VERSION && cached.rensa_version == env!("CARGO_PKG_VERSION")).then_some(cached)
+    }
+
+    /// Stores a result. A cache that can't be written only costs the next
+    /// scan its speedup, so failures are ignored.
+    pub(crate) fn set(&self, key: &str, cached: &CachedResult) {
+        let _ = self.cache.set(RESULT_CACHE_DIR, key, cached);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::Ecosystem;
+    use tempfile::TempDir;
+
+    fn manifest(dir: &std::path::Path) -> DependencyFile {
+        DependencyFile {
+            ecosystem: Ecosystem::Composer,
+            path: dir.join("composer.json"),
+            content: r#"{"require": {"monolog/monolog": "^2.0"}}"#.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_key_follows_lockfile_content() {
+        let temp_dir = TempDir::new().unwrap();
+        let file = manifest(temp_dir.path());
+        assert_eq!(ResultCache::key(&file, ""), None);
+
+        std::fs::write(temp_dir.path().join("composer.lock"), r#"{"content-hash": "a"}"#).unwrap();
+        let key = ResultCache::key(&file, "").unwrap();
+        assert_eq!(ResultCache::key(&file, ""), Some(key.clone()));
+        assert_ne!(ResultCache::key(&file, "eol"), Some(key.clone()));
+
+        std::fs::write(temp_dir.path().join("composer.lock"), r#"{"content-hash": "b"}"#).unwrap();
+        assert_ne!(ResultCache::key(&file, ""), Some(key));
+    }
+
+    #[test]
+    fn test_other_schema_versions_are_ignored() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache = ResultCache::new(CacheManager::new(temp_dir.path().to_path_buf()));
+        let result = EcosystemScanResult {
+            ecosystem: Ecosystem::Composer,
+            files_found: Vec::new(),
+            dependencies: Vec::new(),
+            updates: Vec::new(),
+            vulnerabilities: Vec::new(),
+            errors: Vec::new(),
+            unresolved: Vec::new(),
+            unchecked: Vec::new(),
+        };
+
+        let mut cached = CachedResult::new(result, Vec::new(), Vec::new());
+        cache.set("current", &cached);
+        assert!(cache.get("current").is_some());
+
+        cached.schema_version = SCHEMA_VERSION + 1;
+        cache.set("future", &cached);
+        assert!(cache.get("future").is_none());
+    }
+}
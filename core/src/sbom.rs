@@ -0,0 +1,144 @@
+use crate::error::{RensaError, Result};
+use crate::types::{Dependency, DependencyScope, Ecosystem, VersionConstraint};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+struct SyftDocument {
+    #[serde(default)]
+    artifacts: Vec<SyftArtifact>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SyftArtifact {
+    name: String,
+    #[serde(default)]
+    version: String,
+    #[serde(default, rename = "type")]
+    package_type: String,
+}
+
+/// Maps a Syft package `type` onto the ecosystem Rensa scans it as.
+pub fn ecosystem_for_syft_type(package_type: &str) -> Option<Ecosystem> {
+    match package_type {
+        "php-composer" | "php-composer-plugin" => Some(Ecosystem::Composer),
+        "npm" => Some(Ecosystem::Npm),
+        "rust-crate" => Some(Ecosystem::Cargo),
+        "python" => Some(Ecosystem::PyPI),
+        "go-module" => Some(Ecosystem::Go),
+        "java-archive" | "jenkins-plugin" => Some(Ecosystem::Maven),
+        "dotnet" => Some(Ecosystem::NuGet),
+        "gem" => Some(Ecosystem::Gem),
+        "dart-pub" => Some(Ecosystem::Pub),
+        "github-action" | "github-action-workflow" => Some(Ecosystem::GitHubActions),
+        _ => None,
+    }
+}
+
+/// Parses a Syft JSON SBOM into dependencies grouped by ecosystem.
+///
+/// Artifacts with an unmapped type or no version are skipped.
+pub fn parse_syft(content: &str, path: &Path) -> Result<HashMap<Ecosystem, Vec<Dependency>>> {
+    let document: SyftDocument = serde_json::from_str(content).map_err(|e| RensaError::ParseError {
+        file: path.to_path_buf(),
+        source: e,
+    })?;
+
+    let mut grouped: HashMap<Ecosystem, Vec<Dependency>> = HashMap::new();
+
+    for artifact in document.artifacts {
+        let ecosystem = match ecosystem_for_syft_type(&artifact.package_type) {
+            Some(e) => e,
+            None => continue,
+        };
+
+        if artifact.version.is_empty() {
+            continue;
+        }
+
+        grouped.entry(ecosystem).or_default().push(Dependency {
+            ecosystem,
+            scope: DependencyScope::Runtime,
+            name: artifact.name,
+            constraint: VersionConstraint::Exact(artifact.version.clone()),
+            version: artifact.version,
+            file: path.to_path_buf(),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        });
+    }
+
+    Ok(grouped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYFT_FIXTURE: &str = r#"{
+  "artifacts": [
+    {
+      "id": "1a2b",
+      "name": "guzzlehttp/guzzle",
+      "version": "7.4.0",
+      "type": "php-composer",
+      "purl": "pkg:composer/guzzlehttp/guzzle@7.4.0"
+    },
+    {
+      "id": "3c4d",
+      "name": "lodash",
+      "version": "4.17.20",
+      "type": "npm",
+      "purl": "pkg:npm/lodash@4.17.20"
+    },
+    {
+      "id": "5e6f",
+      "name": "musl",
+      "version": "1.2.3-r4",
+      "type": "apk"
+    },
+    {
+      "id": "7a8b",
+      "name": "symfony/console",
+      "version": "",
+      "type": "php-composer"
+    }
+  ],
+  "source": { "type": "image", "target": { "userInput": "app:latest" } }
+}"#;
+
+    #[test]
+    fn test_ecosystem_mapping() {
+        assert_eq!(ecosystem_for_syft_type("php-composer"), Some(Ecosystem::Composer));
+        assert_eq!(ecosystem_for_syft_type("php-composer-plugin"), Some(Ecosystem::Composer));
+        assert_eq!(ecosystem_for_syft_type("rust-crate"), Some(Ecosystem::Cargo));
+        assert_eq!(ecosystem_for_syft_type("apk"), None);
+    }
+
+    #[test]
+    fn test_parse_syft_groups_by_ecosystem() {
+        let path = Path::new("syft.json");
+        let grouped = parse_syft(SYFT_FIXTURE, path).unwrap();
+
+        assert_eq!(grouped.len(), 2);
+
+        let composer = &grouped[&Ecosystem::Composer];
+        assert_eq!(composer.len(), 1);
+        assert_eq!(composer[0].name, "guzzlehttp/guzzle");
+        assert_eq!(composer[0].version, "7.4.0");
+
+        let npm = &grouped[&Ecosystem::Npm];
+        assert_eq!(npm.len(), 1);
+        assert_eq!(npm[0].name, "lodash");
+    }
+
+    #[test]
+    fn test_parse_syft_invalid_json() {
+        let result = parse_syft("not json", Path::new("syft.json"));
+        assert!(matches!(result, Err(RensaError::ParseError { .. })));
+    }
+}
@@ -1,66 +1,1178 @@
-use crate::report::{ScanReport, EcosystemScanResult};
-use crate::Result;
-use std::path::PathBuf;
-use std::time::Instant;
+use crate::eol::{EndOfLife, EolChecker};
+use crate::denylist::{PackageDenylist, DENIED_PACKAGE_ID};
+use crate::filter::DependencyFilter;
+use crate::license::LicensePolicy;
+use crate::progress::ScanProgress;
+use crate::config::UnresolvedPolicy;
+use crate::report::{
+    EcosystemScanResult, PartialScan, ProjectInfo, ScanConfigSnapshot, ScanReport, SourceKind, UnresolvedDependency,
+};
+use crate::result_cache::{CachedResult, ResultCache};
+use crate::checkpoint::{Checkpoint, CheckpointEntry};
+use crate::error::RensaError;
+use crate::{
+    Dependency, DependencyFile, DependencyScope, DependencyTrace, Ecosystem, LookupTrace, PackageInfo, Remediation,
+    Result, SeverityThreshold, UpdateInfo, UpdateType, VersionComparator, Vulnerability,
+};
+use futures::stream::{self, Stream, StreamExt};
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Semaphore, SemaphorePermit};
 
 pub struct Scanner<'a> {
     registry: &'a crate::plugin::PluginRegistry,
+    limit: Option<usize>,
+    fail_fast: Option<SeverityThreshold>,
+    eol: Option<EolChecker>,
+    severity_threshold: SeverityThreshold,
+    overrides: HashMap<String, String>,
+    include_submodules: bool,
+    concurrency: usize,
+    /// Bounds network requests across every phase of the scan.
+    permits: Semaphore,
+    groups: Vec<DependencyScope>,
+    filter: DependencyFilter,
+    licenses: LicensePolicy,
+    denylist: PackageDenylist,
+    /// Advisory ids left out of the report.
+    ignored: Vec<String>,
+    results: Option<ResultCache>,
+    checkpoint: Option<Checkpoint>,
+    unresolved: UnresolvedPolicy,
+    trace: bool,
+    progress: Option<Arc<ScanProgress>>,
+}
+
+/// A parsed manifest, before any registry or vulnerability lookups.
+struct ParsedFile {
+    file: DependencyFile,
+    /// `None` when no parser is registered for the ecosystem.
+    deps: Option<Vec<Dependency>>,
+    /// Dependencies found, before any limit is applied.
+    total: usize,
+    warnings: Vec<String>,
+    end_of_life: Vec<EndOfLife>,
+    parse_time: Duration,
+    eol_time: Duration,
+    project: Option<ProjectInfo>,
+    /// Versions the manifest declares itself incompatible with.
+    conflicts: Vec<Dependency>,
+    /// Where the result is stored once every lookup succeeded.
+    cache_key: Option<String>,
+    /// A previous scan's result, reused in place of `deps`.
+    cached: Option<EcosystemScanResult>,
+}
+
+/// Registry and vulnerability results for one dependency. A lookup that
+/// wasn't made (no plugin, or an overridden version) is `None`.
+#[derive(Default)]
+struct Lookup {
+    update: Option<Result<PackageInfo>>,
+    vulnerabilities: Option<Result<Vec<Vulnerability>>>,
+    registry_time: Duration,
+    vuln_time: Duration,
+    trace: Option<DependencyTrace>,
 }
 
 impl<'a> Scanner<'a> {
     pub fn new(registry: &'a crate::plugin::PluginRegistry) -> Self {
-        Self { registry }
+        Self {
+            registry,
+            limit: None,
+            fail_fast: None,
+            eol: None,
+            severity_threshold: SeverityThreshold::default(),
+            overrides: HashMap::new(),
+            include_submodules: false,
+            concurrency: 1,
+            permits: Semaphore::new(default_max_concurrency()),
+            groups: Vec::new(),
+            filter: DependencyFilter::default(),
+            licenses: LicensePolicy::default(),
+            denylist: PackageDenylist::default(),
+            ignored: Vec::new(),
+            results: None,
+            checkpoint: None,
+            unresolved: UnresolvedPolicy::default(),
+            trace: false,
+            progress: None,
+        }
+    }
+
+    /// Caps how many dependencies are sent to the registry and vulnerability
+    /// phases. Dependencies are taken in name order so the subset is stable.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Stops scanning as soon as a vulnerability at or above `threshold` is
+    /// found. The report then only covers the dependencies checked so far.
+    pub fn with_fail_fast(mut self, threshold: SeverityThreshold) -> Self {
+        self.fail_fast = Some(threshold);
+        self
+    }
+
+    /// Checks runtimes and mapped dependencies against an end-of-life dataset.
+    pub fn with_eol(mut self, checker: EolChecker) -> Self {
+        self.eol = Some(checker);
+        self
+    }
+
+    /// Records the severity threshold the caller applies to the results.
+    pub fn with_severity_threshold(mut self, threshold: SeverityThreshold) -> Self {
+        self.severity_threshold = threshold;
+        self
+    }
+
+    /// Pins the latest version considered for the given packages. Their
+    /// registry is not queried.
+    pub fn with_overrides(mut self, overrides: HashMap<String, String>) -> Self {
+        self.overrides = overrides;
+        self
+    }
+
+    /// Also scans manifests inside git submodules, which are skipped by
+    /// default since they are usually upstream code the user doesn't own.
+    pub fn with_include_submodules(mut self, include: bool) -> Self {
+        self.include_submodules = include;
+        self
+    }
+
+    /// Only scans dependencies in these groups, e.g. to leave peer
+    /// dependencies out. Empty scans every group.
+    pub fn with_groups(mut self, groups: Vec<DependencyScope>) -> Self {
+        self.groups = groups;
+        self
+    }
+
+    /// Only scans dependencies whose names the filter matches.
+    pub fn with_filter(mut self, filter: DependencyFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Warns about dependencies whose registry-reported licenses the
+    /// policy doesn't accept.
+    pub fn with_license_policy(mut self, policy: LicensePolicy) -> Self {
+        self.licenses = policy;
+        self
+    }
+
+    /// Reports a finding for every dependency the denylist matches, on top
+    /// of any known vulnerabilities.
+    pub fn with_denylist(mut self, denylist: PackageDenylist) -> Self {
+        self.denylist = denylist;
+        self
+    }
+
+    /// Leaves advisories with these ids out of the report, e.g. ones that
+    /// were reviewed and accepted.
+    pub fn with_ignored_advisories(mut self, ids: Vec<String>) -> Self {
+        self.ignored = ids;
+        self
+    }
+
+    /// Whether dependencies no registry knows are reported as warnings or
+    /// errors. They are listed in `unresolved_dependencies` either way.
+    pub fn with_unresolved_policy(mut self, policy: UnresolvedPolicy) -> Self {
+        self.unresolved = policy;
+        self
+    }
+
+    /// Records on each dependency which URLs were queried, whether the
+    /// answers were cached and how its update was classified.
+    pub fn with_trace(mut self, trace: bool) -> Self {
+        self.trace = trace;
+        self
+    }
+
+    /// Counts queued and finished lookups in `progress`, which the caller
+    /// can read while the scan runs.
+    pub fn with_progress(mut self, progress: Arc<ScanProgress>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Reuses a manifest's whole result while it and its lockfile are
+    /// unchanged, skipping parsing and every network lookup for it.
+    pub fn with_result_cache(mut self, cache: crate::CacheManager) -> Self {
+        self.results = Some(ResultCache::new(cache));
+        self
+    }
+
+    /// Records finished lookups in `checkpoint` and reuses the ones an
+    /// earlier, interrupted scan recorded there, so resuming it only
+    /// queries what's left.
+    pub fn with_checkpoint(mut self, checkpoint: Checkpoint) -> Self {
+        self.checkpoint = Some(checkpoint);
+        self
+    }
+
+    /// How many dependencies are looked up at once. Detection and parsing
+    /// always overlap with the lookups; this only bounds the requests in
+    /// flight. With more than one, the phase timings in the report add up
+    /// the time spent in each request and can exceed the elapsed time.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = concurrency.max(1);
+        self
+    }
+
+    /// How many dependencies are looked up at once.
+    pub fn concurrency(&self) -> usize {
+        self.concurrency
+    }
+
+    /// Caps the registry, vulnerability and end-of-life requests in flight
+    /// at once, whichever phase issues them. Defaults to twice the number
+    /// of CPUs.
+    pub fn with_max_concurrency(mut self, max: usize) -> Self {
+        self.permits = Semaphore::new(max.max(1));
+        self
+    }
+
+    /// Waits for a slot in the request budget; the request may be sent
+    /// while the permit is held.
+    async fn permit(&self) -> SemaphorePermit<'_> {
+        self.permits.acquire().await.expect("scanner semaphore is never closed")
+    }
+
+    fn config_snapshot(&self, mut ecosystems: Vec<crate::Ecosystem>) -> ScanConfigSnapshot {
+        ecosystems.sort();
+        ecosystems.dedup();
+
+        let dev_group = self.groups.is_empty() || self.groups.contains(&DependencyScope::Development);
+        let dev_parsed = ecosystems.is_empty()
+            || ecosystems.iter().any(|e| self.registry.get_parser(e).is_none_or(|p| p.includes_dev()));
+
+        ScanConfigSnapshot {
+            ecosystems,
+            severity_threshold: self.severity_threshold,
+            include_dev: dev_group && dev_parsed,
+            limit: self.limit,
+        }
     }
 
     pub async fn scan(&self, path: PathBuf) -> Result<ScanReport> {
+        self.start_parsing(1);
+        self.scan_source(path, &mut HashMap::new()).await
+    }
+
+    /// Scans several paths into one report. A manifest reached through more
+    /// than one path (e.g. a directory and a file inside it) is scanned once.
+    pub async fn scan_paths(&self, paths: &[PathBuf]) -> Result<ScanReport> {
+        let mut claims = HashMap::new();
+        let mut merged: Option<ScanReport> = None;
+        self.start_parsing(paths.len());
+
+        for path in paths {
+            let report = self.scan_source(path.clone(), &mut claims).await?;
+
+            let stop = match self.fail_fast {
+                Some(threshold) => !report.vulnerabilities_at_or_above(threshold).is_empty(),
+                None => false,
+            };
+
+            match merged.as_mut() {
+                Some(existing) => existing.merge(report),
+                None => merged = Some(report),
+            }
+
+            if stop {
+                break;
+            }
+        }
+
+        let mut report = merged.unwrap_or_else(|| ScanReport::new(PathBuf::from(".")));
+        if paths.len() > 1 {
+            report.scanned_path = common_root(paths);
+        }
+        Ok(report)
+    }
+
+    /// Scans like [`Scanner::scan_paths`], but one manifest at a time,
+    /// handing each manifest's result to `sink` as soon as it is complete.
+    /// Only counters, warnings and errors are kept, so memory stays bounded
+    /// by the largest manifest rather than the whole repository. The
+    /// returned report has no per-dependency lists, and constraints are
+    /// only intersected within a manifest.
+    pub async fn scan_streaming(
+        &self,
+        paths: &[PathBuf],
+        mut sink: impl FnMut(&EcosystemScanResult) -> Result<()>,
+    ) -> Result<ScanReport> {
         let start = Instant::now();
-        let mut report = ScanReport::new(path.clone());
+        let cancelled = AtomicBool::new(false);
+        let mut claims = HashMap::new();
+        let mut ambiguous = Vec::new();
+        let mut summary: Option<ScanReport> = None;
+        self.start_parsing(paths.len());
 
-        let files = self.registry.detect_all(&path).await?;
+        for path in paths {
+            let detect_start = Instant::now();
+            let submodules = self.submodules(path);
+            let mut files = Vec::new();
+            for detector in self.registry.detectors() {
+                for file in detector.detect(path).await? {
+                    if self.claim(&file, &submodules, &mut claims, &mut ambiguous) {
+                        files.push(file);
+                    }
+                }
+            }
+            files.sort_by(|a, b| a.path.cmp(&b.path));
+            let detect_ms = detect_start.elapsed().as_millis() as u64;
 
-        for file in files {
-            let ecosystem = file.ecosystem;
-            let parser = self.registry.get_parser(&ecosystem);
-            let registry_client = self.registry.get_registry_client(&ecosystem);
-            let vulnerability_scanner = self.registry.get_vulnerability_scanner(&ecosystem);
+            for (n, file) in files.into_iter().enumerate() {
+                let parsed = self.load_file(file).await?;
+                let ecosystem = parsed.file.ecosystem;
+                let queue: Vec<_> = parsed
+                    .deps
+                    .iter()
+                    .flatten()
+                    .enumerate()
+                    .map(|(dep_index, dep)| (0, dep_index, ecosystem, dep.clone()))
+                    .collect();
+                self.add_total(queue.len());
+                let lookups = self.lookup_all(stream::iter(queue), &cancelled).await;
 
-            let deps = match parser {
-                Some(p) => p.parse(&file).await?,
-                None => {
-                    report.warnings.push(format!("No parser for ecosystem: {:?}", ecosystem));
+                let mut report = self.assemble(path.clone(), vec![parsed], lookups);
+                if n == 0 {
+                    report.detect_ms = detect_ms;
+                }
+                for result in report.ecosystem_results.values() {
+                    sink(result)?;
+                }
+                match summary.as_mut() {
+                    Some(existing) => existing.merge_summary(report),
+                    None => {
+                        let mut first = ScanReport::new(path.clone());
+                        first.merge_summary(report);
+                        summary = Some(first);
+                    }
+                }
+            }
+            self.finish_parsing();
+        }
+
+        let mut report = summary.unwrap_or_else(|| ScanReport::new(PathBuf::from(".")));
+        report.warnings.extend(ambiguous);
+        report.scanned_path = match paths {
+            [path] => path.clone(),
+            _ => common_root(paths),
+        };
+        report.elapsed = start.elapsed().as_millis() as u64;
+        Ok(report)
+    }
+
+    fn start_parsing(&self, sources: usize) {
+        if let Some(progress) = &self.progress {
+            progress.start_parsing(sources);
+        }
+    }
+
+    fn finish_parsing(&self) {
+        if let Some(progress) = &self.progress {
+            progress.finish_parsing();
+        }
+    }
+
+    fn add_total(&self, dependencies: usize) {
+        if let Some(progress) = &self.progress {
+            progress.add_total(dependencies);
+        }
+    }
+
+    /// Submodule checkouts under `path` to leave out, unless they are
+    /// included.
+    fn submodules(&self, path: &Path) -> Vec<PathBuf> {
+        if self.include_submodules {
+            Vec::new()
+        } else {
+            crate::submodules::submodule_paths(path)
+        }
+    }
+
+    /// Whether `file` should be scanned: it isn't inside a skipped
+    /// submodule and hasn't been claimed yet. `claims` records which
+    /// ecosystem's detector claimed each manifest first; when another
+    /// ecosystem's detector matches it too, the file is only scanned as the
+    /// first one and a warning is added to `ambiguous`.
+    fn claim(
+        &self,
+        file: &DependencyFile,
+        submodules: &[PathBuf],
+        claims: &mut HashMap<PathBuf, Ecosystem>,
+        ambiguous: &mut Vec<String>,
+    ) -> bool {
+        if !submodules.is_empty() && crate::submodules::is_in_submodule(&file.path, submodules) {
+            return false;
+        }
+
+        let path = file.path.canonicalize().unwrap_or_else(|_| file.path.clone());
+        match claims.get(&path) {
+            None => {
+                claims.insert(path, file.ecosystem);
+                true
+            }
+            Some(&claimed) => {
+                if claimed != file.ecosystem {
+                    ambiguous.push(format!(
+                        "{} matches both the {} and {} detectors; scanning it as {} only",
+                        file.path.display(),
+                        claimed,
+                        file.ecosystem,
+                        claimed
+                    ));
+                }
+                false
+            }
+        }
+    }
+
+    /// Runs detection, parsing and the registry/vulnerability lookups as a
+    /// pipeline, so the first requests go out as soon as the first manifest
+    /// is parsed. Results are collected as they arrive and the report is
+    /// assembled afterwards in manifest path order, which keeps it identical
+    /// to checking one dependency at a time.
+    async fn scan_source(&self, path: PathBuf, claims: &mut HashMap<PathBuf, Ecosystem>) -> Result<ScanReport> {
+        let start = Instant::now();
+        let cancelled = AtomicBool::new(false);
+        let cancelled = &cancelled;
+        let (file_tx, mut file_rx) = mpsc::unbounded_channel::<DependencyFile>();
+        let (dep_tx, dep_rx) = mpsc::unbounded_channel::<(usize, usize, Ecosystem, Dependency)>();
+        let source = path.as_path();
+
+        let detection = async move {
+            let detect_start = Instant::now();
+            let submodules = self.submodules(source);
+            let mut ambiguous = Vec::new();
+
+            for detector in self.registry.detectors() {
+                for file in detector.detect(source).await? {
+                    if !self.claim(&file, &submodules, claims, &mut ambiguous) {
+                        continue;
+                    }
+                    // The receiver is only gone once parsing failed, and
+                    // that error is what the scan reports.
+                    let _ = file_tx.send(file);
+                }
+            }
+            Ok::<_, RensaError>((detect_start.elapsed(), ambiguous))
+        };
+
+        let parsing = async move {
+            let dispatch = |index: usize, file: &ParsedFile| {
+                self.add_total(file.deps.as_ref().map_or(0, Vec::len));
+                for (dep_index, dep) in file.deps.iter().flatten().enumerate() {
+                    let _ = dep_tx.send((index, dep_index, file.file.ecosystem, dep.clone()));
+                }
+            };
+
+            // Which dependencies a limit keeps, and where fail-fast stops,
+            // depend on the order they are checked in, so those need every
+            // manifest before anything is dispatched.
+            let ordered = self.limit.is_some() || self.fail_fast.is_some();
+            let mut parsed = Vec::new();
+            let mut pending = Vec::new();
+
+            while let Some(file) = file_rx.recv().await {
+                if ordered {
+                    pending.push(file);
                     continue;
                 }
+                let file = self.load_file(file).await?;
+                self.prefetch(file.file.ecosystem, file.deps.as_deref().unwrap_or_default()).await;
+                dispatch(parsed.len(), &file);
+                parsed.push(file);
+            }
+
+            pending.sort_by(|a, b| a.path.cmp(&b.path));
+            let mut files = Vec::new();
+            for file in pending {
+                files.push(self.load_file(file).await?);
+            }
+            if let Some(limit) = self.limit {
+                limit_dependencies(files.iter_mut().filter_map(|f| f.deps.as_mut()), limit);
+            }
+            for file in files {
+                self.prefetch(file.file.ecosystem, file.deps.as_deref().unwrap_or_default()).await;
+                dispatch(parsed.len(), &file);
+                parsed.push(file);
+            }
+            self.finish_parsing();
+
+            Ok::<_, RensaError>(parsed)
+        };
+
+        let queue = stream::unfold(dep_rx, |mut rx| async move { rx.recv().await.map(|item| (item, rx)) });
+        let lookups = async move { Ok::<_, RensaError>(self.lookup_all(queue, cancelled).await) };
+
+        let ((detect, ambiguous), parsed, lookups) = tokio::try_join!(detection, parsing, lookups)?;
+
+        let mut report = self.assemble(path, parsed, lookups);
+        report.warnings.extend(ambiguous);
+        report.detect_ms = detect.as_millis() as u64;
+        report.elapsed = start.elapsed().as_millis() as u64;
+        Ok(report)
+    }
+
+    /// Hands the vulnerability scanner a manifest's dependencies before
+    /// their lookups, so it can query them all at once. A failed batch only
+    /// means each dependency is scanned on its own.
+    async fn prefetch(&self, ecosystem: Ecosystem, deps: &[Dependency]) {
+        let Some(scanner) = self.registry.get_vulnerability_scanner(&ecosystem) else {
+            return;
+        };
+        let pending: Vec<Dependency> = deps
+            .iter()
+            .filter(|dep| self.checkpoint.as_ref().is_none_or(|c| c.get(dep).is_none()))
+            .cloned()
+            .collect();
+        if pending.is_empty() {
+            return;
+        }
+        let _permit = self.permit().await;
+        let _ = scanner.prefetch(&pending).await;
+    }
+
+    /// Looks up each queued dependency, at most `concurrency` at a time.
+    /// Results are keyed by (manifest, dependency) index.
+    async fn lookup_all(
+        &self,
+        queue: impl Stream<Item = (usize, usize, Ecosystem, Dependency)>,
+        cancelled: &AtomicBool,
+    ) -> HashMap<(usize, usize), Lookup> {
+        let mut results = std::pin::pin!(queue
+            .map(|(index, dep_index, ecosystem, dep)| async move {
+                let lookup = self.lookup(ecosystem, &dep, cancelled).await;
+                ((index, dep_index), lookup)
+            })
+            .buffer_unordered(self.concurrency));
+
+        let mut lookups = HashMap::new();
+        while let Some((key, lookup)) = results.next().await {
+            if let Some(progress) = &self.progress {
+                progress.complete_one();
+            }
+            if let Some(lookup) = lookup {
+                lookups.insert(key, lookup);
+            }
+        }
+        if let Some(checkpoint) = &self.checkpoint {
+            let _ = checkpoint.save();
+        }
+        lookups
+    }
+
+    /// Builds the report from the parsed manifests and their lookups, in
+    /// manifest path order.
+    fn assemble(
+        &self,
+        path: PathBuf,
+        parsed: Vec<ParsedFile>,
+        mut lookups: HashMap<(usize, usize), Lookup>,
+    ) -> ScanReport {
+        let mut report = ScanReport::new(path);
+        let mut parse_time = Duration::ZERO;
+        let mut registry_time = Duration::ZERO;
+        let mut vuln_time = Duration::ZERO;
+
+        let mut parsed: Vec<_> = parsed.into_iter().enumerate().collect();
+        parsed.sort_by(|(_, a), (_, b)| a.file.path.cmp(&b.file.path));
+        report.scan_config = self.config_snapshot(parsed.iter().map(|(_, f)| f.file.ecosystem).collect());
+        // The shallowest manifest that names a package is the project's own.
+        report.project = parsed
+            .iter()
+            .filter_map(|(_, f)| Some((f.file.path.components().count(), f.project.clone()?)))
+            .min_by_key(|(depth, _)| *depth)
+            .map(|(_, project)| project);
+
+        for (_, file) in &parsed {
+            report.warnings.extend(file.warnings.iter().cloned());
+            report.end_of_life.extend(file.end_of_life.iter().cloned());
+            parse_time += file.parse_time;
+            registry_time += file.eol_time;
+        }
+        for lookup in lookups.values() {
+            registry_time += lookup.registry_time;
+            vuln_time += lookup.vuln_time;
+        }
+
+        let total: usize = parsed.iter().map(|(_, f)| f.total).sum();
+        let mut scanned = 0;
+        let mut cancelled = false;
+
+        for (index, file) in parsed {
+            if cancelled {
+                break;
+            }
+
+            if let Some(result) = file.cached {
+                scanned += result.dependencies.len();
+                self.add_cached_result(&mut report, result);
+                continue;
+            }
+
+            let deps = match file.deps {
+                Some(deps) => deps,
+                None => continue,
             };
+            let ecosystem = file.file.ecosystem;
+            let registry_client = self.registry.get_registry_client(&ecosystem);
 
             let mut ecosystem_result = EcosystemScanResult {
                 ecosystem,
-                files_found: vec![file.path.clone()],
-                dependencies: deps.clone(),
+                files_found: vec![file.file.path.clone()],
+                dependencies: Vec::new(),
                 updates: Vec::new(),
                 vulnerabilities: Vec::new(),
                 errors: Vec::new(),
+                unresolved: Vec::new(),
+                unchecked: Vec::new(),
             };
 
             let mut updates = Vec::new();
             let mut vulnerabilities = Vec::new();
+            let mut complete = true;
+
+            for (dep_index, mut dep) in deps.into_iter().enumerate() {
+                if let Some(client) = registry_client.filter(|c| !c.is_valid_name(&dep.name)) {
+                    report.warnings.push(format!(
+                        "Skipping {} dependency with invalid name '{}' in {}",
+                        client.ecosystem(),
+                        dep.name,
+                        dep.file.display()
+                    ));
+                    ecosystem_result.unchecked.push(dep.name.clone());
+                    ecosystem_result.dependencies.push(dep);
+                    scanned += 1;
+                    continue;
+                }
+
+                let lookup = match lookups.remove(&(index, dep_index)) {
+                    Some(lookup) => lookup,
+                    None => {
+                        cancelled = true;
+                        break;
+                    }
+                };
 
-            for dep in deps {
-                if let Some(client) = registry_client {
-                    if let Some(info) = client.get_update_info(&dep).await? {
-                        updates.push(info);
+                if let Some(latest) = self.overrides.get(&dep.name) {
+                    updates.extend(overridden_update(&dep, latest));
+                }
+                let mut releases = Vec::new();
+                let mut checked = true;
+                dep.trace = lookup.trace;
+                match lookup.update {
+                    Some(Ok(info)) => {
+                        report.record_source(ecosystem, SourceKind::Registry, Ok(()));
+                        if let Some(trace) = dep.trace.as_mut() {
+                            trace.latest_version =
+                                info.update.as_ref().map(|u| u.latest_version.clone()).or_else(|| info.versions.first().cloned());
+                            trace.update_type = trace
+                                .latest_version
+                                .as_ref()
+                                .map(|latest| VersionComparator::classify_update(&dep.version, latest));
+                        }
+                        dep.repository = info.repository;
+                        dep.license = info.license;
+                        dep.provenance = info.provenance;
+                        report.warnings.extend(self.licenses.check(&dep));
+                        if let Some(message) = &info.deprecated {
+                            report.warnings.push(format!("{}@{} is deprecated: {}", dep.name, dep.version, message));
+                        }
+                        let latest = info.update.as_ref().map(|u| &u.latest_version).or(info.versions.first());
+                        let downgrade =
+                            latest.filter(|latest| VersionComparator::is_downgrade(&dep.version, latest)).cloned();
+                        if let Some(latest) = &downgrade {
+                            report.warnings.push(format!(
+                                "{}: current version {} is newer than latest published {} (yanked or private?)",
+                                dep.name, dep.version, latest
+                            ));
+                        }
+                        updates.extend(info.update.filter(|_| downgrade.is_none()).map(|mut update| {
+                            update.dependency.repository = dep.repository.clone();
+                            update.dependency.license = dep.license.clone();
+                            update.dependency.provenance = dep.provenance.clone();
+                            update.set_freshness(&info.versions, &info.released);
+                            update.set_release_notes(&info.versions);
+                            update
+                        }));
+                        releases = info.versions;
+                    }
+                    Some(Err(e)) if e.is_not_found() => {
+                        report.record_source(ecosystem, SourceKind::Registry, Ok(()));
+                        ecosystem_result.unresolved.push(UnresolvedDependency {
+                            name: dep.name.clone(),
+                            ecosystem,
+                            file: dep.file.clone(),
+                            reason: e.to_string(),
+                        });
                     }
+                    Some(Err(e)) => {
+                        complete = false;
+                        checked = false;
+                        report.record_source(ecosystem, SourceKind::Registry, Err(&e));
+                        if e.is_rate_limited() {
+                            // Transient: the dependency is still scanned for vulnerabilities.
+                            report.warnings.push(format!("Skipped update check for {}: {}", dep.name, e));
+                        } else {
+                            ecosystem_result.errors.push(format!("{}: {}", dep.name, e));
+                        }
+                    }
+                    None => {}
                 }
-                if let Some(scanner) = vulnerability_scanner {
-                    if let Ok(vulns) = scanner.scan(&dep).await {
-                        vulnerabilities.extend(vulns);
+                match lookup.vulnerabilities {
+                    Some(Ok(vulns)) => {
+                        report.record_source(ecosystem, SourceKind::VulnerabilityDatabase, Ok(()));
+                        cancelled = self.fails_fast(&vulns);
+                        vulnerabilities.extend(attribute(vulns, &dep).map(|mut vuln| {
+                            vuln.remediation = Remediation::find(&dep.constraint, &vuln.fixed_versions, &releases);
+                            vuln
+                        }));
+                    }
+                    Some(Err(e)) => {
+                        complete = false;
+                        checked = false;
+                        report.record_source(ecosystem, SourceKind::VulnerabilityDatabase, Err(&e));
                     }
+                    None => {}
+                }
+                vulnerabilities.extend(self.denylist.check(&dep));
+                if !checked {
+                    ecosystem_result.unchecked.push(dep.name.clone());
+                }
+
+                ecosystem_result.dependencies.push(dep);
+                scanned += 1;
+
+                if cancelled {
+                    break;
                 }
             }
 
+            let mut warnings = file.warnings;
+            let blocked: Vec<String> = updates.iter().flat_map(|u| conflict_warnings(u, &file.conflicts)).collect();
+            report.warnings.extend(blocked.iter().cloned());
+            warnings.extend(blocked);
+
             ecosystem_result.updates = updates;
             ecosystem_result.vulnerabilities = vulnerabilities;
 
-            report.add_ecosystem_result(ecosystem, ecosystem_result);
+            if let (Some(results), Some(key), true) = (&self.results, &file.cache_key, complete && !cancelled) {
+                let mut result = ecosystem_result.clone();
+                result.vulnerabilities.retain(|v| v.id != DENIED_PACKAGE_ID);
+                results.set(key, &CachedResult::new(result, warnings, file.end_of_life));
+            }
+
+            self.add_result(&mut report, ecosystem_result);
+        }
+
+        if scanned < total {
+            report.partial = Some(PartialScan { scanned, total });
+        }
+
+        report.effective_constraints = report.intersect_constraints();
+        for conflict in report.effective_constraints.iter().filter(|c| c.effective.is_none()) {
+            let declared: Vec<String> = conflict
+                .declared
+                .iter()
+                .map(|d| format!("{} in {}", d.constraint, d.file.display()))
+                .collect();
+            report.warnings.push(format!(
+                "No version of {} package {} satisfies all of its constraints: {}",
+                conflict.ecosystem,
+                conflict.name,
+                declared.join(", ")
+            ));
+        }
+
+        for unresolved in &report.unresolved_dependencies {
+            let message = format!(
+                "{} package {} in {} was not found in its registry: {}",
+                unresolved.ecosystem,
+                unresolved.name,
+                unresolved.file.display(),
+                unresolved.reason
+            );
+            match self.unresolved {
+                UnresolvedPolicy::Warn => report.warnings.push(message),
+                UnresolvedPolicy::Error => report.errors.push(message),
+            }
+        }
+
+        report.parse_ms = parse_time.as_millis() as u64;
+        report.registry_ms = registry_time.as_millis() as u64;
+        report.vuln_ms = vuln_time.as_millis() as u64;
+
+        report
+    }
+
+    /// Adds a manifest's cached result, re-applying the local checks that
+    /// aren't cached since their settings may have changed since.
+    fn add_cached_result(&self, report: &mut ScanReport, mut result: EcosystemScanResult) {
+        let registry_client = self.registry.get_registry_client(&result.ecosystem);
+        for dep in &result.dependencies {
+            match registry_client.filter(|c| !c.is_valid_name(&dep.name)) {
+                Some(client) => report.warnings.push(format!(
+                    "Skipping {} dependency with invalid name '{}' in {}",
+                    client.ecosystem(),
+                    dep.name,
+                    dep.file.display()
+                )),
+                None => report.warnings.extend(self.licenses.check(dep)),
+            }
+            result.vulnerabilities.extend(self.denylist.check(dep));
+        }
+        self.add_result(report, result);
+    }
+
+    /// Whether `vulns` hold a finding that stops a fail-fast scan.
+    fn fails_fast(&self, vulns: &[Vulnerability]) -> bool {
+        self.fail_fast.is_some_and(|threshold| {
+            vulns.iter().any(|v| v.severity.meets_threshold(threshold) && !self.ignored.contains(&v.id))
+        })
+    }
+
+    /// Adds a manifest's result minus any ignored advisories. They are only
+    /// dropped here so that cached results still hold them.
+    fn add_result(&self, report: &mut ScanReport, mut result: EcosystemScanResult) {
+        result.vulnerabilities.retain(|v| !self.ignored.contains(&v.id));
+        report.add_ecosystem_result(result.ecosystem, result);
+    }
+
+    /// Reuses a cached result for the manifest when there is one, and
+    /// parses it otherwise.
+    async fn load_file(&self, file: DependencyFile) -> Result<ParsedFile> {
+        let key = self.result_key(&file);
+        let cached = key.as_deref().zip(self.results.as_ref()).and_then(|(key, results)| results.get(key));
+
+        if let Some(cached) = cached {
+            let project = self.registry.get_parser(&file.ecosystem).and_then(|p| p.project(&file));
+            return Ok(ParsedFile {
+                file,
+                project,
+                deps: None,
+                total: cached.result.dependencies.len(),
+                warnings: cached.warnings,
+                end_of_life: cached.end_of_life,
+                parse_time: Duration::ZERO,
+                eol_time: Duration::ZERO,
+                conflicts: Vec::new(),
+                cache_key: None,
+                cached: Some(cached.result),
+            });
+        }
+
+        let mut parsed = self.parse_file(file).await?;
+        parsed.cache_key = key;
+        Ok(parsed)
+    }
+
+    /// The result cache key for a manifest. Limits and fail-fast only scan
+    /// part of it, and overrides change its updates without touching the
+    /// lockfile, so none of those scans use the cache.
+    fn result_key(&self, file: &DependencyFile) -> Option<String> {
+        if self.results.is_none()
+            || self.limit.is_some()
+            || self.fail_fast.is_some()
+            || !self.overrides.is_empty()
+            || self.trace
+        {
+            return None;
+        }
+        let parser = self.registry.get_parser(&file.ecosystem).map(|p| p.options_fingerprint()).unwrap_or_default();
+        let registry = self
+            .registry
+            .get_registry_client(&file.ecosystem)
+            .map(|c| c.options_fingerprint())
+            .unwrap_or_default();
+        let vulnerabilities = self
+            .registry
+            .get_vulnerability_scanner(&file.ecosystem)
+            .map(|s| s.options_fingerprint())
+            .unwrap_or_default();
+        let settings = format!(
+            "{:?} filter={} eol={} unresolved={:?} {} registry={} vulnerabilities={}",
+            self.groups,
+            self.filter.fingerprint(),
+            self.eol.is_some(),
+            self.unresolved,
+            parser,
+            registry,
+            vulnerabilities
+        );
+        ResultCache::key(file, &settings)
+    }
+
+    /// Parses a manifest and runs its end-of-life checks.
+    async fn parse_file(&self, file: DependencyFile) -> Result<ParsedFile> {
+        let mut parsed = ParsedFile {
+            file,
+            deps: None,
+            total: 0,
+            warnings: Vec::new(),
+            end_of_life: Vec::new(),
+            parse_time: Duration::ZERO,
+            eol_time: Duration::ZERO,
+            project: None,
+            conflicts: Vec::new(),
+            cache_key: None,
+            cached: None,
+        };
+
+        let parser = match self.registry.get_parser(&parsed.file.ecosystem) {
+            Some(p) => p,
+            None => {
+                parsed
+                    .warnings
+                    .push(format!("No parser for ecosystem: {:?}", parsed.file.ecosystem));
+                return Ok(parsed);
+            }
+        };
+        let parse_start = Instant::now();
+        let mut deps = parser.parse(&parsed.file).await?;
+        if !self.groups.is_empty() {
+            deps.retain(|d| self.groups.contains(&d.scope));
+        }
+        if !self.filter.is_empty() {
+            deps.retain(|d| self.filter.matches(&d.name));
+        }
+        parsed.warnings.extend(parser.warnings(&parsed.file));
+        parsed.project = parser.project(&parsed.file);
+        parsed.conflicts = parser.conflicts(&parsed.file);
+        parsed.parse_time = parse_start.elapsed();
+
+        if let Some(checker) = &self.eol {
+            let eol_start = Instant::now();
+            let mut tracked = parser.runtime_requirements(&parsed.file)?;
+            tracked.extend(deps.iter().filter(|d| checker.tracks(&d.name)).cloned());
+            for dep in &tracked {
+                let _permit = self.permit().await;
+                match checker.check(dep).await {
+                    Ok(Some(eol)) => parsed.end_of_life.push(eol),
+                    Ok(None) => {}
+                    Err(e) => parsed
+                        .warnings
+                        .push(format!("End-of-life lookup failed for {}: {}", dep.name, e)),
+                }
+            }
+            parsed.eol_time = eol_start.elapsed();
+        }
+
+        parsed.total = deps.len();
+        parsed.deps = Some(deps);
+        Ok(parsed)
+    }
+
+    /// Queries the registry and vulnerability database for one dependency.
+    /// Returns `None` once fail-fast has cancelled the scan.
+    async fn lookup(&self, ecosystem: Ecosystem, dep: &Dependency, cancelled: &AtomicBool) -> Option<Lookup> {
+        if cancelled.load(Ordering::SeqCst) {
+            return None;
+        }
+
+        let mut lookup = Lookup::default();
+        let registry_client = self.registry.get_registry_client(&ecosystem);
+        if registry_client.is_some_and(|c| !c.is_valid_name(&dep.name)) {
+            return Some(lookup);
+        }
+
+        let mut trace = self.trace.then(|| self.trace_lookups(ecosystem, dep));
+
+        if let Some(entry) = self.checkpoint.as_ref().and_then(|c| c.get(dep)) {
+            if entry.vulnerabilities.as_deref().is_some_and(|vulns| self.fails_fast(vulns)) {
+                cancelled.store(true, Ordering::SeqCst);
+            }
+            lookup.update = entry.update.map(Ok);
+            lookup.vulnerabilities = entry.vulnerabilities.map(Ok);
+            lookup.trace = trace.map(|mut trace| {
+                for step in trace.registry.iter_mut().chain(trace.vulnerabilities.iter_mut()) {
+                    step.cached = Some(true);
+                }
+                trace
+            });
+            return Some(lookup);
+        }
+
+        if let Some(client) = registry_client.filter(|_| !self.overrides.contains_key(&dep.name)) {
+            let _permit = self.permit().await;
+            let registry_start = Instant::now();
+            let result = client.get_package_info(dep).await;
+            lookup.registry_time = registry_start.elapsed();
+            if let (Some(step), Err(e)) = (trace.as_mut().and_then(|t| t.registry.as_mut()), &result) {
+                step.error = Some(e.to_string());
+            }
+            lookup.update = Some(result);
+        }
+        if let Some(scanner) = self.registry.get_vulnerability_scanner(&ecosystem) {
+            let _permit = self.permit().await;
+            let vuln_start = Instant::now();
+            let result = scanner.scan(dep).await;
+            lookup.vuln_time = vuln_start.elapsed();
+            if let (Some(step), Err(e)) = (trace.as_mut().and_then(|t| t.vulnerabilities.as_mut()), &result) {
+                step.error = Some(e.to_string());
+            }
+            if result.as_deref().is_ok_and(|vulns| self.fails_fast(vulns)) {
+                cancelled.store(true, Ordering::SeqCst);
+            }
+            lookup.vulnerabilities = Some(result);
+        }
+
+        // Failed lookups are left out so a resumed scan retries them.
+        if let Some(checkpoint) = &self.checkpoint {
+            let update = lookup.update.as_ref().map(|r| r.as_ref()).transpose();
+            let vulnerabilities = lookup.vulnerabilities.as_ref().map(|r| r.as_ref()).transpose();
+            if let (Ok(update), Ok(vulnerabilities)) = (update, vulnerabilities) {
+                let entry = CheckpointEntry {
+                    update: update.cloned(),
+                    vulnerabilities: vulnerabilities.cloned(),
+                };
+                checkpoint.record(dep, entry);
+            }
+        }
+
+        lookup.trace = trace;
+        Some(lookup)
+    }
+
+    /// The lookups about to be made for `dep`, asked of the plugins before
+    /// they run so the cache status reflects this scan.
+    fn trace_lookups(&self, ecosystem: Ecosystem, dep: &Dependency) -> DependencyTrace {
+        let registry = self
+            .registry
+            .get_registry_client(&ecosystem)
+            .filter(|_| !self.overrides.contains_key(&dep.name))
+            .map(|client| {
+                client.trace(dep).unwrap_or_else(|| LookupTrace {
+                    url: client.endpoint().map(str::to_string),
+                    ..LookupTrace::default()
+                })
+            });
+        let vulnerabilities = self
+            .registry
+            .get_vulnerability_scanner(&ecosystem)
+            .map(|scanner| scanner.trace(dep).unwrap_or_default());
+
+        DependencyTrace {
+            registry,
+            vulnerabilities,
+            ..DependencyTrace::default()
+        }
+    }
+
+    /// Scans dependencies resolved outside of a manifest, such as the
+    /// output of `composer show`. `source` is the file they were read from.
+    pub async fn scan_installed(
+        &self,
+        source: PathBuf,
+        ecosystem: Ecosystem,
+        mut deps: Vec<Dependency>,
+    ) -> Result<ScanReport> {
+        let start = Instant::now();
+        let total = deps.len();
+        if let Some(limit) = self.limit {
+            deps.sort_by(|a, b| a.name.cmp(&b.name));
+            deps.truncate(limit);
+        }
+        self.add_total(deps.len());
+
+        self.prefetch(ecosystem, &deps).await;
+        let cancelled = AtomicBool::new(false);
+        let queue = stream::iter(deps.clone().into_iter().enumerate().map(|(i, dep)| (0, i, ecosystem, dep)));
+        let lookups = self.lookup_all(queue, &cancelled).await;
+
+        let file = ParsedFile {
+            file: DependencyFile {
+                ecosystem,
+                path: source.clone(),
+                content: String::new(),
+            },
+            deps: Some(deps),
+            total,
+            warnings: Vec::new(),
+            end_of_life: Vec::new(),
+            parse_time: Duration::ZERO,
+            eol_time: Duration::ZERO,
+            project: None,
+            conflicts: Vec::new(),
+            cache_key: None,
+            cached: None,
+        };
+        let mut report = self.assemble(source, vec![file], lookups);
+        report.elapsed = start.elapsed().as_millis() as u64;
+        Ok(report)
+    }
+
+    /// Scans a Syft JSON SBOM for vulnerabilities. There is no manifest to
+    /// resolve updates against, so only the vulnerability phase runs.
+    pub async fn scan_sbom(&self, path: PathBuf) -> Result<ScanReport> {
+        let start = Instant::now();
+        let mut report = ScanReport::new(path.clone());
+
+        let content = std::fs::read_to_string(&path)?;
+        let grouped = crate::sbom::parse_syft(&content, &path)?;
+
+        let mut ecosystems: Vec<_> = grouped.into_iter().collect();
+        ecosystems.sort_by_key(|(ecosystem, _)| *ecosystem);
+        self.add_total(
+            ecosystems
+                .iter()
+                .filter(|(ecosystem, _)| self.registry.get_vulnerability_scanner(ecosystem).is_some())
+                .map(|(_, deps)| deps.len())
+                .sum(),
+        );
+        report.scan_config = self.config_snapshot(ecosystems.iter().map(|(e, _)| *e).collect());
+
+        for (ecosystem, deps) in ecosystems {
+            let vulnerability_scanner = match self.registry.get_vulnerability_scanner(&ecosystem) {
+                Some(s) => s,
+                None => {
+                    report.warnings.push(format!(
+                        "No vulnerability scanner for ecosystem: {:?} ({} packages skipped)",
+                        ecosystem,
+                        deps.len()
+                    ));
+                    continue;
+                }
+            };
+
+            let mut vulnerabilities = Vec::new();
+            let mut unchecked = Vec::new();
+            let vuln_start = Instant::now();
+            self.prefetch(ecosystem, &deps).await;
+            for dep in &deps {
+                let _permit = self.permit().await;
+                match vulnerability_scanner.scan(dep).await {
+                    Ok(vulns) => {
+                        report.record_source(ecosystem, SourceKind::VulnerabilityDatabase, Ok(()));
+                        vulnerabilities.extend(attribute(vulns, dep));
+                    }
+                    Err(e) => {
+                        report.record_source(ecosystem, SourceKind::VulnerabilityDatabase, Err(&e));
+                        unchecked.push(dep.name.clone());
+                    }
+                }
+                vulnerabilities.extend(self.denylist.check(dep));
+                if let Some(progress) = &self.progress {
+                    progress.complete_one();
+                }
+            }
+            report.vuln_ms += vuln_start.elapsed().as_millis() as u64;
+
+            self.add_result(&mut report, EcosystemScanResult {
+                ecosystem,
+                files_found: vec![path.clone()],
+                dependencies: deps,
+                updates: Vec::new(),
+                vulnerabilities,
+                errors: Vec::new(),
+                unresolved: Vec::new(),
+                unchecked,
+            });
         }
 
         report.elapsed = start.elapsed().as_millis() as u64;
@@ -69,7 +1181,1679 @@ impl<'a> Scanner<'a> {
     }
 }
 
-pub async fn scan_path(path: PathBuf, registry: &crate::plugin::PluginRegistry) -> Result<ScanReport> {
+/// The number of CPUs, which front ends use as the default concurrency.
+pub fn default_concurrency() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+fn default_max_concurrency() -> usize {
+    default_concurrency() * 2
+}
+
+/// Keeps the first `limit` dependencies by name across every list, so the
+/// subset doesn't depend on how they are split between manifests. Each
+/// list's share is left in name order.
+fn limit_dependencies<'d>(lists: impl Iterator<Item = &'d mut Vec<Dependency>>, limit: usize) {
+    let mut lists: Vec<_> = lists.collect();
+    let mut names: Vec<(String, usize, usize)> = lists
+        .iter()
+        .enumerate()
+        .flat_map(|(i, deps)| deps.iter().enumerate().map(move |(j, d)| (d.name.clone(), i, j)))
+        .collect();
+    names.sort();
+    names.truncate(limit);
+
+    let mut keep = vec![Vec::new(); lists.len()];
+    for (_, i, j) in names {
+        keep[i].push(j);
+    }
+    for (deps, keep) in lists.iter_mut().zip(keep) {
+        let mut all: Vec<_> = std::mem::take(*deps).into_iter().map(Some).collect();
+        **deps = keep.into_iter().filter_map(|j| all[j].take()).collect();
+    }
+}
+
+fn overridden_update(dep: &Dependency, latest: &str) -> Option<UpdateInfo> {
+    match VersionComparator::classify_update(dep.version.trim_start_matches('v'), latest.trim_start_matches('v')) {
+        UpdateType::None | UpdateType::Unknown => None,
+        _ => Some(UpdateInfo {
+            dependency: dep.clone(),
+            current_version: dep.version.clone(),
+            latest_version: latest.to_string(),
+            changelog: None,
+            latest_in_constraint: None,
+            versions_behind: None,
+            age_behind_days: None,
+            intermediate_releases: Vec::new(),
+        }),
+    }
+}
+
+/// Warns when an update's target version is one the manifest declares a
+/// conflict with, since the package manager would refuse to install it.
+fn conflict_warnings(update: &UpdateInfo, conflicts: &[Dependency]) -> Vec<String> {
+    let mut targets = vec![&update.latest_version];
+    targets.extend(update.latest_in_constraint.as_ref().filter(|v| **v != update.latest_version));
+
+    let mut warnings = Vec::new();
+    for target in targets {
+        for conflict in conflicts.iter().filter(|c| c.name == update.dependency.name) {
+            if VersionComparator::satisfies(&conflict.constraint, target.trim_start_matches('v')) {
+                warnings.push(format!(
+                    "Upgrading {} to {} conflicts with \"{}\" (declared in {})",
+                    conflict.name,
+                    target,
+                    conflict.version,
+                    conflict.file.display()
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+fn attribute(vulns: Vec<Vulnerability>, dep: &Dependency) -> impl Iterator<Item = Vulnerability> + '_ {
+    vulns.into_iter().map(move |mut v| {
+        v.package = Some(dep.name.clone());
+        v
+    })
+}
+
+/// Expands glob patterns (`services/*/composer.json`) into paths. Plain
+/// paths are passed through; a pattern matching nothing is an error.
+pub fn expand_paths(patterns: &[String]) -> Result<Vec<PathBuf>> {
+    let mut paths = Vec::new();
+
+    for pattern in patterns {
+        if !pattern.contains(['*', '?', '[']) {
+            paths.push(PathBuf::from(pattern));
+            continue;
+        }
+
+        let invalid = |reason: String| RensaError::InvalidDirectory {
+            path: pattern.clone(),
+            reason,
+        };
+
+        let matches = glob::glob(pattern).map_err(|e| invalid(e.to_string()))?;
+        let before = paths.len();
+        for entry in matches {
+            paths.push(entry.map_err(|e| invalid(e.to_string()))?);
+        }
+        if paths.len() == before {
+            return Err(invalid("pattern matched no files".to_string()));
+        }
+    }
+
+    let mut seen = HashSet::new();
+    paths.retain(|p| seen.insert(p.canonicalize().unwrap_or_else(|_| p.clone())));
+    Ok(paths)
+}
+
+fn common_root(paths: &[PathBuf]) -> PathBuf {
+    let mut root: Option<PathBuf> = None;
+    for path in paths {
+        let dir = if path.is_file() { path.parent().unwrap_or(Path::new("")) } else { path.as_path() };
+        root = Some(match root {
+            None => dir.to_path_buf(),
+            Some(current) => current
+                .components()
+                .zip(dir.components())
+                .take_while(|(a, b)| a == b)
+                .map(|(a, _)| a)
+                .collect(),
+        });
+    }
+
+    match root {
+        Some(r) if !r.as_os_str().is_empty() => r,
+        _ => PathBuf::from("."),
+    }
+}
+
+pub async fn scan_path(path: PathBuf, registry: &crate::plugin::PluginRegistry) -> Result<ScanReport> {
     let scanner = Scanner::new(registry);
     scanner.scan(path).await
 }
+
+pub async fn scan_sbom(path: PathBuf, registry: &crate::plugin::PluginRegistry) -> Result<ScanReport> {
+    let scanner = Scanner::new(registry);
+    scanner.scan_sbom(path).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::plugin::{Detector, Parser, PluginRegistry, RegistryClient, VulnerabilityScanner};
+    use crate::report::Reachability;
+    use crate::types::{DependencyFile, DependencyScope, Ecosystem, Severity, VersionConstraint};
+    use async_trait::async_trait;
+    use std::path::Path;
+    use std::sync::{Arc, Mutex};
+
+    struct FixedDetector;
+
+    #[async_trait]
+    impl Detector for FixedDetector {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn detect(&self, path: &Path) -> Result<Vec<DependencyFile>> {
+            Ok(vec![DependencyFile {
+                ecosystem: Ecosystem::Composer,
+                path: path.join("composer.json"),
+                content: String::new(),
+            }])
+        }
+    }
+
+    struct ManifestDetector;
+
+    #[async_trait]
+    impl Detector for ManifestDetector {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn detect(&self, path: &Path) -> Result<Vec<DependencyFile>> {
+            Ok(walkdir::WalkDir::new(path)
+                .into_iter()
+                .filter_map(|e| e.ok())
+                .filter(|e| e.file_name() == std::ffi::OsStr::new("composer.json"))
+                .map(|e| DependencyFile {
+                    ecosystem: Ecosystem::Composer,
+                    path: e.path().to_path_buf(),
+                    content: String::new(),
+                })
+                .collect())
+        }
+    }
+
+    struct FixedParser {
+        names: Vec<&'static str>,
+    }
+
+    #[async_trait]
+    impl Parser for FixedParser {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn parse(&self, file: &DependencyFile) -> Result<Vec<Dependency>> {
+            Ok(self
+                .names
+                .iter()
+                .map(|name| Dependency {
+                    ecosystem: Ecosystem::Composer,
+                    scope: DependencyScope::Runtime,
+                    name: name.to_string(),
+                    version: "1.0.0".to_string(),
+                    constraint: VersionConstraint::Exact("1.0.0".to_string()),
+                    file: file.path.clone(),
+                    repository: None,
+                    license: None,
+                    provenance: None,
+                    direct: true,
+                    trace: None,
+                })
+                .collect())
+        }
+    }
+
+    /// Requires `vendor/pkg-0` up to `vendor/pkg-{count - 1}` at 1.0.0.
+    struct SyntheticParser {
+        count: usize,
+    }
+
+    #[async_trait]
+    impl Parser for SyntheticParser {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn parse(&self, file: &DependencyFile) -> Result<Vec<Dependency>> {
+            Ok((0..self.count)
+                .map(|i| Dependency {
+                    ecosystem: Ecosystem::Composer,
+                    scope: DependencyScope::Runtime,
+                    name: format!("vendor/pkg-{}", i),
+                    version: "1.0.0".to_string(),
+                    constraint: VersionConstraint::Caret("1.0".to_string()),
+                    file: file.path.clone(),
+                    repository: None,
+                    license: None,
+                    provenance: None,
+                    direct: true,
+                    trace: None,
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_streaming_scan_matches_in_memory_summary() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for n in 0..25 {
+            let dir = temp_dir.path().join(format!("service-{}", n));
+            std::fs::create_dir(&dir).unwrap();
+            std::fs::write(dir.join("composer.json"), "").unwrap();
+        }
+
+        let mut releases = crate::testing::MockRegistryClient::new(Ecosystem::Composer);
+        let mut advisories = crate::testing::MockVulnerabilityScanner::new(Ecosystem::Composer);
+        for i in (0..400).step_by(3) {
+            releases = releases.with_package(&format!("vendor/pkg-{}", i), &["1.0.0", "1.1.0"]);
+        }
+        for i in (0..400).step_by(7) {
+            advisories = advisories.with_vulnerability(
+                &format!("vendor/pkg-{}", i),
+                Vulnerability {
+                    id: format!("GHSA-{}", i),
+                    summary: String::new(),
+                    details: String::new(),
+                    severity: if i % 2 == 0 { Severity::Critical } else { Severity::Low },
+                    affected_versions: Vec::new(),
+                    fixed_versions: Vec::new(),
+                    references: Vec::new(),
+                    package: None,
+                    related_to: None,
+                    remediation: None,
+                },
+            );
+        }
+
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(ManifestDetector));
+        registry.register_parser(Box::new(SyntheticParser { count: 400 }));
+        registry.register_registry_client(Box::new(releases));
+        registry.register_vulnerability_scanner(Box::new(advisories));
+        let scanner = Scanner::new(&registry).with_concurrency(16);
+        let paths = vec![temp_dir.path().to_path_buf()];
+
+        let full = scanner.scan_paths(&paths).await.unwrap();
+        let mut streamed = 0;
+        let summary = scanner
+            .scan_streaming(&paths, |result| {
+                streamed += result.dependencies.len();
+                Ok(())
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(full.total_dependencies, 10_000);
+        assert_eq!(streamed, 10_000);
+        assert_eq!(summary.total_dependencies, full.total_dependencies);
+        assert_eq!(summary.total_dependency_files, full.total_dependency_files);
+        assert_eq!(
+            serde_json::to_value(&summary.summary).unwrap(),
+            serde_json::to_value(&full.summary).unwrap()
+        );
+        assert_eq!(
+            summary.summary.vulnerabilities_at_or_above(SeverityThreshold::Critical),
+            full.vulnerabilities_at_or_above(SeverityThreshold::Critical).len()
+        );
+        assert!(summary.ecosystem_results.is_empty() && summary.vulnerabilities.is_empty());
+    }
+
+    struct CountingRegistry {
+        queried: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl RegistryClient for CountingRegistry {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        fn trace(&self, dependency: &Dependency) -> Option<LookupTrace> {
+            Some(LookupTrace::new(format!("https://registry.test/{}", dependency.name)).with_cached(false))
+        }
+
+        async fn get_latest_version(&self, dependency: &Dependency) -> Result<Option<String>> {
+            self.queried.lock().unwrap().push(dependency.name.clone());
+            Ok(None)
+        }
+    }
+
+    struct CriticalForScanner {
+        vulnerable: &'static str,
+        scanned: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl VulnerabilityScanner for CriticalForScanner {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn scan(&self, dependency: &Dependency) -> Result<Vec<Vulnerability>> {
+            self.scanned.lock().unwrap().push(dependency.name.clone());
+            if dependency.name != self.vulnerable {
+                return Ok(Vec::new());
+            }
+            Ok(vec![Vulnerability {
+                id: "GHSA-test".to_string(),
+                summary: "Remote code execution".to_string(),
+                details: String::new(),
+                severity: Severity::Critical,
+                affected_versions: Vec::new(),
+                fixed_versions: Vec::new(),
+                references: Vec::new(),
+                package: None,
+                related_to: None,
+                remediation: None,
+            }])
+        }
+    }
+
+    fn registry_with(names: Vec<&'static str>, queried: Arc<Mutex<Vec<String>>>) -> PluginRegistry {
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(FixedParser { names }));
+        registry.register_registry_client(Box::new(CountingRegistry { queried }));
+        registry
+    }
+
+    #[tokio::test]
+    async fn test_limit_caps_queried_dependencies() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry_with(vec!["d/d", "a/a", "c/c", "b/b", "e/e"], queried.clone());
+
+        let report = Scanner::new(&registry)
+            .with_limit(2)
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        assert_eq!(*queried.lock().unwrap(), vec!["a/a".to_string(), "b/b".to_string()]);
+        assert_eq!(report.total_dependencies, 2);
+
+        let partial = report.partial.unwrap();
+        assert_eq!(partial.scanned, 2);
+        assert_eq!(partial.total, 5);
+    }
+
+    #[tokio::test]
+    async fn test_no_limit_scans_everything() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry_with(vec!["b/b", "a/a"], queried.clone());
+
+        let report = Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        assert_eq!(queried.lock().unwrap().len(), 2);
+        assert!(report.partial.is_none());
+    }
+
+    /// Finds `first/composer.json` and `second/composer.json`.
+    struct TwoManifestDetector;
+
+    #[async_trait]
+    impl Detector for TwoManifestDetector {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn detect(&self, path: &Path) -> Result<Vec<DependencyFile>> {
+            Ok(["first", "second"]
+                .iter()
+                .map(|dir| DependencyFile {
+                    ecosystem: Ecosystem::Composer,
+                    path: path.join(dir).join("composer.json"),
+                    content: String::new(),
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_limit_takes_first_dependencies_across_manifests() {
+        struct InterleavedParser;
+
+        #[async_trait]
+        impl Parser for InterleavedParser {
+            fn ecosystem(&self) -> Ecosystem {
+                Ecosystem::Composer
+            }
+
+            async fn parse(&self, file: &DependencyFile) -> Result<Vec<Dependency>> {
+                let first = file.path.components().any(|c| c.as_os_str() == "first");
+                let names = if first { ["c/c", "a/a"] } else { ["d/d", "b/b"] };
+                FixedParser { names: names.to_vec() }.parse(file).await
+            }
+        }
+
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(TwoManifestDetector));
+        registry.register_parser(Box::new(InterleavedParser));
+        registry.register_registry_client(Box::new(CountingRegistry { queried: queried.clone() }));
+
+        let report = Scanner::new(&registry)
+            .with_limit(2)
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        let mut queried = queried.lock().unwrap().clone();
+        queried.sort();
+        assert_eq!(queried, vec!["a/a".to_string(), "b/b".to_string()]);
+
+        let partial = report.partial.unwrap();
+        assert_eq!(partial.scanned, 2);
+        assert_eq!(partial.total, 4);
+    }
+
+    #[tokio::test]
+    async fn test_unchanged_lockfile_reuses_cached_result() {
+        let project = tempfile::TempDir::new().unwrap();
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(project.path().join("composer.lock"), r#"{"content-hash": "a"}"#).unwrap();
+
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let scanned = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = registry_with(vec!["a/a", "b/b"], queried.clone());
+        registry.register_vulnerability_scanner(Box::new(CriticalForScanner {
+            vulnerable: "b/b",
+            scanned: scanned.clone(),
+        }));
+        let scanner = || {
+            Scanner::new(&registry).with_result_cache(crate::CacheManager::new(cache_dir.path().to_path_buf()))
+        };
+
+        let first = scanner().scan(project.path().to_path_buf()).await.unwrap();
+        assert_eq!(queried.lock().unwrap().len(), 2);
+        assert_eq!(scanned.lock().unwrap().len(), 2);
+
+        let second = scanner().scan(project.path().to_path_buf()).await.unwrap();
+        assert_eq!(queried.lock().unwrap().len(), 2);
+        assert_eq!(scanned.lock().unwrap().len(), 2);
+        assert_eq!(second.total_dependencies, first.total_dependencies);
+        assert_eq!(
+            serde_json::to_value(&second.ecosystem_results).unwrap(),
+            serde_json::to_value(&first.ecosystem_results).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(&second.vulnerabilities).unwrap(),
+            serde_json::to_value(&first.vulnerabilities).unwrap()
+        );
+
+        std::fs::write(project.path().join("composer.lock"), r#"{"content-hash": "b"}"#).unwrap();
+        scanner().scan(project.path().to_path_buf()).await.unwrap();
+        assert_eq!(queried.lock().unwrap().len(), 4);
+    }
+
+    #[tokio::test]
+    async fn test_vulnerability_scanner_is_prefetched_each_manifest() {
+        struct BatchRecorder {
+            batches: Arc<Mutex<Vec<Vec<String>>>>,
+        }
+
+        #[async_trait]
+        impl VulnerabilityScanner for BatchRecorder {
+            fn ecosystem(&self) -> Ecosystem {
+                Ecosystem::Composer
+            }
+
+            async fn prefetch(&self, dependencies: &[Dependency]) -> Result<()> {
+                self.batches.lock().unwrap().push(dependencies.iter().map(|d| d.name.clone()).collect());
+                Ok(())
+            }
+
+            async fn scan(&self, _dependency: &Dependency) -> Result<Vec<Vulnerability>> {
+                Ok(Vec::new())
+            }
+        }
+
+        let batches = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = registry_with(vec!["b/b", "a/a"], Arc::new(Mutex::new(Vec::new())));
+        registry.register_vulnerability_scanner(Box::new(BatchRecorder { batches: batches.clone() }));
+
+        Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        assert_eq!(*batches.lock().unwrap(), vec![vec!["b/b".to_string(), "a/a".to_string()]]);
+    }
+
+    #[tokio::test]
+    async fn test_changed_lookup_options_miss_the_result_cache() {
+        struct FloorScanner {
+            floor: &'static str,
+        }
+
+        #[async_trait]
+        impl VulnerabilityScanner for FloorScanner {
+            fn ecosystem(&self) -> Ecosystem {
+                Ecosystem::Composer
+            }
+
+            fn options_fingerprint(&self) -> String {
+                self.floor.to_string()
+            }
+
+            async fn scan(&self, _dependency: &Dependency) -> Result<Vec<Vulnerability>> {
+                Ok(Vec::new())
+            }
+        }
+
+        let project = tempfile::TempDir::new().unwrap();
+        let cache_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(project.path().join("composer.lock"), r#"{"content-hash": "a"}"#).unwrap();
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let scan = |floor, unresolved| {
+            let queried = queried.clone();
+            let project = project.path().to_path_buf();
+            let cache = crate::CacheManager::new(cache_dir.path().to_path_buf());
+            async move {
+                let mut registry = registry_with(vec!["a/a"], queried);
+                registry.register_vulnerability_scanner(Box::new(FloorScanner { floor }));
+                Scanner::new(&registry)
+                    .with_result_cache(cache)
+                    .with_unresolved_policy(unresolved)
+                    .scan(project)
+                    .await
+                    .unwrap();
+            }
+        };
+
+        scan("low", UnresolvedPolicy::Warn).await;
+        scan("low", UnresolvedPolicy::Warn).await;
+        assert_eq!(queried.lock().unwrap().len(), 1);
+
+        scan("high", UnresolvedPolicy::Warn).await;
+        assert_eq!(queried.lock().unwrap().len(), 2);
+
+        scan("high", UnresolvedPolicy::Error).await;
+        assert_eq!(queried.lock().unwrap().len(), 3);
+    }
+
+    #[tokio::test]
+    async fn test_resumed_scan_skips_checkpointed_lookups() {
+        let project = tempfile::TempDir::new().unwrap();
+        let manifest = project.path().join("composer.json");
+        let path = project.path().join("checkpoint.json");
+        std::fs::write(&manifest, r#"{"require": {}}"#).unwrap();
+
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry_with(vec!["d/d", "a/a", "c/c", "b/b", "e/e"], queried.clone());
+        let checkpoint = || Checkpoint::open(path.clone()).unwrap();
+
+        // Stands in for a scan interrupted after two lookups.
+        Scanner::new(&registry)
+            .with_limit(2)
+            .with_checkpoint(checkpoint())
+            .scan(project.path().to_path_buf())
+            .await
+            .unwrap();
+        assert_eq!(queried.lock().unwrap().len(), 2);
+
+        let report = Scanner::new(&registry)
+            .with_checkpoint(checkpoint())
+            .scan(project.path().to_path_buf())
+            .await
+            .unwrap();
+        let mut resumed = queried.lock().unwrap().split_off(2);
+        resumed.sort();
+        assert_eq!(resumed, vec!["c/c", "d/d", "e/e"]);
+        assert_eq!(report.total_dependencies, 5);
+
+        std::fs::write(&manifest, r#"{"require": {"a/a": "^1.0"}}"#).unwrap();
+        queried.lock().unwrap().clear();
+        Scanner::new(&registry)
+            .with_checkpoint(checkpoint())
+            .scan(project.path().to_path_buf())
+            .await
+            .unwrap();
+        assert_eq!(queried.lock().unwrap().len(), 5);
+    }
+
+    #[tokio::test]
+    async fn test_trace_records_registry_url_and_cache_status() {
+        let project = tempfile::TempDir::new().unwrap();
+        std::fs::write(project.path().join("composer.json"), r#"{"require": {}}"#).unwrap();
+        let path = project.path().join("checkpoint.json");
+
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry_with(vec!["a/a"], queried.clone());
+        let scan = |trace: bool| {
+            let scanner = Scanner::new(&registry)
+                .with_trace(trace)
+                .with_checkpoint(Checkpoint::open(path.clone()).unwrap());
+            let root = project.path().to_path_buf();
+            async move { scanner.scan(root).await }
+        };
+        let traced = |report: &ScanReport| report.ecosystem_results[&Ecosystem::Composer].dependencies[0].trace.clone();
+
+        let trace = traced(&scan(true).await.unwrap()).unwrap();
+        let registry_step = trace.registry.unwrap();
+        assert_eq!(registry_step.url.as_deref(), Some("https://registry.test/a/a"));
+        assert_eq!(registry_step.cached, Some(false));
+        assert_eq!(registry_step.error, None);
+        assert_eq!(trace.vulnerabilities, None);
+
+        // The second scan is answered from the checkpoint.
+        let trace = traced(&scan(true).await.unwrap()).unwrap();
+        assert_eq!(trace.registry.unwrap().cached, Some(true));
+        assert_eq!(queried.lock().unwrap().len(), 1);
+
+        assert_eq!(traced(&scan(false).await.unwrap()), None);
+    }
+
+    #[tokio::test]
+    async fn test_progress_completes_every_queued_lookup() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        for dir in ["api", "web", "worker"] {
+            std::fs::create_dir(temp_dir.path().join(dir)).unwrap();
+            std::fs::write(temp_dir.path().join(dir).join("composer.json"), "").unwrap();
+        }
+
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(ManifestDetector));
+        registry.register_parser(Box::new(SyntheticParser { count: 20 }));
+        registry.register_registry_client(Box::new(
+            crate::testing::MockRegistryClient::new(Ecosystem::Composer).with_package("vendor/pkg-0", &["1.0.0"]),
+        ));
+        let progress = Arc::new(ScanProgress::new());
+        let paths = vec![temp_dir.path().join("api"), temp_dir.path().join("web"), temp_dir.path().join("worker")];
+
+        Scanner::new(&registry)
+            .with_concurrency(8)
+            .with_progress(progress.clone())
+            .scan_paths(&paths)
+            .await
+            .unwrap();
+
+        let snapshot = progress.snapshot();
+        assert!(snapshot.total_known);
+        assert_eq!(snapshot.total, 60);
+        assert_eq!(snapshot.completed, snapshot.total);
+    }
+
+    /// Names each manifest's project after the directory it is in.
+    struct ProjectParser;
+
+    #[async_trait]
+    impl Parser for ProjectParser {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn parse(&self, _file: &DependencyFile) -> Result<Vec<Dependency>> {
+            Ok(Vec::new())
+        }
+
+        fn project(&self, file: &DependencyFile) -> Option<ProjectInfo> {
+            let dir = file.path.parent()?.file_name()?.to_string_lossy();
+            Some(ProjectInfo {
+                name: format!("acme/{}", dir),
+                version: None,
+                description: None,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_project_comes_from_root_manifest() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().join("app");
+        std::fs::create_dir_all(root.join("packages").join("billing")).unwrap();
+        std::fs::write(root.join("composer.json"), "").unwrap();
+        std::fs::write(root.join("packages").join("billing").join("composer.json"), "").unwrap();
+
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(ManifestDetector));
+        registry.register_parser(Box::new(ProjectParser));
+
+        let report = Scanner::new(&registry).scan(root).await.unwrap();
+        assert_eq!(report.project.unwrap().name, "acme/app");
+    }
+
+    /// Requires symfony/console ^5.4 while declaring a conflict with 6.x.
+    struct ConflictParser;
+
+    #[async_trait]
+    impl Parser for ConflictParser {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn parse(&self, file: &DependencyFile) -> Result<Vec<Dependency>> {
+            Ok(vec![Dependency {
+                ecosystem: Ecosystem::Composer,
+                scope: DependencyScope::Runtime,
+                name: "symfony/console".to_string(),
+                version: "5.4.0".to_string(),
+                constraint: VersionConstraint::Caret("5.4".to_string()),
+                file: file.path.clone(),
+                repository: None,
+                license: None,
+                provenance: None,
+                direct: true,
+                trace: None,
+            }])
+        }
+
+        fn conflicts(&self, file: &DependencyFile) -> Vec<Dependency> {
+            vec![Dependency {
+                ecosystem: Ecosystem::Composer,
+                scope: DependencyScope::Runtime,
+                name: "symfony/console".to_string(),
+                version: ">=6.0".to_string(),
+                constraint: VersionConstraint::GreaterThanEqual("6.0".to_string()),
+                file: file.path.clone(),
+                repository: None,
+                license: None,
+                provenance: None,
+                direct: true,
+                trace: None,
+            }]
+        }
+    }
+
+    /// Requires a/shared and b/split with constraints that depend on the
+    /// manifest's directory.
+    struct MonorepoParser;
+
+    #[async_trait]
+    impl Parser for MonorepoParser {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn parse(&self, file: &DependencyFile) -> Result<Vec<Dependency>> {
+            let nested = file.path.components().any(|c| c.as_os_str() == "billing");
+            let requirement = |name: &str, constraint: VersionConstraint| Dependency {
+                ecosystem: Ecosystem::Composer,
+                scope: DependencyScope::Runtime,
+                name: name.to_string(),
+                version: String::new(),
+                constraint,
+                file: file.path.clone(),
+                repository: None,
+                license: None,
+                provenance: None,
+                direct: true,
+                trace: None,
+            };
+
+            Ok(if nested {
+                vec![
+                    requirement("a/shared", VersionConstraint::Tilde("1.4".to_string())),
+                    requirement("b/split", VersionConstraint::Caret("2.0".to_string())),
+                ]
+            } else {
+                vec![
+                    requirement("a/shared", VersionConstraint::Caret("1.0".to_string())),
+                    requirement("b/split", VersionConstraint::Caret("1.0".to_string())),
+                ]
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_constraints_across_manifests_are_intersected() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let billing = temp_dir.path().join("packages").join("billing");
+        std::fs::create_dir_all(&billing).unwrap();
+        std::fs::write(temp_dir.path().join("composer.json"), "").unwrap();
+        std::fs::write(billing.join("composer.json"), "").unwrap();
+
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(ManifestDetector));
+        registry.register_parser(Box::new(MonorepoParser));
+
+        let report = Scanner::new(&registry).scan(temp_dir.path().to_path_buf()).await.unwrap();
+
+        let effective: Vec<(&str, Option<&str>)> = report
+            .effective_constraints
+            .iter()
+            .map(|c| (c.name.as_str(), c.effective.as_deref()))
+            .collect();
+        assert_eq!(effective, vec![("a/shared", Some("~1.4")), ("b/split", None)]);
+        assert_eq!(report.effective_constraints[1].declared.len(), 2);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].starts_with("No version of composer package b/split satisfies all of its constraints: ^1.0 in "));
+    }
+
+    /// Publishes nothing newer than 0.9.0, below what the manifests pin.
+    struct StaleRegistry;
+
+    #[async_trait]
+    impl RegistryClient for StaleRegistry {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn get_latest_version(&self, _dependency: &Dependency) -> Result<Option<String>> {
+            Ok(Some("0.9.0".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_pinned_above_latest_is_reported() {
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(FixedParser { names: vec!["a/a"] }));
+        registry.register_registry_client(Box::new(StaleRegistry));
+
+        let report = Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        assert!(report.updates.is_empty());
+        assert_eq!(
+            report.warnings,
+            vec!["a/a: current version 1.0.0 is newer than latest published 0.9.0 (yanked or private?)"]
+        );
+    }
+
+    struct LatestRegistry;
+
+    #[async_trait]
+    impl RegistryClient for LatestRegistry {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn get_latest_version(&self, _dependency: &Dependency) -> Result<Option<String>> {
+            Ok(Some("6.1.0".to_string()))
+        }
+    }
+
+    #[tokio::test]
+    async fn test_update_blocked_by_declared_conflict() {
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(ConflictParser));
+        registry.register_registry_client(Box::new(LatestRegistry));
+
+        let report = Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        assert_eq!(report.updates.len(), 1);
+        assert_eq!(
+            report.warnings,
+            vec!["Upgrading symfony/console to 6.1.0 conflicts with \">=6.0\" (declared in ./composer.json)"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_full_scan_with_mock_plugins() {
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(FixedParser { names: vec!["a/a", "b/b", "c/c"] }));
+        registry.register_registry_client(Box::new(
+            crate::testing::MockRegistryClient::new(Ecosystem::Composer)
+                .with_package("a/a", &["1.0.0", "1.2.0", "2.0.0-beta.1"])
+                .with_package("b/b", &["1.0.0"]),
+        ));
+        registry.register_vulnerability_scanner(Box::new(
+            crate::testing::MockVulnerabilityScanner::new(Ecosystem::Composer).with_vulnerability(
+                "c/c",
+                Vulnerability {
+                    id: "GHSA-mock".to_string(),
+                    summary: "Mock advisory".to_string(),
+                    details: String::new(),
+                    severity: Severity::High,
+                    affected_versions: Vec::new(),
+                    fixed_versions: Vec::new(),
+                    references: Vec::new(),
+                    package: None,
+                    related_to: None,
+                    remediation: None,
+                },
+            ),
+        ));
+
+        let report = Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        assert_eq!(report.total_dependencies, 3);
+        assert_eq!(report.updates.len(), 1);
+        assert_eq!(report.updates[0].dependency.name, "a/a");
+        assert_eq!(report.updates[0].latest_version, "1.2.0");
+        assert_eq!(report.vulnerabilities.len(), 1);
+        assert_eq!(report.vulnerabilities[0].package.as_deref(), Some("c/c"));
+        assert!(report.errors.is_empty());
+    }
+
+    struct FailingRegistry;
+
+    #[async_trait]
+    impl RegistryClient for FailingRegistry {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn get_latest_version(&self, _dependency: &Dependency) -> Result<Option<String>> {
+            Err(RensaError::Plugin {
+                message: "connection refused".to_string(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_source_errors_are_reported_as_unreachable() {
+        let scanned = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(FixedParser { names: vec!["a/a", "b/b"] }));
+        registry.register_registry_client(Box::new(FailingRegistry));
+        registry.register_vulnerability_scanner(Box::new(CriticalForScanner {
+            vulnerable: "none/none",
+            scanned,
+        }));
+
+        let report = Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        let registry_status = report
+            .sources
+            .iter()
+            .find(|s| s.source == SourceKind::Registry)
+            .unwrap();
+        assert_eq!(registry_status.status, Reachability::Unreachable);
+        assert_eq!(registry_status.failures, 2);
+        assert!(registry_status.last_error.as_deref().unwrap().contains("connection refused"));
+
+        let osv_status = report
+            .sources
+            .iter()
+            .find(|s| s.source == SourceKind::VulnerabilityDatabase)
+            .unwrap();
+        assert_eq!(osv_status.status, Reachability::Reachable);
+        assert_eq!(osv_status.successes, 2);
+
+        assert_eq!(report.ecosystem_results[&Ecosystem::Composer].errors.len(), 2);
+    }
+
+    /// Knows every package except `ghost/*`.
+    struct GhostRegistry;
+
+    #[async_trait]
+    impl RegistryClient for GhostRegistry {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn get_latest_version(&self, dependency: &Dependency) -> Result<Option<String>> {
+            if dependency.name.starts_with("ghost/") {
+                return Err(RensaError::DependencyNotFound {
+                    name: dependency.name.clone(),
+                });
+            }
+            Ok(None)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_unknown_package_is_unresolved() {
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(FixedParser { names: vec!["a/a", "ghost/pkg"] }));
+        registry.register_registry_client(Box::new(GhostRegistry));
+
+        let report = Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        assert_eq!(report.unresolved_dependencies.len(), 1);
+        assert_eq!(report.unresolved_dependencies[0].name, "ghost/pkg");
+        assert_eq!(report.ecosystem_results[&Ecosystem::Composer].unresolved.len(), 1);
+        assert!(report.ecosystem_results[&Ecosystem::Composer].errors.is_empty());
+        assert_eq!(report.warnings.iter().filter(|w| w.contains("ghost/pkg")).count(), 1);
+        assert!(report.errors.is_empty());
+
+        let strict = Scanner::new(&registry)
+            .with_unresolved_policy(UnresolvedPolicy::Error)
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+        assert!(!strict.warnings.iter().any(|w| w.contains("ghost/pkg")));
+        assert_eq!(strict.errors.len(), 1);
+        assert!(strict.errors[0].contains("ghost/pkg"));
+    }
+
+    #[tokio::test]
+    async fn test_coverage_counts_unresolved_and_skipped_dependencies() {
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(FixedParser {
+            names: vec!["a/a", "b/b", "c/c", "ghost/pkg", "bad name"],
+        }));
+        registry.register_registry_client(Box::new(GhostRegistry));
+
+        let report = Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        assert_eq!(report.total_dependencies, 5);
+        assert_eq!(report.summary.unchecked_dependencies, 2);
+        assert!((report.summary.coverage - 0.6).abs() < f32::EPSILON);
+        assert_eq!(report.ecosystem_results[&Ecosystem::Composer].unchecked, vec!["bad name"]);
+    }
+
+    /// Claims the same composer.json as [`FixedDetector`], for npm.
+    struct OverlappingDetector;
+
+    #[async_trait]
+    impl Detector for OverlappingDetector {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Npm
+        }
+
+        async fn detect(&self, path: &Path) -> Result<Vec<DependencyFile>> {
+            Ok(vec![DependencyFile {
+                ecosystem: Ecosystem::Npm,
+                path: path.join("composer.json"),
+                content: String::new(),
+            }])
+        }
+    }
+
+    #[tokio::test]
+    async fn test_file_matched_by_two_detectors_is_scanned_once() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = registry_with(vec!["a/a"], queried.clone());
+        registry.register_detector(Box::new(OverlappingDetector));
+
+        let report = Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        assert_eq!(report.total_dependency_files, 1);
+        assert!(report.ecosystem_results.contains_key(&Ecosystem::Composer));
+        assert!(!report.ecosystem_results.contains_key(&Ecosystem::Npm));
+        assert_eq!(*queried.lock().unwrap(), vec!["a/a".to_string()]);
+        assert_eq!(
+            report.warnings,
+            vec!["./composer.json matches both the composer and npm detectors; scanning it as composer only"]
+        );
+    }
+
+    struct RateLimitedRegistry;
+
+    #[async_trait]
+    impl RegistryClient for RateLimitedRegistry {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn get_latest_version(&self, _dependency: &Dependency) -> Result<Option<String>> {
+            Err(RensaError::RateLimited {
+                registry: "repo.packagist.org".to_string(),
+                retry_after: Some(std::time::Duration::from_secs(30)),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_rate_limited_dependencies_are_warnings() {
+        let scanned = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(FixedParser { names: vec!["a/a", "b/b"] }));
+        registry.register_registry_client(Box::new(RateLimitedRegistry));
+        registry.register_vulnerability_scanner(Box::new(CriticalForScanner {
+            vulnerable: "b/b",
+            scanned,
+        }));
+
+        let report = Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        assert!(report.ecosystem_results[&Ecosystem::Composer].errors.is_empty());
+        assert_eq!(report.warnings.len(), 2);
+        assert!(report.warnings[0].contains("rate limited by repo.packagist.org; try again in 30s"));
+        assert_eq!(report.vulnerabilities.len(), 1);
+    }
+
+    struct SlowRegistry;
+
+    #[async_trait]
+    impl RegistryClient for SlowRegistry {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn get_latest_version(&self, _dependency: &Dependency) -> Result<Option<String>> {
+            tokio::time::sleep(Duration::from_millis(20)).await;
+            Ok(Some("2.0.0".to_string()))
+        }
+    }
+
+    struct SlowScanner;
+
+    #[async_trait]
+    impl VulnerabilityScanner for SlowScanner {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn scan(&self, _dependency: &Dependency) -> Result<Vec<Vulnerability>> {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_phase_timings_sum_to_elapsed() {
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(FixedParser { names: vec!["a/a", "b/b", "c/c"] }));
+        registry.register_registry_client(Box::new(SlowRegistry));
+        registry.register_vulnerability_scanner(Box::new(SlowScanner));
+
+        let report = Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        assert!(report.registry_ms >= 60);
+        assert!(report.vuln_ms >= 30);
+
+        let phases = report.detect_ms + report.parse_ms + report.registry_ms + report.vuln_ms;
+        assert!(phases <= report.elapsed);
+        assert!(report.elapsed - phases < 25, "phases {}ms, elapsed {}ms", phases, report.elapsed);
+    }
+
+    /// Answers after a delay that varies per dependency, so concurrent
+    /// lookups finish out of order.
+    struct JitteryRegistry;
+
+    #[async_trait]
+    impl RegistryClient for JitteryRegistry {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn get_latest_version(&self, dependency: &Dependency) -> Result<Option<String>> {
+            let jitter = dependency.file.to_string_lossy().len() + dependency.name.len() * 3;
+            tokio::time::sleep(Duration::from_millis((jitter % 7) as u64)).await;
+            match dependency.name.as_str() {
+                "a/a" => Ok(Some("2.0.0".to_string())),
+                "c/c" => Err(RensaError::Plugin {
+                    message: "connection refused".to_string(),
+                }),
+                _ => Ok(None),
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_scan_matches_sequential() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in ["a", "bb", "ccc", "dddd"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            std::fs::write(root.join(dir).join("composer.json"), "{}").unwrap();
+        }
+
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(ManifestDetector));
+        registry.register_parser(Box::new(FixedParser {
+            names: vec!["d/d", "a/a", "bad name", "c/c", "bb/bb", "b/b"],
+        }));
+        registry.register_registry_client(Box::new(JitteryRegistry));
+        registry.register_vulnerability_scanner(Box::new(CriticalForScanner {
+            vulnerable: "b/b",
+            scanned: Arc::new(Mutex::new(Vec::new())),
+        }));
+
+        let snapshot = |report: ScanReport| {
+            let mut value = serde_json::to_value(report).unwrap();
+            for field in ["timestamp", "elapsed", "detect_ms", "parse_ms", "registry_ms", "vuln_ms"] {
+                value[field] = serde_json::Value::Null;
+            }
+            value
+        };
+
+        let sequential = snapshot(Scanner::new(&registry).scan(root.to_path_buf()).await.unwrap());
+        let concurrent = snapshot(
+            Scanner::new(&registry)
+                .with_concurrency(8)
+                .scan(root.to_path_buf())
+                .await
+                .unwrap(),
+        );
+
+        assert_eq!(sequential["total_dependencies"], 24);
+        assert_eq!(concurrent, sequential);
+    }
+
+    /// Records the most requests it has had in flight at once.
+    struct InFlightRecorder {
+        in_flight: Arc<std::sync::atomic::AtomicUsize>,
+        peak: Arc<std::sync::atomic::AtomicUsize>,
+    }
+
+    impl InFlightRecorder {
+        async fn request(&self) {
+            let now = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+            self.peak.fetch_max(now, Ordering::SeqCst);
+            tokio::time::sleep(Duration::from_millis(5)).await;
+            self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    #[async_trait]
+    impl RegistryClient for InFlightRecorder {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn get_latest_version(&self, _dependency: &Dependency) -> Result<Option<String>> {
+            self.request().await;
+            Ok(None)
+        }
+    }
+
+    #[async_trait]
+    impl VulnerabilityScanner for InFlightRecorder {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn scan(&self, _dependency: &Dependency) -> Result<Vec<Vulnerability>> {
+            self.request().await;
+            Ok(Vec::new())
+        }
+    }
+
+    #[tokio::test]
+    async fn test_max_concurrency_bounds_requests_in_flight() {
+        let in_flight = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let peak = Arc::new(std::sync::atomic::AtomicUsize::new(0));
+        let counting = || InFlightRecorder {
+            in_flight: in_flight.clone(),
+            peak: peak.clone(),
+        };
+
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(FixedParser {
+            names: vec!["a/a", "b/b", "c/c", "d/d", "e/e", "f/f", "g/g", "h/h"],
+        }));
+        registry.register_registry_client(Box::new(counting()));
+        registry.register_vulnerability_scanner(Box::new(counting()));
+
+        let report = Scanner::new(&registry)
+            .with_concurrency(8)
+            .with_max_concurrency(3)
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        assert_eq!(report.total_dependencies, 8);
+        assert!(peak.load(Ordering::SeqCst) <= 3, "peak {}", peak.load(Ordering::SeqCst));
+        assert!(peak.load(Ordering::SeqCst) > 1);
+    }
+
+    /// Reports `b/b` as GPL-licensed and everything else as MIT.
+    struct LicensedRegistry;
+
+    #[async_trait]
+    impl RegistryClient for LicensedRegistry {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn get_latest_version(&self, _dependency: &Dependency) -> Result<Option<String>> {
+            Ok(None)
+        }
+
+        async fn get_package_info(&self, dependency: &Dependency) -> Result<PackageInfo> {
+            let license = if dependency.name == "b/b" { "GPL-3.0-only" } else { "MIT" };
+            Ok(PackageInfo {
+                license: Some(vec![license.to_string()]),
+                provenance: None,
+                ..PackageInfo::default()
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn test_denied_license_produces_warning() {
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(FixedParser {
+            names: vec!["a/a", "b/b"],
+        }));
+        registry.register_registry_client(Box::new(LicensedRegistry));
+
+        let report = Scanner::new(&registry)
+            .with_license_policy(LicensePolicy {
+                allowed: Vec::new(),
+                denied: vec!["GPL".to_string()],
+            })
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            report.warnings,
+            vec!["b/b 1.0.0 is licensed GPL-3.0-only, which is denied by license policy"]
+        );
+        let deps = &report.ecosystem_results[&Ecosystem::Composer].dependencies;
+        assert_eq!(deps[0].license, Some(vec!["MIT".to_string()]));
+    }
+
+    #[tokio::test]
+    async fn test_denied_package_counts_towards_fail_on() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry_with(vec!["a/a", "evil/package"], queried);
+
+        let report = Scanner::new(&registry)
+            .with_denylist(PackageDenylist::new(&["evil/*".to_string()]).unwrap())
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        assert_eq!(report.vulnerabilities.len(), 1);
+        assert_eq!(report.vulnerabilities[0].id, crate::denylist::DENIED_PACKAGE_ID);
+        assert_eq!(report.summary.high_vulnerabilities, 1);
+        assert_eq!(report.vulnerabilities_at_or_above(SeverityThreshold::High).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_overridden_package_skips_registry() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry_with(vec!["a/a", "b/b"], queried.clone());
+
+        let mut overrides = HashMap::new();
+        overrides.insert("b/b".to_string(), "1.5.0".to_string());
+
+        let report = Scanner::new(&registry)
+            .with_overrides(overrides)
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        assert_eq!(*queried.lock().unwrap(), vec!["a/a".to_string()]);
+        assert_eq!(report.updates.len(), 1);
+        assert_eq!(report.updates[0].dependency.name, "b/b");
+        assert_eq!(report.updates[0].latest_version, "1.5.0");
+    }
+
+    /// Reports every dependency as a peer dependency except `a/a`.
+    struct PeerParser;
+
+    #[async_trait]
+    impl Parser for PeerParser {
+        fn ecosystem(&self) -> Ecosystem {
+            Ecosystem::Composer
+        }
+
+        async fn parse(&self, file: &DependencyFile) -> Result<Vec<Dependency>> {
+            let mut deps = FixedParser { names: vec!["a/a", "b/b"] }.parse(file).await?;
+            deps[1].scope = DependencyScope::Peer;
+            Ok(deps)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_groups_limit_scanned_dependencies() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(PeerParser));
+        registry.register_registry_client(Box::new(CountingRegistry { queried: queried.clone() }));
+
+        let report = Scanner::new(&registry)
+            .with_groups(vec![DependencyScope::Runtime, DependencyScope::Development])
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        assert_eq!(*queried.lock().unwrap(), vec!["a/a".to_string()]);
+        assert_eq!(report.total_dependencies, 1);
+    }
+
+    #[tokio::test]
+    async fn test_filter_limits_scanned_dependencies() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry_with(vec!["symfony/console", "monolog/monolog", "symfony/yaml"], queried.clone());
+
+        let report = Scanner::new(&registry)
+            .with_filter(DependencyFilter::new(&["symfony/*".to_string()]).unwrap())
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *queried.lock().unwrap(),
+            vec!["symfony/console".to_string(), "symfony/yaml".to_string()]
+        );
+        assert_eq!(report.total_dependencies, 2);
+    }
+
+    #[tokio::test]
+    async fn test_scan_installed_checks_given_dependencies() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry_with(Vec::new(), queried.clone());
+        let file = DependencyFile {
+            ecosystem: Ecosystem::Composer,
+            path: PathBuf::from("show.json"),
+            content: String::new(),
+        };
+        let deps = FixedParser { names: vec!["b/b", "a/a"] }.parse(&file).await.unwrap();
+
+        let report = Scanner::new(&registry)
+            .scan_installed(PathBuf::from("show.json"), Ecosystem::Composer, deps)
+            .await
+            .unwrap();
+
+        assert_eq!(*queried.lock().unwrap(), vec!["b/b".to_string(), "a/a".to_string()]);
+        assert_eq!(report.total_dependencies, 2);
+        assert_eq!(
+            report.ecosystem_results[&Ecosystem::Composer].files_found,
+            vec![PathBuf::from("show.json")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_names_are_skipped_with_warning() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry_with(vec!["a/a", "bad name"], queried.clone());
+
+        let report = Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        assert_eq!(*queried.lock().unwrap(), vec!["a/a".to_string()]);
+        assert_eq!(report.warnings.len(), 1);
+        assert!(report.warnings[0].contains("bad name"));
+    }
+
+    #[tokio::test]
+    async fn test_report_records_scan_config() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry_with(vec!["a/a"], queried);
+
+        let report = Scanner::new(&registry)
+            .with_limit(10)
+            .with_severity_threshold(SeverityThreshold::High)
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        assert_eq!(report.rensa_version, env!("CARGO_PKG_VERSION"));
+        assert_eq!(report.scan_config.ecosystems, vec![Ecosystem::Composer]);
+        assert_eq!(report.scan_config.severity_threshold, SeverityThreshold::High);
+        assert_eq!(report.scan_config.limit, Some(10));
+        assert!(report.scan_config.include_dev);
+    }
+
+    #[tokio::test]
+    async fn test_report_records_dev_dependencies_left_out() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let registry = registry_with(vec!["a/a"], queried);
+
+        let report = Scanner::new(&registry)
+            .with_groups(vec![DependencyScope::Runtime])
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        assert!(!report.scan_config.include_dev);
+    }
+
+    #[tokio::test]
+    async fn test_report_records_dev_dependencies_the_parser_skips() {
+        struct RuntimeOnlyParser;
+
+        #[async_trait]
+        impl Parser for RuntimeOnlyParser {
+            fn ecosystem(&self) -> Ecosystem {
+                Ecosystem::Composer
+            }
+
+            async fn parse(&self, _file: &DependencyFile) -> Result<Vec<Dependency>> {
+                Ok(Vec::new())
+            }
+
+            fn includes_dev(&self) -> bool {
+                false
+            }
+        }
+
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(FixedDetector));
+        registry.register_parser(Box::new(RuntimeOnlyParser));
+
+        let report = Scanner::new(&registry).scan(PathBuf::from(".")).await.unwrap();
+
+        assert_eq!(report.scan_config.ecosystems, vec![Ecosystem::Composer]);
+        assert!(!report.scan_config.include_dev);
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_stops_after_first_critical() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let scanned = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = registry_with(vec!["a/a", "b/b", "c/c", "d/d"], queried.clone());
+        registry.register_vulnerability_scanner(Box::new(CriticalForScanner {
+            vulnerable: "b/b",
+            scanned: scanned.clone(),
+        }));
+
+        let report = Scanner::new(&registry)
+            .with_fail_fast(SeverityThreshold::Critical)
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        assert_eq!(*queried.lock().unwrap(), vec!["a/a".to_string(), "b/b".to_string()]);
+        assert_eq!(*scanned.lock().unwrap(), vec!["a/a".to_string(), "b/b".to_string()]);
+        assert_eq!(report.summary.critical_vulnerabilities, 1);
+
+        let partial = report.partial.unwrap();
+        assert_eq!(partial.scanned, 2);
+        assert_eq!(partial.total, 4);
+    }
+
+    #[tokio::test]
+    async fn test_fail_fast_ignores_findings_below_threshold() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let scanned = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = registry_with(vec!["a/a", "b/b", "c/c"], queried.clone());
+        registry.register_vulnerability_scanner(Box::new(CriticalForScanner {
+            vulnerable: "none/none",
+            scanned: scanned.clone(),
+        }));
+
+        let report = Scanner::new(&registry)
+            .with_fail_fast(SeverityThreshold::High)
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        assert_eq!(scanned.lock().unwrap().len(), 3);
+        assert!(report.partial.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_ignored_advisories_are_left_out_and_dont_fail_fast() {
+        let queried = Arc::new(Mutex::new(Vec::new()));
+        let scanned = Arc::new(Mutex::new(Vec::new()));
+        let mut registry = registry_with(vec!["a/a", "b/b", "c/c"], queried);
+        registry.register_vulnerability_scanner(Box::new(CriticalForScanner {
+            vulnerable: "b/b",
+            scanned: scanned.clone(),
+        }));
+
+        let report = Scanner::new(&registry)
+            .with_fail_fast(SeverityThreshold::Critical)
+            .with_ignored_advisories(vec!["GHSA-test".to_string()])
+            .scan(PathBuf::from("."))
+            .await
+            .unwrap();
+
+        assert_eq!(scanned.lock().unwrap().len(), 3);
+        assert!(report.vulnerabilities.is_empty());
+        assert_eq!(report.summary.critical_vulnerabilities, 0);
+        assert!(report.partial.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_scan_paths_merges_globs_and_explicit_paths() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        for dir in ["services/a", "services/b", "legacy"] {
+            std::fs::create_dir_all(root.join(dir)).unwrap();
+            std::fs::write(root.join(dir).join("composer.json"), "{}").unwrap();
+        }
+
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(ManifestDetector));
+        registry.register_parser(Box::new(FixedParser { names: vec!["a/a"] }));
+
+        let paths = expand_paths(&[
+            root.join("legacy").display().to_string(),
+            root.join("services/a").display().to_string(),
+            root.join("services/*/composer.json").display().to_string(),
+        ])
+        .unwrap();
+        assert_eq!(paths.len(), 4);
+
+        let report = Scanner::new(&registry).scan_paths(&paths).await.unwrap();
+
+        let mut files = report.ecosystem_results[&Ecosystem::Composer].files_found.clone();
+        files.sort();
+        assert_eq!(
+            files,
+            vec![
+                root.join("legacy/composer.json"),
+                root.join("services/a/composer.json"),
+                root.join("services/b/composer.json"),
+            ]
+        );
+        assert_eq!(report.total_dependency_files, 3);
+        assert_eq!(report.total_dependencies, 3);
+        assert_eq!(report.scanned_path, root.to_path_buf());
+    }
+
+    #[test]
+    fn test_expand_paths_rejects_unmatched_glob() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let pattern = temp_dir.path().join("*/composer.json").display().to_string();
+
+        assert!(matches!(
+            expand_paths(&[pattern]),
+            Err(RensaError::InvalidDirectory { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_submodules_skipped_unless_included() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path();
+        std::fs::write(
+            root.join(".gitmodules"),
+            "[submodule \"upstream\"]\n\tpath = libs/upstream\n\turl = https://example.com/upstream.git\n",
+        )
+        .unwrap();
+        std::fs::create_dir_all(root.join("libs/upstream")).unwrap();
+        std::fs::write(root.join("composer.json"), "{}").unwrap();
+        std::fs::write(root.join("libs/upstream/composer.json"), "{}").unwrap();
+
+        let mut registry = PluginRegistry::new();
+        registry.register_detector(Box::new(ManifestDetector));
+        registry.register_parser(Box::new(FixedParser { names: vec!["a/a"] }));
+
+        let report = Scanner::new(&registry).scan(root.to_path_buf()).await.unwrap();
+        assert_eq!(
+            report.ecosystem_results[&Ecosystem::Composer].files_found,
+            vec![root.join("composer.json")]
+        );
+
+        let report = Scanner::new(&registry)
+            .with_include_submodules(true)
+            .scan(root.to_path_buf())
+            .await
+            .unwrap();
+        assert_eq!(report.total_dependency_files, 2);
+    }
+}
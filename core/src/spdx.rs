@@ -0,0 +1,174 @@
+use crate::report::ScanReport;
+use crate::Dependency;
+use serde_json::{json, Value};
+use std::collections::HashSet;
+
+const NOASSERTION: &str = "NOASSERTION";
+
+impl ScanReport {
+    /// An SPDX 2.3 JSON document listing every scanned dependency once,
+    /// with its package URL, and a `DESCRIBES` relationship from the
+    /// document to each package.
+    pub fn to_spdx(&self) -> Result<String, serde_json::Error> {
+        let mut dependencies: Vec<&Dependency> =
+            self.ecosystem_results.values().flat_map(|r| &r.dependencies).collect();
+        dependencies.sort_by(|a, b| {
+            (a.ecosystem, &a.name, &a.version).cmp(&(b.ecosystem, &b.name, &b.version))
+        });
+        dependencies.dedup_by(|a, b| a.ecosystem == b.ecosystem && a.name == b.name && a.version == b.version);
+
+        let mut ids = HashSet::new();
+        let packages: Vec<(String, &Dependency)> = dependencies
+            .into_iter()
+            .map(|dep| (unique_id(&format!("Package-{}-{}", dep.name, dep.version), &mut ids), dep))
+            .collect();
+
+        let name = match &self.project {
+            Some(project) => project.to_string(),
+            None => self.scanned_path.display().to_string(),
+        };
+        let created = self.timestamp.format("%Y-%m-%dT%H:%M:%SZ").to_string();
+
+        let document = json!({
+            "spdxVersion": "SPDX-2.3",
+            "dataLicense": "CC0-1.0",
+            "SPDXID": "SPDXRef-DOCUMENT",
+            "name": name,
+            "documentNamespace": format!(
+                "https://spdx.org/spdxdocs/rensa-{}-{}",
+                sanitize(&name),
+                self.timestamp.format("%Y%m%dT%H%M%SZ")
+            ),
+            "creationInfo": {
+                "created": created,
+                "creators": [format!("Tool: rensa-{}", self.rensa_version)],
+            },
+            "packages": packages.iter().map(|(id, dep)| package(id, dep)).collect::<Vec<_>>(),
+            "relationships": packages
+                .iter()
+                .map(|(id, _)| json!({
+                    "spdxElementId": "SPDXRef-DOCUMENT",
+                    "relationshipType": "DESCRIBES",
+                    "relatedSpdxElement": id,
+                }))
+                .collect::<Vec<_>>(),
+        });
+
+        serde_json::to_string_pretty(&document)
+    }
+}
+
+fn package(id: &str, dep: &Dependency) -> Value {
+    json!({
+        "SPDXID": id,
+        "name": dep.name,
+        "versionInfo": dep.version,
+        "downloadLocation": dep.repository.as_deref().unwrap_or(NOASSERTION),
+        "filesAnalyzed": false,
+        "licenseConcluded": NOASSERTION,
+        "licenseDeclared": license_expression(dep).unwrap_or_else(|| NOASSERTION.to_string()),
+        "copyrightText": NOASSERTION,
+        "externalRefs": [{
+            "referenceCategory": "PACKAGE-MANAGER",
+            "referenceType": "purl",
+            "referenceLocator": dep.purl(),
+        }],
+    })
+}
+
+/// The registry's licenses as an SPDX expression; several are
+/// alternatives. `None` when any of them isn't a plain identifier.
+fn license_expression(dep: &Dependency) -> Option<String> {
+    let licenses = dep.license.as_ref().filter(|l| !l.is_empty())?;
+    let valid = |l: &String| !l.is_empty() && l.chars().all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '+'));
+    licenses.iter().all(valid).then(|| licenses.join(" OR "))
+}
+
+/// An `SPDXRef-` id for `name` that isn't in `used` yet. SPDX ids may only
+/// hold letters, digits, `.` and `-`, so names that differ only in other
+/// characters get a numeric suffix.
+fn unique_id(name: &str, used: &mut HashSet<String>) -> String {
+    let base = format!("SPDXRef-{}", sanitize(name));
+    let mut id = base.clone();
+    let mut n = 2;
+    while !used.insert(id.clone()) {
+        id = format!("{}-{}", base, n);
+        n += 1;
+    }
+    id
+}
+
+fn sanitize(text: &str) -> String {
+    text.chars()
+        .map(|c| if c.is_ascii_alphanumeric() || c == '.' || c == '-' { c } else { '-' })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::report::EcosystemScanResult;
+    use crate::{DependencyScope, Ecosystem, VersionConstraint};
+    use std::path::PathBuf;
+
+    fn dependency(name: &str, scope: DependencyScope) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            ecosystem: Ecosystem::Composer,
+            scope,
+            version: "1.0.0".to_string(),
+            constraint: VersionConstraint::Exact("1.0.0".to_string()),
+            file: PathBuf::from("composer.json"),
+            repository: None,
+            license: Some(vec!["MIT".to_string()]),
+            provenance: None,
+            direct: true,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn test_document_has_required_fields() {
+        let mut report = ScanReport::new(PathBuf::from("/app"));
+        report.add_ecosystem_result(Ecosystem::Composer, EcosystemScanResult {
+            ecosystem: Ecosystem::Composer,
+            files_found: vec![PathBuf::from("composer.json")],
+            dependencies: vec![
+                dependency("monolog/monolog", DependencyScope::Runtime),
+                dependency("monolog/monolog", DependencyScope::Development),
+                dependency("monolog_monolog", DependencyScope::Runtime),
+            ],
+            updates: Vec::new(),
+            vulnerabilities: Vec::new(),
+            errors: Vec::new(),
+            unresolved: Vec::new(),
+            unchecked: Vec::new(),
+        });
+
+        let document: Value = serde_json::from_str(&report.to_spdx().unwrap()).unwrap();
+        assert_eq!(document["spdxVersion"], "SPDX-2.3");
+        assert_eq!(document["dataLicense"], "CC0-1.0");
+        assert_eq!(document["SPDXID"], "SPDXRef-DOCUMENT");
+        assert!(document["documentNamespace"].as_str().unwrap().starts_with("https://"));
+        assert!(document["creationInfo"]["created"].as_str().unwrap().ends_with('Z'));
+        assert!(document["creationInfo"]["creators"][0].as_str().unwrap().starts_with("Tool: rensa-"));
+
+        let packages = document["packages"].as_array().unwrap();
+        assert_eq!(packages.len(), 2);
+        let ids: Vec<&str> = packages.iter().map(|p| p["SPDXID"].as_str().unwrap()).collect();
+        assert_eq!(ids, vec!["SPDXRef-Package-monolog-monolog-1.0.0", "SPDXRef-Package-monolog-monolog-1.0.0-2"]);
+        for package in packages {
+            for field in ["name", "versionInfo", "downloadLocation", "licenseDeclared", "copyrightText"] {
+                assert!(package[field].is_string(), "{}", field);
+            }
+            assert_eq!(package["licenseDeclared"], "MIT");
+            assert_eq!(package["externalRefs"][0]["referenceType"], "purl");
+        }
+        assert_eq!(packages[0]["externalRefs"][0]["referenceLocator"], "pkg:composer/monolog/monolog@1.0.0");
+
+        let relationships = document["relationships"].as_array().unwrap();
+        assert_eq!(relationships.len(), 2);
+        assert_eq!(relationships[1]["relationshipType"], "DESCRIBES");
+        assert_eq!(relationships[1]["relatedSpdxElement"], ids[1]);
+    }
+}
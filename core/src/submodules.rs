@@ -0,0 +1,73 @@
+use std::path::{Path, PathBuf};
+
+/// Returns the absolute paths of the git submodules declared in the
+/// `.gitmodules` closest to `path` (searching `path` and its ancestors).
+pub fn submodule_paths(path: &Path) -> Vec<PathBuf> {
+    let start = match path.canonicalize() {
+        Ok(p) => p,
+        Err(_) => return Vec::new(),
+    };
+
+    for dir in start.ancestors() {
+        let gitmodules = dir.join(".gitmodules");
+        if let Ok(content) = std::fs::read_to_string(&gitmodules) {
+            return parse_gitmodules(&content)
+                .into_iter()
+                .map(|p| dir.join(p))
+                .collect();
+        }
+    }
+
+    Vec::new()
+}
+
+/// Extracts the `path = ...` entries of a `.gitmodules` file.
+fn parse_gitmodules(content: &str) -> Vec<String> {
+    content
+        .lines()
+        .filter_map(|line| {
+            let (key, value) = line.trim().split_once('=')?;
+            (key.trim() == "path").then(|| value.trim().trim_matches('"').to_string())
+        })
+        .filter(|p| !p.is_empty())
+        .collect()
+}
+
+/// Whether `file` lives inside one of `submodules`.
+pub fn is_in_submodule(file: &Path, submodules: &[PathBuf]) -> bool {
+    let file = file.canonicalize().unwrap_or_else(|_| file.to_path_buf());
+    submodules.iter().any(|s| file.starts_with(s))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GITMODULES: &str = r#"[submodule "upstream"]
+	path = libs/upstream
+	url = https://github.com/example/upstream.git
+[submodule "themes/base"]
+	path = "themes/base"
+	url = git@github.com:example/base.git
+	branch = main
+"#;
+
+    #[test]
+    fn test_parse_gitmodules() {
+        assert_eq!(parse_gitmodules(GITMODULES), vec!["libs/upstream", "themes/base"]);
+    }
+
+    #[test]
+    fn test_submodule_paths_found_from_subdirectory() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let root = temp_dir.path().canonicalize().unwrap();
+        std::fs::write(root.join(".gitmodules"), GITMODULES).unwrap();
+        std::fs::create_dir_all(root.join("libs/upstream")).unwrap();
+
+        let paths = submodule_paths(&root.join("libs"));
+        assert_eq!(paths, vec![root.join("libs/upstream"), root.join("themes/base")]);
+
+        assert!(is_in_submodule(&root.join("libs/upstream"), &paths));
+        assert!(!is_in_submodule(&root.join("libs"), &paths));
+    }
+}
@@ -0,0 +1,112 @@
+//! In-memory plugin components for tests and demos that must not touch the
+//! network. Enabled by the `test-utils` feature.
+
+use crate::error::Result;
+use crate::plugin::{RegistryClient, VulnerabilityScanner};
+use crate::types::{Dependency, Ecosystem, UpdateInfo, Vulnerability};
+use crate::version::{UpdateType, VersionComparator};
+use semver::Version;
+use std::collections::HashMap;
+
+/// A registry that serves published versions from a map of package name
+/// to versions. Unknown packages have no versions.
+#[derive(Debug, Clone)]
+pub struct MockRegistryClient {
+    ecosystem: Ecosystem,
+    packages: HashMap<String, Vec<String>>,
+}
+
+impl MockRegistryClient {
+    pub fn new(ecosystem: Ecosystem) -> Self {
+        Self {
+            ecosystem,
+            packages: HashMap::new(),
+        }
+    }
+
+    pub fn with_package(mut self, name: &str, versions: &[&str]) -> Self {
+        self.packages
+            .insert(name.to_string(), versions.iter().map(|v| v.to_string()).collect());
+        self
+    }
+
+    /// Stable versions of `name`, newest first.
+    fn versions(&self, name: &str) -> Vec<String> {
+        let mut versions: Vec<Version> = self
+            .packages
+            .get(name)
+            .into_iter()
+            .flatten()
+            .filter_map(|v| Version::parse(v.trim_start_matches('v')).ok())
+            .filter(|v| v.pre.is_empty())
+            .collect();
+        versions.sort_by(|a, b| b.cmp(a));
+        versions.iter().map(Version::to_string).collect()
+    }
+}
+
+#[async_trait::async_trait]
+impl RegistryClient for MockRegistryClient {
+    fn ecosystem(&self) -> Ecosystem {
+        self.ecosystem
+    }
+
+    async fn get_latest_version(&self, dependency: &Dependency) -> Result<Option<String>> {
+        Ok(self.versions(&dependency.name).into_iter().next())
+    }
+
+    async fn get_update_info(&self, dependency: &Dependency) -> Result<Option<UpdateInfo>> {
+        let versions = self.versions(&dependency.name);
+        let latest_version = match versions.first() {
+            Some(v) => v.clone(),
+            None => return Ok(None),
+        };
+
+        match VersionComparator::classify_update(&dependency.version, &latest_version) {
+            UpdateType::None | UpdateType::Unknown => Ok(None),
+            _ => Ok(Some(UpdateInfo {
+                dependency: dependency.clone(),
+                current_version: dependency.version.clone(),
+                latest_in_constraint: VersionComparator::highest_satisfying(&dependency.constraint, &versions),
+                versions_behind: None,
+                age_behind_days: None,
+                intermediate_releases: Vec::new(),
+                latest_version,
+                changelog: None,
+            })),
+        }
+    }
+}
+
+/// A vulnerability database that reports the advisories registered for a
+/// package whatever version is installed.
+#[derive(Debug, Clone)]
+pub struct MockVulnerabilityScanner {
+    ecosystem: Ecosystem,
+    advisories: HashMap<String, Vec<Vulnerability>>,
+}
+
+impl MockVulnerabilityScanner {
+    pub fn new(ecosystem: Ecosystem) -> Self {
+        Self {
+            ecosystem,
+            advisories: HashMap::new(),
+        }
+    }
+
+    pub fn with_vulnerability(mut self, package: &str, vulnerability: Vulnerability) -> Self {
+        self.advisories.entry(package.to_string()).or_default().push(vulnerability);
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl VulnerabilityScanner for MockVulnerabilityScanner {
+    fn ecosystem(&self) -> Ecosystem {
+        self.ecosystem
+    }
+
+    async fn scan(&self, dependency: &Dependency) -> Result<Vec<Vulnerability>> {
+        Ok(self.advisories.get(&dependency.name).cloned().unwrap_or_default())
+    }
+}
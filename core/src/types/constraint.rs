@@ -1,4 +1,7 @@
+use crate::version::VersionComparator;
+use semver::{Comparator, Op, Version};
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub enum VersionConstraint {
@@ -7,6 +10,10 @@ pub enum VersionConstraint {
     GreaterThanEqual(String),
     Caret(String),
     Tilde(String),
+    /// Any version the inner constraint rejects, e.g. composer's `!=1.5.0`.
+    Not(Box<VersionConstraint>),
+    /// Versions every constraint accepts, e.g. `>=1.0 <2.0 !=1.5.0`.
+    And(Vec<VersionConstraint>),
 }
 
 impl Default for VersionConstraint {
@@ -18,11 +25,391 @@ impl Default for VersionConstraint {
 impl std::fmt::Display for VersionConstraint {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
+            VersionConstraint::Range(v) if v.starts_with(['<', '>', '=', '*']) => write!(f, "{}", v),
             VersionConstraint::Range(v) => write!(f, "^{}", v),
             VersionConstraint::Exact(v) => write!(f, "{}", v),
             VersionConstraint::GreaterThanEqual(v) => write!(f, ">={}", v),
             VersionConstraint::Caret(v) => write!(f, "^{}", v),
             VersionConstraint::Tilde(v) => write!(f, "~{}", v),
+            VersionConstraint::Not(c) => match c.as_ref() {
+                VersionConstraint::Exact(v) => write!(f, "!={}", v),
+                other => write!(f, "!({})", other),
+            },
+            VersionConstraint::And(cs) => {
+                let parts: Vec<String> = cs.iter().map(|c| c.to_string()).collect();
+                write!(f, "{}", parts.join(" "))
+            }
         }
     }
 }
+
+impl VersionConstraint {
+    /// The lowest version the constraint names, e.g. `4.17.0` for `^4.17.0`.
+    /// `None` when it doesn't name a single lower bound.
+    pub fn lower_bound(&self) -> Option<&str> {
+        match self {
+            VersionConstraint::Exact(v)
+            | VersionConstraint::GreaterThanEqual(v)
+            | VersionConstraint::Caret(v)
+            | VersionConstraint::Tilde(v) => Some(v),
+            _ => None,
+        }
+    }
+
+    /// Parses a hyphen range, `1.0.0 - 2.0.0`, which includes both ends, into
+    /// `>=1.0.0, <=2.0.0`. As in composer and npm, a partial lower bound is
+    /// padded with zeros and a partial upper bound takes in the whole release
+    /// line it names: `1.2 - 2.3` is `>=1.2.0, <2.4.0`.
+    pub fn parse_hyphen_range(raw: &str) -> Option<VersionConstraint> {
+        let (low, high) = raw.trim().split_once(" - ")?;
+        let bound = |v: &str| v.trim().trim_start_matches(['v', 'V']).to_string();
+        let (low, high) = (bound(low), bound(high));
+        let parts = |v: &str| -> Option<Vec<u64>> {
+            let parts: Vec<u64> = v.split('.').map(|p| p.parse().ok()).collect::<Option<_>>()?;
+            (1..=3).contains(&parts.len()).then_some(parts)
+        };
+
+        let lower = match Version::parse(&low) {
+            Ok(version) => version,
+            Err(_) => {
+                let p = parts(&low)?;
+                Version::new(p[0], p.get(1).copied().unwrap_or(0), p.get(2).copied().unwrap_or(0))
+            }
+        };
+        let upper = match Version::parse(&high) {
+            Ok(version) => format!("<={}", version),
+            Err(_) => match parts(&high)?.as_slice() {
+                [major] => format!("<{}.0.0", major + 1),
+                [major, minor] => format!("<{}.{}.0", major, minor + 1),
+                _ => return None,
+            },
+        };
+        Some(VersionConstraint::Range(format!(">={}, {}", lower, upper)))
+    }
+
+    /// The constraint both `self` and `other` allow, e.g. `^2.0` and `~2.5`
+    /// give `~2.5`, and `^2.0` and `>=2.5 <4.0` give `>=2.5.0, <3.0.0`.
+    /// `None` when no version satisfies both.
+    ///
+    /// Constraints whose bounds can't be worked out, such as `||`
+    /// alternatives or branch names, are assumed to overlap and are kept
+    /// side by side.
+    pub fn intersect(&self, other: &VersionConstraint) -> Option<VersionConstraint> {
+        if self == other {
+            return Some(self.clone());
+        }
+        let (Some(mine), Some(theirs)) = (Interval::of(self), Interval::of(other)) else {
+            return Some(VersionConstraint::And(vec![self.clone(), other.clone()]));
+        };
+        let overlap = mine.intersect(&theirs)?;
+
+        let (base, exclusions) = if overlap == mine {
+            (self.clone(), other.exclusions())
+        } else if overlap == theirs {
+            (other.clone(), self.exclusions())
+        } else {
+            let mut exclusions = self.exclusions();
+            exclusions.extend(other.exclusions());
+            (overlap.to_constraint(), exclusions)
+        };
+
+        let mut excluded = base.exclusions();
+        let mut parts = match base {
+            VersionConstraint::And(parts) => parts,
+            base => vec![base],
+        };
+        for version in exclusions {
+            if overlap.contains(&version) && !excluded.contains(&version) {
+                parts.push(VersionConstraint::Not(Box::new(VersionConstraint::Exact(version.to_string()))));
+                excluded.push(version);
+            }
+        }
+
+        if overlap.point().is_some_and(|point| excluded.contains(point)) {
+            return None;
+        }
+        match parts.len() {
+            1 => parts.pop(),
+            _ => Some(VersionConstraint::And(parts)),
+        }
+    }
+
+    /// Versions ruled out with `!=`.
+    fn exclusions(&self) -> Vec<Version> {
+        match self {
+            VersionConstraint::Not(c) => match c.as_ref() {
+                VersionConstraint::Exact(v) => VersionComparator::parse_lenient(v).into_iter().collect(),
+                _ => Vec::new(),
+            },
+            VersionConstraint::And(cs) => cs.iter().flat_map(|c| c.exclusions()).collect(),
+            _ => Vec::new(),
+        }
+    }
+}
+
+/// One end of an [`Interval`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Bound {
+    version: Version,
+    inclusive: bool,
+}
+
+impl Bound {
+    fn inclusive(version: Version) -> Option<Self> {
+        Some(Self { version, inclusive: true })
+    }
+
+    fn exclusive(version: Version) -> Option<Self> {
+        Some(Self { version, inclusive: false })
+    }
+}
+
+/// The versions a constraint allows, leaving out `!=` exclusions. A
+/// missing bound means unbounded on that side.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Interval {
+    lower: Option<Bound>,
+    upper: Option<Bound>,
+}
+
+impl Interval {
+    /// `None` when the constraint's bounds can't be worked out.
+    fn of(constraint: &VersionConstraint) -> Option<Self> {
+        match constraint {
+            VersionConstraint::Exact(v) => {
+                let version = VersionComparator::parse_lenient(v)?;
+                Some(Self {
+                    lower: Bound::inclusive(version.clone()),
+                    upper: Bound::inclusive(version),
+                })
+            }
+            VersionConstraint::GreaterThanEqual(v) => Some(Self {
+                lower: Bound::inclusive(VersionComparator::parse_lenient(v)?),
+                upper: None,
+            }),
+            // Matches `VersionComparator::satisfies`: `^0.y` stays within 0.y.
+            VersionConstraint::Caret(v) => {
+                let version = VersionComparator::parse_lenient(v)?;
+                let upper = match version.major {
+                    0 => Version::new(0, version.minor + 1, 0),
+                    major => Version::new(major + 1, 0, 0),
+                };
+                Some(Self {
+                    lower: Bound::inclusive(version),
+                    upper: Bound::exclusive(upper),
+                })
+            }
+            VersionConstraint::Tilde(v) => {
+                let version = VersionComparator::parse_lenient(v)?;
+                let upper = match v.split('.').count() {
+                    1 => Version::new(version.major + 1, 0, 0),
+                    _ => Version::new(version.major, version.minor + 1, 0),
+                };
+                Some(Self {
+                    lower: Bound::inclusive(version),
+                    upper: Bound::exclusive(upper),
+                })
+            }
+            VersionConstraint::Range(range) if matches!(range.trim(), "" | "*") => Some(Self::default()),
+            VersionConstraint::Range(range) => comparators(range)?
+                .iter()
+                .try_fold(Self::default(), |acc, c| acc.intersect(&Self::from_comparator(c)?)),
+            VersionConstraint::Not(_) => Some(Self::default()),
+            VersionConstraint::And(cs) => cs
+                .iter()
+                .try_fold(Self::default(), |acc, c| acc.intersect(&Self::of(c)?)),
+        }
+    }
+
+    fn from_comparator(c: &Comparator) -> Option<Self> {
+        let mut lower = Version::new(c.major, c.minor.unwrap_or(0), c.patch.unwrap_or(0));
+        lower.pre = c.pre.clone();
+        // The first version past a partial one: `1.2` -> 1.3.0, `1` -> 2.0.0.
+        let next = match c.minor {
+            Some(minor) => Version::new(c.major, minor + 1, 0),
+            None => Version::new(c.major + 1, 0, 0),
+        };
+        let full = c.patch.is_some();
+
+        let (lower, upper) = match c.op {
+            Op::Exact | Op::Wildcard if full => (Bound::inclusive(lower.clone()), Bound::inclusive(lower)),
+            Op::Exact | Op::Wildcard => (Bound::inclusive(lower), Bound::exclusive(next)),
+            Op::Greater if full => (Bound::exclusive(lower), None),
+            Op::Greater => (Bound::inclusive(next), None),
+            Op::GreaterEq => (Bound::inclusive(lower), None),
+            Op::Less => (None, Bound::exclusive(lower)),
+            Op::LessEq if full => (None, Bound::inclusive(lower)),
+            Op::LessEq => (None, Bound::exclusive(next)),
+            Op::Tilde => (Bound::inclusive(lower), Bound::exclusive(next)),
+            Op::Caret => {
+                let upper = match (c.major, c.minor, c.patch) {
+                    (0, Some(0), Some(patch)) => Version::new(0, 0, patch + 1),
+                    (0, Some(minor), _) => Version::new(0, minor + 1, 0),
+                    (major, _, _) => Version::new(major + 1, 0, 0),
+                };
+                (Bound::inclusive(lower), Bound::exclusive(upper))
+            }
+            _ => return None,
+        };
+        Some(Self { lower, upper })
+    }
+
+    /// The versions in both intervals, or `None` if there are none.
+    fn intersect(&self, other: &Interval) -> Option<Interval> {
+        let interval = Interval {
+            lower: tighter(&self.lower, &other.lower, Ordering::Greater),
+            upper: tighter(&self.upper, &other.upper, Ordering::Less),
+        };
+        (!interval.is_empty()).then_some(interval)
+    }
+
+    fn is_empty(&self) -> bool {
+        match (&self.lower, &self.upper) {
+            (Some(lower), Some(upper)) => match lower.version.cmp(&upper.version) {
+                Ordering::Greater => true,
+                Ordering::Equal => !(lower.inclusive && upper.inclusive),
+                Ordering::Less => false,
+            },
+            _ => false,
+        }
+    }
+
+    /// The only version in the interval, if it holds just one.
+    fn point(&self) -> Option<&Version> {
+        match (&self.lower, &self.upper) {
+            (Some(lower), Some(upper)) if lower.version == upper.version => Some(&lower.version),
+            _ => None,
+        }
+    }
+
+    fn contains(&self, version: &Version) -> bool {
+        let above = self.lower.as_ref().is_none_or(|l| match version.cmp(&l.version) {
+            Ordering::Equal => l.inclusive,
+            ordering => ordering == Ordering::Greater,
+        });
+        let below = self.upper.as_ref().is_none_or(|u| match version.cmp(&u.version) {
+            Ordering::Equal => u.inclusive,
+            ordering => ordering == Ordering::Less,
+        });
+        above && below
+    }
+
+    fn to_constraint(&self) -> VersionConstraint {
+        if let Some(version) = self.point() {
+            return VersionConstraint::Exact(version.to_string());
+        }
+        if let (Some(lower), None) = (&self.lower, &self.upper) {
+            if lower.inclusive {
+                return VersionConstraint::GreaterThanEqual(lower.version.to_string());
+            }
+        }
+
+        let lower = self
+            .lower
+            .iter()
+            .map(|l| format!("{}{}", if l.inclusive { ">=" } else { ">" }, l.version));
+        let upper = self
+            .upper
+            .iter()
+            .map(|u| format!("{}{}", if u.inclusive { "<=" } else { "<" }, u.version));
+        let parts: Vec<String> = lower.chain(upper).collect();
+        if parts.is_empty() {
+            VersionConstraint::Range("*".to_string())
+        } else {
+            VersionConstraint::Range(parts.join(", "))
+        }
+    }
+}
+
+/// Of two bounds on the same side, the one that allows fewer versions:
+/// `keep` is the ordering the tighter version has against the other.
+fn tighter(a: &Option<Bound>, b: &Option<Bound>, keep: Ordering) -> Option<Bound> {
+    match (a, b) {
+        (Some(a), Some(b)) => Some(match a.version.cmp(&b.version) {
+            Ordering::Equal if a.inclusive => b.clone(),
+            Ordering::Equal => a.clone(),
+            ordering if ordering == keep => a.clone(),
+            _ => b.clone(),
+        }),
+        (a, b) => a.clone().or_else(|| b.clone()),
+    }
+}
+
+/// The comparators of a range like `>=1.0, <2.0` or `>= 1.0 < 2.0`, which
+/// may separate them with commas or spaces and an operator from its
+/// version. `None` if any part isn't a comparator, e.g. with `||`.
+fn comparators(range: &str) -> Option<Vec<Comparator>> {
+    let mut comparators = Vec::new();
+    let mut pending = String::new();
+    for token in range.split([' ', ',']).filter(|t| !t.is_empty()) {
+        pending.push_str(token);
+        if token.chars().all(|c| matches!(c, '<' | '>' | '=' | '~' | '^')) {
+            continue;
+        }
+        comparators.push(Comparator::parse(&std::mem::take(&mut pending)).ok()?);
+    }
+    pending.is_empty().then_some(comparators)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn range(range: &str) -> VersionConstraint {
+        VersionConstraint::Range(range.to_string())
+    }
+
+    fn exclude(version: &str) -> VersionConstraint {
+        VersionConstraint::Not(Box::new(VersionConstraint::Exact(version.to_string())))
+    }
+
+    #[test]
+    fn test_intersect_overlapping() {
+        let caret = VersionConstraint::Caret("2.0".to_string());
+        let effective = caret.intersect(&range(">=2.5 <4.0")).unwrap();
+        assert_eq!(effective, range(">=2.5.0, <3.0.0"));
+        assert_eq!(effective.to_string(), ">=2.5.0, <3.0.0");
+
+        let floor = VersionConstraint::GreaterThanEqual("1.4".to_string());
+        assert_eq!(floor.intersect(&range("<=1.4.0")), Some(VersionConstraint::Exact("1.4.0".to_string())));
+    }
+
+    #[test]
+    fn test_intersect_nested() {
+        let caret = VersionConstraint::Caret("2.0".to_string());
+        let tilde = VersionConstraint::Tilde("2.5".to_string());
+        assert_eq!(caret.intersect(&tilde), Some(tilde.clone()));
+        assert_eq!(tilde.intersect(&caret), Some(tilde.clone()));
+
+        // Exclusions from the wider constraint carry over when they fall
+        // inside the narrower one.
+        let excluding = VersionConstraint::And(vec![range(">=2.0 <3.0"), exclude("2.5.1"), exclude("2.9.0")]);
+        assert_eq!(
+            excluding.intersect(&tilde),
+            Some(VersionConstraint::And(vec![tilde.clone(), exclude("2.5.1")]))
+        );
+    }
+
+    #[test]
+    fn test_intersect_disjoint() {
+        let one = VersionConstraint::Caret("1.0".to_string());
+        let two = VersionConstraint::Caret("2.0".to_string());
+        assert_eq!(one.intersect(&two), None);
+        assert_eq!(range("<2.0.0").intersect(&VersionConstraint::GreaterThanEqual("2.0.0".to_string())), None);
+        assert_eq!(VersionConstraint::Caret("0.2".to_string()).intersect(&range(">=0.3")), None);
+
+        let pinned = VersionConstraint::Exact("1.5.0".to_string());
+        assert_eq!(pinned.intersect(&VersionConstraint::And(vec![one, exclude("1.5.0")])), None);
+    }
+
+    #[test]
+    fn test_intersect_unknown_bounds_are_kept() {
+        let alternatives = range("^1.0 || ^2.0");
+        let caret = VersionConstraint::Caret("2.0".to_string());
+        assert_eq!(
+            alternatives.intersect(&caret),
+            Some(VersionConstraint::And(vec![alternatives.clone(), caret]))
+        );
+        assert_eq!(range("*").intersect(&range("dev-main")), Some(VersionConstraint::And(vec![range("*"), range("dev-main")])));
+    }
+}
@@ -3,6 +3,7 @@ use std::path::PathBuf;
 
 use super::constraint::VersionConstraint;
 use super::ecosystem::Ecosystem;
+use super::trace::DependencyTrace;
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct DependencyFile {
@@ -11,11 +12,185 @@ pub struct DependencyFile {
     pub content: String,
 }
 
+/// Which section, or group, of the manifest a dependency was declared in.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DependencyScope {
+    #[default]
+    Runtime,
+    Development,
+    /// npm `optionalDependencies`.
+    Optional,
+    /// npm `peerDependencies`, provided by the consuming project.
+    Peer,
+}
+
+impl DependencyScope {
+    pub const ALL: [DependencyScope; 4] = [Self::Runtime, Self::Development, Self::Optional, Self::Peer];
+
+    /// The group name used in config files.
+    pub fn group(&self) -> &'static str {
+        match self {
+            Self::Runtime => "runtime",
+            Self::Development => "dev",
+            Self::Optional => "optional",
+            Self::Peer => "peer",
+        }
+    }
+
+    pub fn from_group(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|scope| scope.group() == name.to_lowercase())
+    }
+}
+
+/// A declared requirement on a package. A package listed in several
+/// sections yields one dependency per section, each with its own
+/// constraint.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Dependency {
     pub name: String,
+    pub ecosystem: Ecosystem,
+    #[serde(default)]
+    pub scope: DependencyScope,
     pub version: String,
     #[serde(skip)]
     pub constraint: VersionConstraint,
     pub file: PathBuf,
+    /// Canonical source repository, filled in from registry metadata when
+    /// the registry publishes one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repository: Option<String>,
+    /// SPDX license identifiers from registry metadata. Several licenses
+    /// are alternatives the package may be used under.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub license: Option<Vec<String>>,
+    /// Whether the release is signed, from registry metadata. `None` when
+    /// the registry doesn't say.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub provenance: Option<ProvenanceInfo>,
+    /// Required by the project itself, as opposed to pulled in by another
+    /// dependency.
+    #[serde(default = "default_direct")]
+    pub direct: bool,
+    /// What the scanner did for this dependency, only recorded in trace
+    /// mode.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trace: Option<DependencyTrace>,
+}
+
+fn default_direct() -> bool {
+    true
+}
+
+/// Whether a release can be traced back to the source and build that
+/// produced it, e.g. through npm's provenance attestations.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProvenanceInfo {
+    pub signed: bool,
+    /// Where the release's attestations can be fetched.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub attestation_url: Option<String>,
+}
+
+impl Dependency {
+    /// Builds a package URL such as `pkg:composer/guzzlehttp/guzzle@7.0.0`.
+    ///
+    /// The version is left out when it is a constraint (`^7.0`) rather than
+    /// a concrete release, since a purl names a single version.
+    pub fn purl(&self) -> String {
+        let name = match self.ecosystem {
+            Ecosystem::PyPI | Ecosystem::Pip => self.name.to_lowercase().replace('_', "-"),
+            Ecosystem::Maven => self.name.replacen(':', "/", 1),
+            _ => self.name.clone(),
+        };
+
+        let path = name
+            .split('/')
+            .map(percent_encode)
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let mut purl = format!("pkg:{}/{}", self.ecosystem.purl_type(), path);
+        if is_concrete_version(&self.version) {
+            purl.push('@');
+            purl.push_str(&percent_encode(&self.version));
+        }
+        purl
+    }
+}
+
+fn is_concrete_version(version: &str) -> bool {
+    !version.is_empty()
+        && !version.starts_with(['^', '~', '<', '>', '=', '!', '*'])
+        && !version.contains(|c: char| c.is_whitespace() || matches!(c, '|' | ',' | '*'))
+}
+
+fn percent_encode(segment: &str) -> String {
+    let mut encoded = String::with_capacity(segment.len());
+    for byte in segment.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'.' | b'-' | b'_' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dependency(ecosystem: Ecosystem, name: &str, version: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            ecosystem,
+            scope: DependencyScope::Runtime,
+            version: version.to_string(),
+            constraint: VersionConstraint::Exact(version.to_string()),
+            file: PathBuf::from("manifest"),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        }
+    }
+
+    #[test]
+    fn test_composer_purl() {
+        let dep = dependency(Ecosystem::Composer, "guzzlehttp/guzzle", "7.0.0");
+        assert_eq!(dep.purl(), "pkg:composer/guzzlehttp/guzzle@7.0.0");
+    }
+
+    #[test]
+    fn test_npm_scoped_purl() {
+        let dep = dependency(Ecosystem::Npm, "@babel/core", "7.23.0");
+        assert_eq!(dep.purl(), "pkg:npm/%40babel/core@7.23.0");
+
+        let dep = dependency(Ecosystem::Npm, "lodash", "4.17.21");
+        assert_eq!(dep.purl(), "pkg:npm/lodash@4.17.21");
+    }
+
+    #[test]
+    fn test_cargo_purl() {
+        let dep = dependency(Ecosystem::Cargo, "serde_json", "1.0.108");
+        assert_eq!(dep.purl(), "pkg:cargo/serde_json@1.0.108");
+    }
+
+    #[test]
+    fn test_purl_omits_constraints() {
+        let dep = dependency(Ecosystem::Composer, "monolog/monolog", "^2.0");
+        assert_eq!(dep.purl(), "pkg:composer/monolog/monolog");
+
+        let dep = dependency(Ecosystem::Npm, "left-pad", ">=1.0 <2");
+        assert_eq!(dep.purl(), "pkg:npm/left-pad");
+    }
+
+    #[test]
+    fn test_purl_encodes_special_characters() {
+        let dep = dependency(Ecosystem::Composer, "acme/pkg name", "1.0.0+build");
+        assert_eq!(dep.purl(), "pkg:composer/acme/pkg%20name@1.0.0%2Bbuild");
+    }
 }
@@ -14,6 +14,38 @@ pub enum Ecosystem {
     Gem,
     Dotnet,
     GitHubActions,
+    Pub,
+    Terraform,
+}
+
+impl Ecosystem {
+    /// The package-url type for this ecosystem, e.g. `composer` or `pypi`.
+    pub fn purl_type(&self) -> &'static str {
+        match self {
+            Ecosystem::Composer => "composer",
+            Ecosystem::Npm => "npm",
+            Ecosystem::Cargo => "cargo",
+            Ecosystem::PyPI | Ecosystem::Pip => "pypi",
+            Ecosystem::Go => "golang",
+            Ecosystem::Maven => "maven",
+            Ecosystem::NuGet | Ecosystem::Dotnet => "nuget",
+            Ecosystem::Gem => "gem",
+            Ecosystem::GitHubActions => "github",
+            Ecosystem::Pub => "pub",
+            Ecosystem::Terraform => "terraform",
+        }
+    }
+
+    /// The lockfile that pins this ecosystem's whole dependency tree, if
+    /// there is one and Rensa knows it.
+    pub fn lockfile_name(&self) -> Option<&'static str> {
+        match self {
+            Ecosystem::Composer => Some("composer.lock"),
+            Ecosystem::Npm => Some("package-lock.json"),
+            Ecosystem::Pub => Some("pubspec.lock"),
+            _ => None,
+        }
+    }
 }
 
 impl std::fmt::Display for Ecosystem {
@@ -30,6 +62,8 @@ impl std::fmt::Display for Ecosystem {
             Ecosystem::Gem => write!(f, "gem"),
             Ecosystem::Dotnet => write!(f, "dotnet"),
             Ecosystem::GitHubActions => write!(f, "github_actions"),
+            Ecosystem::Pub => write!(f, "pub"),
+            Ecosystem::Terraform => write!(f, "terraform"),
         }
     }
 }
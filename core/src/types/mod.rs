@@ -1,11 +1,13 @@
 pub mod constraint;
 pub mod dependency;
 pub mod ecosystem;
+pub mod trace;
 pub mod update;
 pub mod vulnerability;
 
 pub use constraint::VersionConstraint;
-pub use dependency::{Dependency, DependencyFile};
+pub use dependency::{Dependency, DependencyFile, DependencyScope, ProvenanceInfo};
 pub use ecosystem::Ecosystem;
-pub use update::{CategorizedUpdate, UpdateInfo};
-pub use vulnerability::{Severity, Vulnerability};
+pub use trace::{DependencyTrace, LookupTrace};
+pub use update::{CategorizedUpdate, PackageInfo, ReleaseNote, UpdateInfo};
+pub use vulnerability::{Remediation, Severity, Vulnerability};
@@ -0,0 +1,58 @@
+use serde::{Deserialize, Serialize};
+
+use crate::version::UpdateType;
+
+/// What the scanner did for one dependency, recorded in trace mode to
+/// explain how a result came about.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DependencyTrace {
+    /// The registry lookup, if one was made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub registry: Option<LookupTrace>,
+    /// Latest version the registry reported.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_version: Option<String>,
+    /// How moving to `latest_version` was classified.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub update_type: Option<UpdateType>,
+    /// The vulnerability database lookup, if one was made.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub vulnerabilities: Option<LookupTrace>,
+}
+
+/// One registry or vulnerability database lookup.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct LookupTrace {
+    /// Where the request goes, when the plugin says.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// What was asked for beyond the URL, e.g. OSV's ecosystem and version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub query: Option<String>,
+    /// Whether the answer was already cached, so no request was made.
+    /// `None` when the plugin can't tell.
+    #[serde(default)]
+    pub cached: Option<bool>,
+    /// Why the lookup failed, if it did.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl LookupTrace {
+    pub fn new(url: String) -> Self {
+        Self {
+            url: Some(url),
+            ..Self::default()
+        }
+    }
+
+    pub fn with_query(mut self, query: String) -> Self {
+        self.query = Some(query);
+        self
+    }
+
+    pub fn with_cached(mut self, cached: bool) -> Self {
+        self.cached = Some(cached);
+        self
+    }
+}
@@ -1,8 +1,10 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 
-use super::dependency::Dependency;
+use super::dependency::{Dependency, ProvenanceInfo};
 use super::vulnerability::Vulnerability;
-use crate::version::UpdateType;
+use crate::version::{UpdateType, VersionComparator};
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct UpdateInfo {
@@ -11,6 +13,141 @@ pub struct UpdateInfo {
     pub latest_version: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub changelog: Option<String>,
+    /// Highest version the declared constraint allows, which may be older
+    /// than `latest_version` when upgrading further needs a constraint change.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub latest_in_constraint: Option<String>,
+    /// Stable releases after the current version, up to and including the
+    /// latest.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub versions_behind: Option<usize>,
+    /// Days between the current version's release and the latest's.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub age_behind_days: Option<i64>,
+    /// Every release from the one after the current version up to the
+    /// latest, oldest first, when the update spans more than one and the
+    /// source repository publishes release notes.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub intermediate_releases: Vec<ReleaseNote>,
+}
+
+/// Where the notes for one release are published.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ReleaseNote {
+    pub version: String,
+    pub url: String,
+}
+
+impl UpdateInfo {
+    /// Fills in how far behind the current version is from a registry's
+    /// release list and, where it has them, release dates. Either stays
+    /// `None` when the registry gave nothing to count from.
+    pub fn set_freshness(&mut self, versions: &[String], released: &HashMap<String, DateTime<Utc>>) {
+        let current = VersionComparator::parse_lenient(&self.current_version);
+        let latest = VersionComparator::parse_lenient(&self.latest_version);
+
+        if let (Some(current), Some(latest), false) = (&current, &latest, versions.is_empty()) {
+            let behind = versions
+                .iter()
+                .filter_map(|v| VersionComparator::parse_lenient(v))
+                .filter(|v| v > current && v <= latest)
+                .count();
+            self.versions_behind = Some(behind);
+        }
+
+        let date = |version: &str| {
+            released
+                .get(version)
+                .or_else(|| released.get(version.trim_start_matches('v')))
+                .or_else(|| released.get(&format!("v{}", version)))
+        };
+        if let (Some(current), Some(latest)) = (date(&self.current_version), date(&self.latest_version)) {
+            self.age_behind_days = Some((*latest - *current).num_days().max(0));
+        }
+    }
+
+    /// Fills in `intermediate_releases` from a registry's release list and
+    /// the dependency's repository. Left empty for single-release updates
+    /// and for hosts without a known release page layout.
+    pub fn set_release_notes(&mut self, versions: &[String]) {
+        let (Some(current), Some(latest)) = (
+            VersionComparator::parse_lenient(&self.current_version),
+            VersionComparator::parse_lenient(&self.latest_version),
+        ) else {
+            return;
+        };
+        let Some(repository) = self.dependency.repository.as_deref() else {
+            return;
+        };
+
+        let mut releases: Vec<_> = versions
+            .iter()
+            .filter_map(|v| Some((VersionComparator::parse_lenient(v)?, v)))
+            .filter(|(parsed, _)| *parsed > current && *parsed <= latest)
+            .collect();
+        if releases.len() < 2 {
+            return;
+        }
+        releases.sort_by(|a, b| a.0.cmp(&b.0));
+
+        self.intermediate_releases = releases
+            .into_iter()
+            .filter_map(|(_, version)| {
+                Some(ReleaseNote {
+                    version: version.clone(),
+                    url: release_notes_url(repository, version)?,
+                })
+            })
+            .collect();
+    }
+
+    /// E.g. "3 versions / 14 months behind", when anything is known.
+    pub fn freshness(&self) -> Option<String> {
+        let versions = self.versions_behind.map(|n| format!("{} version{}", n, if n == 1 { "" } else { "s" }));
+        let age = self.age_behind_days.map(|days| match days / 30 {
+            0 => format!("{} day{}", days, if days == 1 { "" } else { "s" }),
+            1 => "1 month".to_string(),
+            months => format!("{} months", months),
+        });
+
+        let parts: Vec<String> = versions.into_iter().chain(age).collect();
+        (!parts.is_empty()).then(|| format!("{} behind", parts.join(" / ")))
+    }
+}
+
+/// What a registry reports about a dependency in one lookup.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PackageInfo {
+    pub update: Option<UpdateInfo>,
+    /// The package's source repository URL, when the registry knows it.
+    pub repository: Option<String>,
+    /// The package's SPDX license identifiers, when the registry knows them.
+    pub license: Option<Vec<String>>,
+    /// Signing status of the dependency's release, when the registry
+    /// publishes it.
+    pub provenance: Option<ProvenanceInfo>,
+    /// The registry's deprecation notice for the dependency's version.
+    #[serde(default)]
+    pub deprecated: Option<String>,
+    /// Published stable releases, newest first, when the registry lists
+    /// them.
+    pub versions: Vec<String>,
+    /// When each version was published, keyed as in `versions`.
+    pub released: HashMap<String, DateTime<Utc>>,
+}
+
+/// The release page for `version` on GitHub or GitLab, which tag releases
+/// with the version as published.
+fn release_notes_url(repository: &str, version: &str) -> Option<String> {
+    let repository = repository.trim_end_matches('/').trim_end_matches(".git");
+    let repository = repository.strip_prefix("git+").unwrap_or(repository);
+    if repository.starts_with("https://github.com/") {
+        Some(format!("{}/releases/tag/{}", repository, version))
+    } else if repository.starts_with("https://gitlab.com/") {
+        Some(format!("{}/-/releases/{}", repository, version))
+    } else {
+        None
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -59,3 +196,79 @@ impl CategorizedUpdate {
         score
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DependencyScope, Ecosystem, VersionConstraint};
+    use std::path::PathBuf;
+
+    fn update(current: &str, latest: &str) -> UpdateInfo {
+        UpdateInfo {
+            dependency: Dependency {
+                name: "monolog/monolog".to_string(),
+                ecosystem: Ecosystem::Composer,
+                scope: DependencyScope::Runtime,
+                version: current.to_string(),
+                constraint: VersionConstraint::Caret(current.to_string()),
+                file: PathBuf::from("composer.json"),
+                repository: None,
+                license: None,
+                provenance: None,
+                direct: true,
+                trace: None,
+            },
+            current_version: current.to_string(),
+            latest_version: latest.to_string(),
+            changelog: None,
+            latest_in_constraint: None,
+            versions_behind: None,
+            age_behind_days: None,
+            intermediate_releases: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_freshness_counts_releases_and_age() {
+        let versions: Vec<String> = ["3.5.0", "3.4.0", "3.3.1", "3.3.0", "3.2.0"].iter().map(|v| v.to_string()).collect();
+        let released = HashMap::from([
+            ("3.3.0".to_string(), "2023-01-10T00:00:00Z".parse().unwrap()),
+            ("3.5.0".to_string(), "2024-03-20T00:00:00Z".parse().unwrap()),
+        ]);
+
+        let mut info = update("3.3.0", "3.5.0");
+        info.set_freshness(&versions, &released);
+        assert_eq!(info.versions_behind, Some(3));
+        assert_eq!(info.age_behind_days, Some(435));
+        assert_eq!(info.freshness().as_deref(), Some("3 versions / 14 months behind"));
+
+        let mut unknown = update("3.3.0", "3.5.0");
+        unknown.set_freshness(&[], &HashMap::new());
+        assert_eq!(unknown.versions_behind, None);
+        assert_eq!(unknown.freshness(), None);
+    }
+
+    #[test]
+    fn test_release_notes_for_each_intermediate_version() {
+        let versions: Vec<String> = ["3.5.0", "3.4.0", "3.3.1", "3.3.0", "3.2.0"].iter().map(|v| v.to_string()).collect();
+
+        let mut info = update("3.3.0", "3.5.0");
+        info.dependency.repository = Some("https://github.com/Seldaek/monolog.git".to_string());
+        info.set_release_notes(&versions);
+        let notes: Vec<(&str, &str)> =
+            info.intermediate_releases.iter().map(|n| (n.version.as_str(), n.url.as_str())).collect();
+        assert_eq!(
+            notes,
+            vec![
+                ("3.3.1", "https://github.com/Seldaek/monolog/releases/tag/3.3.1"),
+                ("3.4.0", "https://github.com/Seldaek/monolog/releases/tag/3.4.0"),
+                ("3.5.0", "https://github.com/Seldaek/monolog/releases/tag/3.5.0"),
+            ]
+        );
+
+        let mut single = update("3.4.0", "3.5.0");
+        single.dependency.repository = Some("https://github.com/Seldaek/monolog".to_string());
+        single.set_release_notes(&versions);
+        assert!(single.intermediate_releases.is_empty());
+    }
+}
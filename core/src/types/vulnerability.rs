@@ -1,6 +1,12 @@
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+use crate::config::SeverityThreshold;
+use crate::types::VersionConstraint;
+use crate::version::VersionComparator;
+use semver::Version;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
 pub enum Severity {
     Critical,
@@ -44,6 +50,40 @@ impl Severity {
             Severity::Unknown
         }
     }
+
+    /// Returns true if this severity is at or above the given threshold.
+    /// `Unknown` never meets any threshold.
+    pub fn meets_threshold(&self, threshold: SeverityThreshold) -> bool {
+        let minimum = match threshold {
+            SeverityThreshold::Low => Severity::Low,
+            SeverityThreshold::Medium => Severity::Medium,
+            SeverityThreshold::High => Severity::High,
+            SeverityThreshold::Critical => Severity::Critical,
+        };
+        *self >= minimum
+    }
+
+    fn rank(&self) -> u8 {
+        match self {
+            Severity::Unknown => 0,
+            Severity::Low => 1,
+            Severity::Medium => 2,
+            Severity::High => 3,
+            Severity::Critical => 4,
+        }
+    }
+}
+
+impl PartialOrd for Severity {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Severity {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.rank().cmp(&other.rank())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -55,4 +95,127 @@ pub struct Vulnerability {
     pub affected_versions: Vec<String>,
     pub fixed_versions: Vec<String>,
     pub references: Vec<String>,
+    /// Name of the dependency this finding was reported for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub package: Option<String>,
+    /// For an advisory included because another one lists it as `related`,
+    /// the id of that advisory.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub related_to: Option<String>,
+    /// Whether a fixed release can be reached without changing the
+    /// dependency's constraint. Only set when the registry listed releases.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub remediation: Option<Remediation>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Remediation {
+    /// The lowest published fixed release the current constraint allows.
+    pub fixed_in_constraint: Option<String>,
+    /// No fixed release satisfies the constraint, e.g. the fix is only in
+    /// the next major version.
+    pub requires_constraint_change: bool,
+}
+
+impl Remediation {
+    /// Cross-references an advisory's fixed versions with the releases in
+    /// `available`. A release fixes the advisory when it is at or above a
+    /// fixed version on that fix's major line, since advisories list one
+    /// fix per maintained branch. `None` when either list is empty.
+    pub fn find(constraint: &VersionConstraint, fixed_versions: &[String], available: &[String]) -> Option<Self> {
+        let fixes: Vec<Version> = fixed_versions.iter().filter_map(|v| parse(v)).collect();
+        if fixes.is_empty() || available.is_empty() {
+            return None;
+        }
+
+        let fixed_in_constraint = available
+            .iter()
+            .filter_map(|raw| Some((parse(raw)?, raw)))
+            .filter(|(v, _)| fixes.iter().any(|fix| v >= fix && v.major == fix.major))
+            .filter(|(v, _)| VersionComparator::satisfies(constraint, &v.to_string()))
+            .min_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, raw)| raw.clone());
+
+        Some(Self {
+            requires_constraint_change: fixed_in_constraint.is_none(),
+            fixed_in_constraint,
+        })
+    }
+}
+
+fn parse(version: &str) -> Option<Version> {
+    Version::parse(version.trim_start_matches('v')).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_severity_sort_order() {
+        let mut severities = vec![
+            Severity::High,
+            Severity::Unknown,
+            Severity::Critical,
+            Severity::Low,
+            Severity::Medium,
+        ];
+        severities.sort();
+
+        assert_eq!(
+            severities,
+            vec![
+                Severity::Unknown,
+                Severity::Low,
+                Severity::Medium,
+                Severity::High,
+                Severity::Critical,
+            ]
+        );
+        assert!(Severity::Critical > Severity::High);
+        assert!(Severity::Low > Severity::Unknown);
+    }
+
+    fn versions(raw: &[&str]) -> Vec<String> {
+        raw.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_fix_reachable_within_constraint() {
+        let constraint = VersionConstraint::Caret("7.0".to_string());
+        let available = versions(&["8.0.1", "7.4.3", "7.4.2", "7.4.1", "6.5.9"]);
+
+        let remediation = Remediation::find(&constraint, &versions(&["6.5.8", "7.4.2"]), &available).unwrap();
+        assert_eq!(remediation.fixed_in_constraint.as_deref(), Some("7.4.2"));
+        assert!(!remediation.requires_constraint_change);
+    }
+
+    #[test]
+    fn test_fix_requires_constraint_change() {
+        let constraint = VersionConstraint::Caret("7.0".to_string());
+        let available = versions(&["8.0.1", "8.0.0", "7.4.3"]);
+
+        let remediation = Remediation::find(&constraint, &versions(&["8.0.1"]), &available).unwrap();
+        assert_eq!(remediation.fixed_in_constraint, None);
+        assert!(remediation.requires_constraint_change);
+
+        assert_eq!(Remediation::find(&constraint, &[], &available), None);
+    }
+
+    #[test]
+    fn test_meets_threshold() {
+        assert!(Severity::Critical.meets_threshold(SeverityThreshold::High));
+        assert!(Severity::High.meets_threshold(SeverityThreshold::High));
+        assert!(!Severity::Medium.meets_threshold(SeverityThreshold::High));
+        assert!(Severity::Low.meets_threshold(SeverityThreshold::Low));
+        assert!(!Severity::Low.meets_threshold(SeverityThreshold::Medium));
+    }
+
+    #[test]
+    fn test_unknown_never_meets_threshold() {
+        assert!(!Severity::Unknown.meets_threshold(SeverityThreshold::Low));
+        assert!(!Severity::Unknown.meets_threshold(SeverityThreshold::Medium));
+        assert!(!Severity::Unknown.meets_threshold(SeverityThreshold::High));
+        assert!(!Severity::Unknown.meets_threshold(SeverityThreshold::Critical));
+    }
 }
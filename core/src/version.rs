@@ -1,5 +1,5 @@
 use crate::types::VersionConstraint;
-use semver::{Version, VersionReq};
+use semver::{BuildMetadata, Prerelease, Version, VersionReq};
 use serde::{Deserialize, Serialize};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
@@ -37,6 +37,16 @@ impl UpdateType {
     }
 }
 
+/// Tweaks how [`VersionComparator::classify_update_with`] labels updates.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ClassifyOptions {
+    /// Under SemVer a `0.x` minor bump (`0.1.0 -> 0.2.0`) may break the API.
+    /// When set, such bumps are reported as `Major` so they aren't treated
+    /// as safe to apply. Off by default, which keeps the strict reading of
+    /// the version numbers.
+    pub treat_zerover_minor_as_breaking: bool,
+}
+
 pub struct VersionComparator;
 
 impl VersionComparator {
@@ -69,20 +79,56 @@ impl VersionComparator {
                 // ~1.2.3 matches >=1.2.3 <1.3.0
                 Self::parse_tilde_range(v, version)
             }
+            VersionConstraint::Not(c) => !Self::satisfies(c, version),
+            VersionConstraint::And(cs) => cs.iter().all(|c| Self::satisfies(c, version)),
         }
     }
 
+    /// Returns the greatest version in `versions` that satisfies `constraint`.
+    ///
+    /// Versions may carry a `v` prefix or be partial (`1.2`). Stable releases
+    /// are preferred; a pre-release is only returned when no stable version
+    /// matches. The original string is returned unchanged.
+    pub fn highest_satisfying(constraint: &VersionConstraint, versions: &[String]) -> Option<String> {
+        let candidates = versions.iter().filter_map(|raw| {
+            let parsed = Self::parse_version(raw.trim_start_matches('v'))?;
+            Self::satisfies(constraint, &parsed.to_string()).then_some((parsed, raw))
+        });
+
+        let (stable, pre): (Vec<_>, Vec<_>) = candidates.partition(|(v, _)| v.pre.is_empty());
+        let pool = if stable.is_empty() { pre } else { stable };
+
+        pool.into_iter()
+            .max_by(|(a, _), (b, _)| a.cmp(b))
+            .map(|(_, raw)| raw.clone())
+    }
+
     pub fn classify_update(current: &str, latest: &str) -> UpdateType {
-        let current = Version::parse(current).ok();
-        let latest = Version::parse(latest).ok();
+        Self::classify_update_with(current, latest, ClassifyOptions::default())
+    }
+
+    /// Moving from a pre-release to the stable release of the same version
+    /// (`1.0.0-rc.1 -> 1.0.0`) is a `Patch`, so users are told to leave the
+    /// release candidate. Build metadata (`+build2`) is ignored.
+    pub fn classify_update_with(current: &str, latest: &str, options: ClassifyOptions) -> UpdateType {
+        let current = Self::parse_lenient(current);
+        let latest = Self::parse_lenient(latest);
 
         match (current, latest) {
             (Some(c), Some(l)) => {
+                let same_release = (l.major, l.minor, l.patch) == (c.major, c.minor, c.patch);
                 if l.major > c.major {
                     UpdateType::Major
                 } else if l.minor > c.minor {
-                    UpdateType::Minor
-                } else if l.patch > c.patch {
+                    if options.treat_zerover_minor_as_breaking && c.major == 0 {
+                        UpdateType::Major
+                    } else {
+                        UpdateType::Minor
+                    }
+                } else if l.patch > c.patch
+                    || (same_release && revision(&l) > revision(&c))
+                    || (same_release && !c.pre.is_empty() && l.pre.is_empty())
+                {
                     UpdateType::Patch
                 } else {
                     UpdateType::None
@@ -92,6 +138,16 @@ impl VersionComparator {
         }
     }
 
+    /// Whether `latest` is older than `current`, e.g. when the manifest is
+    /// pinned to a release that was yanked or only published privately.
+    /// `classify_update` reports these as `None`.
+    pub fn is_downgrade(current: &str, latest: &str) -> bool {
+        match (Self::parse_lenient(current), Self::parse_lenient(latest)) {
+            (Some(c), Some(l)) => precedence(&l) < precedence(&c),
+            _ => false,
+        }
+    }
+
     fn parse_caret_range(constraint: &str, version: &str) -> bool {
         // Use centralized version parsing to handle partial versions like "1" or "5.0"
         let constraint_ver = match Self::parse_version(constraint) {
@@ -178,9 +234,53 @@ impl VersionComparator {
     }
 
     fn parse_version(version: &str) -> Option<Version> {
-        let normalized = Self::pad_version(version);
-        Version::parse(&normalized).ok()
+        Self::parse_lenient(version)
     }
+
+    /// Parses the version forms registries like Packagist publish that
+    /// strict SemVer rejects: a `v` prefix, partial versions (`1.2`),
+    /// zero-padded or date-based segments (`2023.01.15`, `20230115`) and a
+    /// fourth revision segment (`1.2.3.4`), which becomes build metadata.
+    pub fn parse_lenient(version: &str) -> Option<Version> {
+        let version = version.trim().trim_start_matches(['v', 'V']);
+        if let Ok(parsed) = Version::parse(version) {
+            return Some(parsed);
+        }
+
+        let (numbers, suffix) = match version.find(['-', '+']) {
+            Some(i) => version.split_at(i),
+            None => (version, ""),
+        };
+        let parts = numbers
+            .split('.')
+            .map(|p| p.parse::<u64>().ok())
+            .collect::<Option<Vec<_>>>()?;
+        let (major, minor, patch, revision) = match parts[..] {
+            [major] => (major, 0, 0, None),
+            [major, minor] => (major, minor, 0, None),
+            [major, minor, patch] => (major, minor, patch, None),
+            [major, minor, patch, revision] => (major, minor, patch, Some(revision)),
+            _ => return None,
+        };
+
+        let mut parsed = Version::parse(&format!("{}.{}.{}{}", major, minor, patch, suffix)).ok()?;
+        if let Some(revision) = revision {
+            parsed.build = BuildMetadata::new(&revision.to_string()).ok()?;
+        }
+        Some(parsed)
+    }
+}
+
+/// What orders releases: the version numbers, including a fourth revision
+/// segment, then the pre-release, which sorts below the release itself.
+fn precedence(version: &Version) -> (u64, u64, u64, u64, &Prerelease) {
+    (version.major, version.minor, version.patch, revision(version), &version.pre)
+}
+
+/// The fourth segment of a version like `1.2.3.4`, or 0 for other build
+/// metadata.
+fn revision(version: &Version) -> u64 {
+    version.build.as_str().parse().unwrap_or(0)
 }
 
 #[cfg(test)]
@@ -256,6 +356,39 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_classify_prerelease_promotion() {
+        assert_eq!(
+            VersionComparator::classify_update("1.0.0-rc.1", "1.0.0"),
+            UpdateType::Patch
+        );
+        assert_eq!(
+            VersionComparator::classify_update("2.1.0-beta.2", "2.1.0"),
+            UpdateType::Patch
+        );
+        // Stable to a pre-release of the same version is not an update.
+        assert_eq!(
+            VersionComparator::classify_update("1.0.0", "1.0.0-rc.1"),
+            UpdateType::None
+        );
+        assert_eq!(
+            VersionComparator::classify_update("1.2.0-rc.1", "1.1.0"),
+            UpdateType::None
+        );
+    }
+
+    #[test]
+    fn test_classify_build_metadata_only() {
+        assert_eq!(
+            VersionComparator::classify_update("1.0.0+build1", "1.0.0+build2"),
+            UpdateType::None
+        );
+        assert_eq!(
+            VersionComparator::classify_update("1.0.0-rc.1+build1", "1.0.0+build2"),
+            UpdateType::Patch
+        );
+    }
+
     #[test]
     fn test_classify_downgrade() {
         // Downgrades should be classified as None
@@ -269,6 +402,18 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_is_downgrade() {
+        assert!(VersionComparator::is_downgrade("2.0.0", "1.0.0"));
+        assert!(VersionComparator::is_downgrade("v1.2.3.5", "1.2.3.4"));
+        assert!(VersionComparator::is_downgrade("1.0.0", "1.0.0-rc.1"));
+        assert!(!VersionComparator::is_downgrade("1.0.0", "2.0.0"));
+        assert!(!VersionComparator::is_downgrade("1.0.0", "1.0"));
+        assert!(!VersionComparator::is_downgrade("1.0.0-rc.1", "1.0.0"));
+        assert!(!VersionComparator::is_downgrade("1.0.0+build2", "1.0.0+build1"));
+        assert!(!VersionComparator::is_downgrade("^2.0", "1.0.0"));
+    }
+
     #[test]
     fn test_classify_unknown() {
         // Invalid versions should return Unknown
@@ -286,13 +431,67 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_parse_lenient_composer_forms() {
+        let parse = |v: &str| VersionComparator::parse_lenient(v).map(|v| v.to_string());
+        assert_eq!(parse("v1.2.3"), Some("1.2.3".to_string()));
+        assert_eq!(parse("1.2"), Some("1.2.0".to_string()));
+        assert_eq!(parse("1.2.3.4"), Some("1.2.3+4".to_string()));
+        assert_eq!(parse("v2.0.0.1-beta1"), Some("2.0.0-beta1+1".to_string()));
+        assert_eq!(parse("2023.01.15"), Some("2023.1.15".to_string()));
+        assert_eq!(parse("20230115"), Some("20230115.0.0".to_string()));
+        assert_eq!(parse("1.2.3.4.5"), None);
+        assert_eq!(parse("dev-main"), None);
+    }
+
+    #[test]
+    fn test_classify_four_segment_versions() {
+        assert_eq!(VersionComparator::classify_update("1.2.3.4", "1.2.3.10"), UpdateType::Patch);
+        assert_eq!(VersionComparator::classify_update("1.2.3.4", "1.3.0.0"), UpdateType::Minor);
+        assert_eq!(VersionComparator::classify_update("v1.2.3.4", "2.0.0.0"), UpdateType::Major);
+        assert_eq!(VersionComparator::classify_update("1.2.3.4", "1.2.3.4"), UpdateType::None);
+        assert_eq!(VersionComparator::classify_update("2023.01.15", "2023.02.01"), UpdateType::Minor);
+    }
+
+    #[test]
+    fn test_classify_zerover_semver_strict() {
+        let options = ClassifyOptions::default();
+        assert_eq!(
+            VersionComparator::classify_update_with("0.1.0", "0.2.0", options),
+            UpdateType::Minor
+        );
+        assert_eq!(
+            VersionComparator::classify_update_with("0.1.0", "0.1.3", options),
+            UpdateType::Patch
+        );
+    }
+
+    #[test]
+    fn test_classify_zerover_minor_as_breaking() {
+        let options = ClassifyOptions {
+            treat_zerover_minor_as_breaking: true,
+        };
+        assert_eq!(
+            VersionComparator::classify_update_with("0.1.0", "0.2.0", options),
+            UpdateType::Major
+        );
+        assert_eq!(
+            VersionComparator::classify_update_with("0.1.0", "0.1.3", options),
+            UpdateType::Patch
+        );
+        // Only 0.x is affected
+        assert_eq!(
+            VersionComparator::classify_update_with("1.1.0", "1.2.0", options),
+            UpdateType::Minor
+        );
+    }
+
     #[test]
     fn test_classify_with_pre_release() {
-        // Pre-release versions comparison - when numeric parts are equal, it's None
-        // (semver treats pre-release as separate from release)
+        // Leaving a pre-release for its stable release is a patch
         assert_eq!(
             VersionComparator::classify_update("1.0.0-alpha", "1.0.0"),
-            UpdateType::None
+            UpdateType::Patch
         );
         // When numeric parts differ, classification works on numeric parts
         assert_eq!(
@@ -364,6 +563,36 @@ mod tests {
         assert!(!VersionComparator::satisfies(&constraint, "0.9.0"));
     }
 
+    #[test]
+    fn test_satisfies_hyphen_range_inclusive() {
+        let constraint = VersionConstraint::parse_hyphen_range("1.0.0 - 2.0.0").unwrap();
+
+        assert!(VersionComparator::satisfies(&constraint, "1.0.0"));
+        assert!(VersionComparator::satisfies(&constraint, "1.5.3"));
+        assert!(VersionComparator::satisfies(&constraint, "2.0.0"));
+        assert!(!VersionComparator::satisfies(&constraint, "0.9.9"));
+        assert!(!VersionComparator::satisfies(&constraint, "2.0.1"));
+    }
+
+    #[test]
+    fn test_satisfies_partial_hyphen_range() {
+        // The lower bound is padded, the upper takes in all of 2.3.x.
+        let constraint = VersionConstraint::parse_hyphen_range("1.2 - 2.3").unwrap();
+        assert_eq!(constraint, VersionConstraint::Range(">=1.2.0, <2.4.0".to_string()));
+
+        assert!(VersionComparator::satisfies(&constraint, "1.2.0"));
+        assert!(VersionComparator::satisfies(&constraint, "2.3.9"));
+        assert!(!VersionComparator::satisfies(&constraint, "1.1.9"));
+        assert!(!VersionComparator::satisfies(&constraint, "2.4.0"));
+
+        let constraint = VersionConstraint::parse_hyphen_range("1.2 - 2.3.4").unwrap();
+        assert!(VersionComparator::satisfies(&constraint, "2.3.4"));
+        assert!(!VersionComparator::satisfies(&constraint, "2.3.5"));
+
+        assert_eq!(VersionConstraint::parse_hyphen_range("^1.0"), None);
+        assert_eq!(VersionConstraint::parse_hyphen_range("1.0 - 2.0 || 3.0"), None);
+    }
+
     #[test]
     fn test_satisfies_greater_than_equal() {
         let constraint = VersionConstraint::GreaterThanEqual("1.2.0".to_string());
@@ -478,4 +707,70 @@ mod tests {
             UpdateType::Major
         );
     }
+
+    fn versions(list: &[&str]) -> Vec<String> {
+        list.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_highest_satisfying_picks_greatest_match() {
+        let available = versions(&["1.2.0", "1.10.0", "1.9.3", "2.0.0", "0.9.0"]);
+
+        let caret = VersionConstraint::Caret("1.2".to_string());
+        assert_eq!(
+            VersionComparator::highest_satisfying(&caret, &available),
+            Some("1.10.0".to_string())
+        );
+
+        let tilde = VersionConstraint::Tilde("1.9".to_string());
+        assert_eq!(
+            VersionComparator::highest_satisfying(&tilde, &available),
+            Some("1.9.3".to_string())
+        );
+
+        let none = VersionConstraint::Caret("3.0".to_string());
+        assert_eq!(VersionComparator::highest_satisfying(&none, &available), None);
+    }
+
+    #[test]
+    fn test_exclusion_inside_range() {
+        let constraint = VersionConstraint::And(vec![
+            VersionConstraint::GreaterThanEqual("1.0".to_string()),
+            VersionConstraint::Range("<2.0".to_string()),
+            VersionConstraint::Not(Box::new(VersionConstraint::Exact("1.5.0".to_string()))),
+        ]);
+
+        assert!(VersionComparator::satisfies(&constraint, "1.4.0"));
+        assert!(VersionComparator::satisfies(&constraint, "1.6.0"));
+        assert!(!VersionComparator::satisfies(&constraint, "1.5.0"));
+        assert!(!VersionComparator::satisfies(&constraint, "2.0.0"));
+    }
+
+    #[test]
+    fn test_highest_satisfying_prefers_stable() {
+        let constraint = VersionConstraint::Caret("1.0".to_string());
+
+        let available = versions(&["1.0.0", "1.1.0", "1.2.0-beta.1"]);
+        assert_eq!(
+            VersionComparator::highest_satisfying(&constraint, &available),
+            Some("1.1.0".to_string())
+        );
+
+        let only_pre = versions(&["1.0.0-alpha", "1.0.0-beta"]);
+        assert_eq!(
+            VersionComparator::highest_satisfying(&constraint, &only_pre),
+            Some("1.0.0-beta".to_string())
+        );
+    }
+
+    #[test]
+    fn test_highest_satisfying_partial_and_prefixed_versions() {
+        let constraint = VersionConstraint::Caret("2.0".to_string());
+        let available = versions(&["v2.1", "2.0", "v2.0.5", "not-a-version", "3"]);
+
+        assert_eq!(
+            VersionComparator::highest_satisfying(&constraint, &available),
+            Some("v2.1".to_string())
+        );
+    }
 }
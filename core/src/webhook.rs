@@ -0,0 +1,135 @@
+use crate::error::{RensaError, Result};
+use crate::http::HttpClient;
+use crate::report::{ScanReport, ScanSummary};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::path::PathBuf;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum WebhookFormat {
+    /// A compact summary with a Slack-compatible `text` field.
+    #[default]
+    Summary,
+    /// The complete scan report.
+    Full,
+}
+
+impl std::str::FromStr for WebhookFormat {
+    type Err = RensaError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "summary" => Ok(WebhookFormat::Summary),
+            "full" => Ok(WebhookFormat::Full),
+            _ => Err(RensaError::InvalidConfig {
+                message: format!("unknown webhook format '{}' (expected summary or full)", s),
+            }),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+struct SummaryPayload<'a> {
+    text: String,
+    scanned_path: &'a PathBuf,
+    timestamp: DateTime<Utc>,
+    total_dependencies: usize,
+    summary: &'a ScanSummary,
+}
+
+pub fn payload(report: &ScanReport, format: WebhookFormat) -> serde_json::Result<serde_json::Value> {
+    match format {
+        WebhookFormat::Full => serde_json::to_value(report),
+        WebhookFormat::Summary => serde_json::to_value(SummaryPayload {
+            text: format!(
+                "Rensa scan of {}: {} vulnerabilities ({} critical, {} high), {} updates available",
+                report.scanned_path.display(),
+                report.summary.vulnerabilities_found,
+                report.summary.critical_vulnerabilities,
+                report.summary.high_vulnerabilities,
+                report.summary.updates_available,
+            ),
+            scanned_path: &report.scanned_path,
+            timestamp: report.timestamp,
+            total_dependencies: report.total_dependencies,
+            summary: &report.summary,
+        }),
+    }
+}
+
+/// POSTs the report to `url` in the requested format.
+pub async fn notify(client: &HttpClient, url: &str, report: &ScanReport, format: WebhookFormat) -> Result<()> {
+    let body = payload(report, format).map_err(|e| RensaError::Plugin {
+        message: format!("failed to serialize webhook payload: {}", e),
+    })?;
+    client.post_json(url, &body).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wiremock::matchers::{method, path};
+    use wiremock::{Mock, MockServer, ResponseTemplate};
+
+    #[tokio::test]
+    async fn test_notify_posts_summary() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/hook"))
+            .respond_with(ResponseTemplate::new(200).set_body_string("ok"))
+            .expect(1)
+            .mount(&server)
+            .await;
+
+        let mut report = ScanReport::new(PathBuf::from("/app"));
+        report.summary.vulnerabilities_found = 2;
+        report.summary.critical_vulnerabilities = 1;
+
+        let url = format!("{}/hook", server.uri());
+        notify(&HttpClient::new(), &url, &report, WebhookFormat::Summary)
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+
+        assert_eq!(body["scanned_path"], "/app");
+        assert_eq!(body["summary"]["vulnerabilities_found"], 2);
+        assert_eq!(body["summary"]["critical_vulnerabilities"], 1);
+        assert!(body["text"].as_str().unwrap().contains("2 vulnerabilities"));
+    }
+
+    #[tokio::test]
+    async fn test_notify_full_report() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(204))
+            .mount(&server)
+            .await;
+
+        let report = ScanReport::new(PathBuf::from("/app"));
+        notify(&HttpClient::new(), &server.uri(), &report, WebhookFormat::Full)
+            .await
+            .unwrap();
+
+        let requests = server.received_requests().await.unwrap();
+        let body: serde_json::Value = serde_json::from_slice(&requests[0].body).unwrap();
+
+        assert!(body.get("ecosystem_results").is_some());
+        assert!(body.get("text").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_notify_reports_server_errors() {
+        let server = MockServer::start().await;
+        Mock::given(method("POST"))
+            .respond_with(ResponseTemplate::new(500))
+            .mount(&server)
+            .await;
+
+        let report = ScanReport::new(PathBuf::from("/app"));
+        let result = notify(&HttpClient::new(), &server.uri(), &report, WebhookFormat::Summary).await;
+
+        assert!(result.is_err());
+    }
+}
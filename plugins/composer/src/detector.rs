@@ -1,14 +1,25 @@
+use rensa_core::manifest::{read_manifest, DEFAULT_MAX_FILE_SIZE};
 use rensa_core::plugin::Detector;
 use rensa_core::types::{DependencyFile, Ecosystem};
 use rensa_core::Result;
 use std::path::Path;
 use walkdir::WalkDir;
 
-pub struct ComposerDetector;
+pub struct ComposerDetector {
+    max_file_size: u64,
+}
 
 impl ComposerDetector {
     pub fn new() -> Self {
-        Self
+        Self {
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        }
+    }
+
+    /// Skips manifests larger than `bytes` instead of reading them.
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = bytes;
+        self
     }
 }
 
@@ -57,7 +68,7 @@ impl Detector for ComposerDetector {
                     continue;
                 }
 
-                match std::fs::read_to_string(file_path) {
+                match read_manifest(file_path, self.max_file_size) {
                     Ok(content) => {
                         files.push(DependencyFile {
                             ecosystem: Ecosystem::Composer,
@@ -66,7 +77,7 @@ impl Detector for ComposerDetector {
                         });
                     }
                     Err(e) => {
-                        eprintln!("Warning: Failed to read {}: {}", file_path.display(), e);
+                        eprintln!("Warning: Skipping {}: {}", file_path.display(), e);
                     }
                 }
             }
@@ -122,4 +133,32 @@ mod tests {
         assert_eq!(files.len(), 1);
         assert_eq!(files[0].path, root_json);
     }
+
+    #[tokio::test]
+    async fn test_detect_skips_oversized_manifest() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let small_json = temp_dir.path().join("app").join("composer.json");
+        fs::create_dir_all(small_json.parent().unwrap()).unwrap();
+        fs::write(&small_json, r#"{"name":"test/app"}"#).unwrap();
+
+        // Sparse file just over the default limit
+        let huge_dir = temp_dir.path().join("huge");
+        fs::create_dir_all(&huge_dir).unwrap();
+        fs::File::create(huge_dir.join("composer.json"))
+            .unwrap()
+            .set_len(DEFAULT_MAX_FILE_SIZE + 1)
+            .unwrap();
+
+        let files = ComposerDetector::new().detect(temp_dir.path()).await.unwrap();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, small_json);
+
+        let files = ComposerDetector::new()
+            .with_max_file_size(8)
+            .detect(temp_dir.path())
+            .await
+            .unwrap();
+        assert!(files.is_empty());
+    }
 }
@@ -1,8 +1,10 @@
 pub mod detector;
 pub mod parser;
+pub mod lock;
 pub mod registry;
 pub mod osv;
 pub mod plugin;
+pub mod show;
 
 pub use plugin::ComposerPlugin;
 pub use registry::{PackagistClient, PackagistClientExt, UpdateCheck};
@@ -0,0 +1,160 @@
+use serde_json::Value;
+
+/// composer.json keys that feed composer's lock `content-hash`.
+const RELEVANT_KEYS: &[&str] = &[
+    "name",
+    "version",
+    "require",
+    "require-dev",
+    "conflict",
+    "replace",
+    "provide",
+    "minimum-stability",
+    "prefer-stable",
+    "repositories",
+    "extra",
+];
+
+pub const DRIFT_WARNING: &str = "composer.lock is out of date relative to composer.json; run composer update";
+
+/// Computes the `content-hash` composer stores in composer.lock: the md5 of
+/// the relevant manifest sections, key-sorted and encoded like PHP's
+/// `json_encode` with no flags.
+pub fn content_hash(manifest: &Value) -> String {
+    let mut relevant: Vec<(String, Value)> = RELEVANT_KEYS
+        .iter()
+        .filter_map(|key| manifest.get(*key).map(|v| (key.to_string(), v.clone())))
+        .collect();
+
+    if let Some(platform) = manifest.get("config").and_then(|c| c.get("platform")) {
+        relevant.push(("config".to_string(), serde_json::json!({ "platform": platform })));
+    }
+
+    relevant.sort_by(|(a, _), (b, _)| a.cmp(b));
+    let relevant: serde_json::Map<String, Value> = relevant.into_iter().collect();
+
+    format!("{:x}", md5::compute(php_json_encode(&Value::Object(relevant))))
+}
+
+/// Returns a warning when the lock file's `content-hash` doesn't match the
+/// manifest. Lock files without a hash are not checked.
+pub fn check_drift(manifest: &Value, lock: &Value) -> Option<String> {
+    let recorded = lock.get("content-hash").and_then(|h| h.as_str())?;
+
+    if recorded == content_hash(manifest) {
+        None
+    } else {
+        Some(DRIFT_WARNING.to_string())
+    }
+}
+
+fn php_json_encode(value: &Value) -> String {
+    let mut out = String::new();
+    encode_into(value, &mut out);
+    out
+}
+
+fn encode_into(value: &Value, out: &mut String) {
+    match value {
+        Value::Null => out.push_str("null"),
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::Number(n) => out.push_str(&n.to_string()),
+        Value::String(s) => encode_string(s, out),
+        Value::Array(items) => {
+            out.push('[');
+            for (i, item) in items.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                encode_into(item, out);
+            }
+            out.push(']');
+        }
+        // PHP decodes `{}` into an empty array, which encodes back as `[]`.
+        Value::Object(map) if map.is_empty() => out.push_str("[]"),
+        Value::Object(map) => {
+            out.push('{');
+            for (i, (key, item)) in map.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                encode_string(key, out);
+                out.push(':');
+                encode_into(item, out);
+            }
+            out.push('}');
+        }
+    }
+}
+
+/// PHP escapes slashes and every non-ASCII character unless told otherwise.
+fn encode_string(s: &str, out: &mut String) {
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '/' => out.push_str("\\/"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_ascii() && !c.is_ascii_control() => out.push(c),
+            c => {
+                let mut units = [0u16; 2];
+                for unit in c.encode_utf16(&mut units) {
+                    out.push_str(&format!("\\u{:04x}", unit));
+                }
+            }
+        }
+    }
+    out.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MANIFEST: &str = r#"{
+        "name": "test/app",
+        "description": "Not part of the hash",
+        "require": {
+            "php": "^8.1",
+            "monolog/monolog": "^2.0"
+        },
+        "require-dev": {}
+    }"#;
+
+    fn manifest() -> Value {
+        serde_json::from_str(MANIFEST).unwrap()
+    }
+
+    #[test]
+    fn test_content_hash_matches_composer() {
+        assert_eq!(content_hash(&manifest()), "49b72250f9f2e1331774773f0be31a1c");
+    }
+
+    #[test]
+    fn test_matching_lock_has_no_drift() {
+        let lock = serde_json::json!({ "content-hash": "49b72250f9f2e1331774773f0be31a1c" });
+        assert_eq!(check_drift(&manifest(), &lock), None);
+    }
+
+    #[test]
+    fn test_mismatched_content_hash_is_drift() {
+        let lock = serde_json::json!({ "content-hash": "0123456789abcdef0123456789abcdef" });
+        assert_eq!(check_drift(&manifest(), &lock), Some(DRIFT_WARNING.to_string()));
+    }
+
+    #[test]
+    fn test_lock_without_hash_is_not_checked() {
+        let lock = serde_json::json!({ "packages": [] });
+        assert_eq!(check_drift(&manifest(), &lock), None);
+    }
+
+    #[test]
+    fn test_php_string_escaping() {
+        assert_eq!(php_json_encode(&Value::String("a/b \"é\"".to_string())), r#""a\/b \"\u00e9\"""#);
+    }
+}
@@ -1,5 +1,5 @@
 use rensa_core::plugin::VulnerabilityScanner;
-use rensa_core::types::{Dependency, Ecosystem, Vulnerability};
+use rensa_core::types::{Dependency, Ecosystem, LookupTrace, Vulnerability};
 use rensa_core::osv::{OsvClient as OsvClientTrait, OsvQuery, OsvPackage};
 use rensa_core::Result;
 use async_trait::async_trait;
@@ -11,7 +11,7 @@ pub struct OsvScanner {
 impl OsvScanner {
     pub fn new() -> Self {
         Self {
-            client: OsvClientTrait::new("https://api.osv.dev"),
+            client: OsvClientTrait::new(rensa_core::osv::DEFAULT_OSV_API),
         }
     }
 
@@ -20,6 +20,49 @@ impl OsvScanner {
             client: self.client.with_cache(cache),
         }
     }
+
+    pub fn with_base_url(self, base_url: String) -> Self {
+        Self {
+            client: self.client.with_base_url(base_url),
+        }
+    }
+
+    pub fn with_timeout(self, timeout: std::time::Duration) -> Self {
+        Self {
+            client: self.client.with_timeout(timeout),
+        }
+    }
+
+    /// Also reports advisories listed as `related` by the ones found.
+    pub fn with_include_related(self, include: bool) -> Self {
+        Self {
+            client: self.client.with_related(include),
+        }
+    }
+
+    /// Drops advisories rated below `floor` before they are hydrated or
+    /// reported.
+    pub fn with_severity_floor(self, floor: rensa_core::SeverityThreshold) -> Self {
+        Self {
+            client: self.client.with_severity_floor(floor),
+        }
+    }
+
+    pub fn timeout(&self) -> std::time::Duration {
+        self.client.timeout()
+    }
+
+    /// Sizes the OSV connection pools and advisory fan-out for `concurrency`
+    /// requests in flight.
+    pub fn with_concurrency(self, concurrency: usize) -> Self {
+        Self {
+            client: self.client.with_concurrency(concurrency),
+        }
+    }
+
+    pub fn concurrency(&self) -> usize {
+        self.client.concurrency()
+    }
 }
 
 #[async_trait]
@@ -28,18 +71,30 @@ impl VulnerabilityScanner for OsvScanner {
         Ecosystem::Composer
     }
 
+    fn trace(&self, dependency: &Dependency) -> Option<LookupTrace> {
+        Some(self.client.trace(&query(dependency)))
+    }
+
+    fn options_fingerprint(&self) -> String {
+        self.client.fingerprint()
+    }
+
+    async fn prefetch(&self, dependencies: &[Dependency]) -> Result<()> {
+        let queries: Vec<OsvQuery> = dependencies.iter().map(query).collect();
+        self.client.prefetch(&queries).await
+    }
+
     async fn scan(&self, dependency: &Dependency) -> Result<Vec<Vulnerability>> {
-        let query = OsvQuery {
-            package: OsvPackage {
-                name: dependency.name.clone(),
-                ecosystem: "Packagist".to_string(),
-            },
-            version: dependency.version.clone(),
-        };
-
-        let osv_vulns = self.client.query(&query).await?;
-        let vulnerabilities: Vec<Vulnerability> = osv_vulns.into_iter().map(|v| v.to_vulnerability()).collect();
-
-        Ok(vulnerabilities)
+        self.client.vulnerabilities(&query(dependency)).await
+    }
+}
+
+fn query(dependency: &Dependency) -> OsvQuery {
+    OsvQuery::Package {
+        package: OsvPackage {
+            name: dependency.name.clone(),
+            ecosystem: "Packagist".to_string(),
+        },
+        version: dependency.version.clone(),
     }
 }
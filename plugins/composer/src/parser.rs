@@ -1,13 +1,42 @@
 use rensa_core::plugin::Parser;
-use rensa_core::types::{Dependency, DependencyFile, Ecosystem, VersionConstraint};
-use rensa_core::Result;
+use rensa_core::types::{Dependency, DependencyFile, DependencyScope, Ecosystem, VersionConstraint};
+use rensa_core::manifest::{read_manifest, DEFAULT_MAX_FILE_SIZE};
+use rensa_core::report::ProjectInfo;
+use rensa_core::{Result, VersionComparator};
 use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
 
-pub struct ComposerParser;
+/// Sections parsed unless configured otherwise.
+pub const DEFAULT_SECTIONS: &[&str] = &["require", "require-dev"];
+
+pub struct ComposerParser {
+    transitive_depth: usize,
+    sections: Vec<String>,
+}
 
 impl ComposerParser {
     pub fn new() -> Self {
-        Self
+        Self {
+            transitive_depth: 0,
+            sections: DEFAULT_SECTIONS.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    /// Also scans locked packages up to `depth` levels below the direct
+    /// requirements, following each composer.lock package's `require`.
+    /// 0, the default, scans direct requirements only.
+    pub fn with_transitive_depth(mut self, depth: usize) -> Self {
+        self.transitive_depth = depth;
+        self
+    }
+
+    /// The composer.json sections read for requirements, e.g. only
+    /// `require`, or a plugin's custom section. `require-dev` entries are
+    /// dev dependencies; every other section's are runtime ones.
+    pub fn with_sections(mut self, sections: Vec<String>) -> Self {
+        self.sections = sections;
+        self
     }
 }
 
@@ -18,62 +47,376 @@ impl Parser for ComposerParser {
     }
 
     async fn parse(&self, file: &DependencyFile) -> Result<Vec<Dependency>> {
-        let json: Value = serde_json::from_str(&file.content).map_err(|e| rensa_core::RensaError::ParseError {
+        let json: Value = parse_json(&file.content).map_err(|e| rensa_core::RensaError::ParseError {
             file: file.path.clone(),
             source: e,
         })?;
 
-        let mut dependencies = Vec::new();
+        let mut dependencies = requirements(&json, &file.path, &self.sections)?;
 
-        // Parse require section
-        if let Some(require) = json.get("require").and_then(|r| r.as_object()) {
-            for (name, version) in require {
-                // Skip PHP version requirements
-                if name == "php" {
-                    continue;
+        // Requirements merged in by wikimedia/composer-merge-plugin. Like the
+        // plugin, the root file's own constraints win over included ones.
+        for (path, included) in merge_plugin_includes(&json, &file.path) {
+            for dependency in requirements(&included, &path, &self.sections)? {
+                if !dependencies.iter().any(|d| d.name == dependency.name && d.scope == dependency.scope) {
+                    dependencies.push(dependency);
                 }
+            }
+        }
 
-                dependencies.push(Dependency {
+        if self.transitive_depth > 0 {
+            let lock_path = file.path.with_file_name("composer.lock");
+            let lock = std::fs::read_to_string(&lock_path)
+                .ok()
+                .and_then(|content| parse_json(&content).ok());
+            if let Some(lock) = lock {
+                let direct: Vec<&str> = dependencies.iter().map(|d| d.name.as_str()).collect();
+                let transitive = transitive_dependencies(&direct, &lock, self.transitive_depth, &lock_path);
+                dependencies.extend(transitive);
+            }
+        }
+
+        Ok(dependencies)
+    }
+
+    fn options_fingerprint(&self) -> String {
+        format!("transitive_depth={};sections={}", self.transitive_depth, self.sections.join(","))
+    }
+
+    fn includes_dev(&self) -> bool {
+        self.sections.iter().any(|s| s == "require-dev")
+    }
+
+    fn warnings(&self, file: &DependencyFile) -> Vec<String> {
+        let lock_path = file.path.with_file_name("composer.lock");
+        let lock: Value = match std::fs::read_to_string(&lock_path)
+            .ok()
+            .and_then(|content| parse_json(&content).ok())
+        {
+            Some(lock) => lock,
+            None => return Vec::new(),
+        };
+
+        let Ok(manifest) = parse_json(&file.content) else {
+            return Vec::new();
+        };
+        crate::lock::check_drift(&manifest, &lock)
+            .into_iter()
+            .chain(locked_violations(&manifest, &lock, &self.sections))
+            .map(|warning| format!("{}: {}", lock_path.display(), warning))
+            .collect()
+    }
+
+    /// One entry per alternative of each `conflict` range, so `1.0.*|2.0.*`
+    /// is checked as two constraints.
+    fn conflicts(&self, file: &DependencyFile) -> Vec<Dependency> {
+        let json: Value = match parse_json(&file.content) {
+            Ok(json) => json,
+            Err(_) => return Vec::new(),
+        };
+        let Some(conflicts) = json.get("conflict").and_then(|c| c.as_object()) else {
+            return Vec::new();
+        };
+
+        conflicts
+            .iter()
+            .filter_map(|(name, range)| Some((name, range.as_str()?)))
+            .flat_map(|(name, range)| {
+                range.split('|').map(str::trim).filter(|r| !r.is_empty()).map(move |alternative| Dependency {
+                    ecosystem: Ecosystem::Composer,
+                    scope: DependencyScope::Runtime,
                     name: name.to_string(),
-                    version: version.as_str()
-                        .ok_or_else(|| rensa_core::RensaError::ParseError {
-                            file: file.path.clone(),
-                            source: serde_json::Error::io(std::io::Error::new(std::io::ErrorKind::Other, "Invalid version format")),
-                        })?
-                        .to_string(),
-                    constraint: parse_constraint(version),
+                    version: range.to_string(),
+                    constraint: parse_constraint(&Value::String(alternative.to_string())),
                     file: file.path.clone(),
-                });
+                    repository: None,
+                    license: None,
+                    provenance: None,
+                    direct: true,
+                    trace: None,
+                })
+            })
+            .collect()
+    }
+
+    fn project(&self, file: &DependencyFile) -> Option<ProjectInfo> {
+        let json: Value = parse_json(&file.content).ok()?;
+        let field = |key: &str| json.get(key).and_then(Value::as_str).map(str::to_string);
+
+        Some(ProjectInfo {
+            name: field("name")?,
+            version: field("version"),
+            description: field("description"),
+        })
+    }
+
+    fn runtime_requirements(&self, file: &DependencyFile) -> Result<Vec<Dependency>> {
+        let json: Value = parse_json(&file.content).map_err(|e| rensa_core::RensaError::ParseError {
+            file: file.path.clone(),
+            source: e,
+        })?;
+
+        let php = json
+            .get("require")
+            .and_then(|r| r.get("php"))
+            .and_then(|v| v.as_str().map(|s| (v, s)));
+
+        Ok(php
+            .map(|(value, version)| Dependency {
+                ecosystem: Ecosystem::Composer,
+                scope: DependencyScope::Runtime,
+                name: "php".to_string(),
+                version: version.to_string(),
+                constraint: parse_constraint(value),
+                file: file.path.clone(),
+                repository: None,
+                license: None,
+                provenance: None,
+                direct: true,
+                trace: None,
+            })
+            .into_iter()
+            .collect())
+    }
+}
+
+/// Parses composer JSON, tolerating the UTF-8 byte order mark some
+/// Windows editors write at the start of the file.
+fn parse_json(content: &str) -> serde_json::Result<Value> {
+    serde_json::from_str(content.trim_start_matches('\u{feff}').trim_start())
+}
+
+/// The requirements listed in `sections` of a composer.json, minus the
+/// `php` platform requirement.
+fn requirements(json: &Value, path: &Path, sections: &[String]) -> Result<Vec<Dependency>> {
+    let mut dependencies = Vec::new();
+
+    for section in sections {
+        let Some(packages) = json.get(section).and_then(|r| r.as_object()) else {
+            continue;
+        };
+        let scope = match section.as_str() {
+            "require-dev" => DependencyScope::Development,
+            _ => DependencyScope::Runtime,
+        };
+
+        for (name, version) in packages {
+            if name == "php" {
+                continue;
             }
+
+            dependencies.push(Dependency {
+                ecosystem: Ecosystem::Composer,
+                scope,
+                name: name.to_string(),
+                version: version
+                    .as_str()
+                    .ok_or_else(|| rensa_core::RensaError::ParseError {
+                        file: path.to_path_buf(),
+                        source: serde_json::Error::io(std::io::Error::other("Invalid version format")),
+                    })?
+                    .to_string(),
+                constraint: parse_constraint(version),
+                file: path.to_path_buf(),
+                repository: None,
+                license: None,
+                provenance: None,
+                direct: true,
+                trace: None,
+            });
         }
+    }
+
+    Ok(dependencies)
+}
 
-        // Parse require-dev section (could filter based on config)
-        if let Some(require_dev) = json.get("require-dev").and_then(|r| r.as_object()) {
-            for (name, version) in require_dev {
-                if name == "php" {
+/// Locked packages reachable from `direct` in at most `depth` steps along
+/// the lock's per-package `require`, each at its locked version. Platform
+/// requirements like `php` and `ext-json` have no `/` and are skipped.
+fn transitive_dependencies(direct: &[&str], lock: &Value, depth: usize, lock_path: &Path) -> Vec<Dependency> {
+    let mut locked = HashMap::new();
+    for (section, scope) in [("packages", DependencyScope::Runtime), ("packages-dev", DependencyScope::Development)] {
+        for package in lock.get(section).and_then(|p| p.as_array()).into_iter().flatten() {
+            if let Some(name) = package.get("name").and_then(|n| n.as_str()) {
+                locked.insert(name.to_lowercase(), (package, scope));
+            }
+        }
+    }
+
+    let mut seen: HashSet<String> = direct.iter().map(|name| name.to_lowercase()).collect();
+    let mut frontier: Vec<String> = seen.iter().cloned().collect();
+    frontier.sort();
+    let mut dependencies = Vec::new();
+
+    for _ in 0..depth {
+        let mut next = Vec::new();
+        for name in &frontier {
+            let Some((package, _)) = locked.get(name) else {
+                continue;
+            };
+            let Some(requires) = package.get("require").and_then(|r| r.as_object()) else {
+                continue;
+            };
+
+            for required in requires.keys().map(|r| r.to_lowercase()) {
+                if !required.contains('/') || !seen.insert(required.clone()) {
                     continue;
                 }
+                let Some((package, scope)) = locked.get(&required) else {
+                    continue;
+                };
+                let version = package
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or_default()
+                    .trim_start_matches('v')
+                    .to_string();
 
                 dependencies.push(Dependency {
-                    name: name.to_string(),
-                    version: version.as_str()
-                        .ok_or_else(|| rensa_core::RensaError::ParseError {
-                            file: file.path.clone(),
-                            source: serde_json::Error::io(std::io::Error::new(std::io::ErrorKind::Other, "Invalid version format")),
-                        })?
-                        .to_string(),
-                    constraint: parse_constraint(version),
-                    file: file.path.clone(),
+                    ecosystem: Ecosystem::Composer,
+                    scope: *scope,
+                    name: required.clone(),
+                    constraint: VersionConstraint::Exact(version.clone()),
+                    version,
+                    file: lock_path.to_path_buf(),
+                    repository: None,
+                    license: None,
+                    provenance: None,
+                    direct: false,
+                    trace: None,
                 });
+                next.push(required);
             }
         }
+        frontier = next;
+    }
 
-        Ok(dependencies)
+    dependencies
+}
+
+/// A warning for each requirement in `sections` whose locked version the
+/// manifest's constraint no longer allows, as after editing composer.json
+/// without updating the lock. Branches and versions that can't be compared
+/// are skipped.
+fn locked_violations(manifest: &Value, lock: &Value, sections: &[String]) -> Vec<String> {
+    let mut locked = HashMap::new();
+    for section in ["packages", "packages-dev"] {
+        for package in lock.get(section).and_then(|p| p.as_array()).into_iter().flatten() {
+            if let (Some(name), Some(version)) = (
+                package.get("name").and_then(|n| n.as_str()),
+                package.get("version").and_then(|v| v.as_str()),
+            ) {
+                locked.insert(name.to_lowercase(), version);
+            }
+        }
+    }
+
+    let mut warnings = Vec::new();
+    for section in sections {
+        let Some(packages) = manifest.get(section).and_then(|r| r.as_object()) else {
+            continue;
+        };
+        for (name, raw) in packages {
+            let (Some(raw), Some(version)) = (raw.as_str(), locked.get(&name.to_lowercase())) else {
+                continue;
+            };
+            let Some(parsed) = VersionComparator::parse_lenient(version) else {
+                continue;
+            };
+            if !allows(raw, &parsed.to_string()) {
+                warnings.push(format!(
+                    "locked version {} of {} does not satisfy constraint {} in composer.json",
+                    version, name, raw
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+/// Whether any `||` alternative of a composer constraint accepts `version`.
+/// Stability flags like `@dev` don't change which versions match.
+fn allows(raw: &str, version: &str) -> bool {
+    raw.split('|').map(str::trim).filter(|a| !a.is_empty()).any(|alternative| {
+        let alternative = alternative.split('@').next().unwrap_or_default().trim();
+        let constraint = match VersionConstraint::parse_hyphen_range(alternative) {
+            Some(range) => range,
+            None if alternative.contains([' ', ',']) => parse_exclusions(alternative),
+            None if alternative.is_empty() => return true,
+            None => parse_constraint(&Value::String(alternative.to_string())),
+        };
+        VersionComparator::satisfies(&constraint, version)
+    })
+}
+
+/// Files matched by `extra.merge-plugin.include`, resolved relative to the
+/// including file and followed recursively, as the plugin does by default.
+/// Unreadable or invalid includes are skipped with a warning.
+fn merge_plugin_includes(json: &Value, path: &Path) -> Vec<(PathBuf, Value)> {
+    let mut seen = HashSet::from([path.to_path_buf()]);
+    let mut pending = vec![(path.to_path_buf(), json.clone())];
+    let mut included = Vec::new();
+
+    while let Some((path, json)) = pending.pop() {
+        let dir = path.parent().unwrap_or_else(|| Path::new(""));
+        for include in include_paths(&json, dir) {
+            if !seen.insert(include.clone()) {
+                continue;
+            }
+
+            let content = match read_manifest(&include, DEFAULT_MAX_FILE_SIZE) {
+                Ok(content) => content,
+                Err(e) => {
+                    eprintln!("Warning: Skipping merge-plugin include {}: {}", include.display(), e);
+                    continue;
+                }
+            };
+            match parse_json(&content) {
+                Ok(json) => {
+                    pending.push((include.clone(), json.clone()));
+                    included.push((include, json));
+                }
+                Err(e) => eprintln!("Warning: Skipping merge-plugin include {}: {}", include.display(), e),
+            }
+        }
     }
+
+    included
+}
+
+/// Expands the `include` globs, which may be a single string or a list.
+fn include_paths(json: &Value, dir: &Path) -> Vec<PathBuf> {
+    let patterns: Vec<&str> = match json.pointer("/extra/merge-plugin/include") {
+        Some(Value::String(pattern)) => vec![pattern.as_str()],
+        Some(Value::Array(patterns)) => patterns.iter().filter_map(Value::as_str).collect(),
+        _ => return Vec::new(),
+    };
+
+    patterns
+        .into_iter()
+        .filter_map(|pattern| glob::glob(&dir.join(pattern).to_string_lossy()).ok())
+        .flat_map(|paths| {
+            let mut paths: Vec<PathBuf> = paths.filter_map(|p| p.ok()).collect();
+            paths.sort();
+            paths
+        })
+        .collect()
 }
 
 fn parse_constraint(version: &Value) -> VersionConstraint {
-    let version_str = version.as_str().unwrap_or("*").to_string();
+    let raw = version.as_str().unwrap_or("*");
+    if let Some(range) = VersionConstraint::parse_hyphen_range(raw) {
+        return range;
+    }
+    if raw.contains("!=") && !raw.contains("||") {
+        return parse_exclusions(raw);
+    }
+
+    let version_str = normalize_constraint(raw);
+
+    if let Some(range) = expand_wildcard(&version_str) {
+        return VersionConstraint::Range(range);
+    }
 
     if version_str.starts_with('^') {
         VersionConstraint::Caret(version_str.trim_start_matches('^').to_string())
@@ -88,9 +431,76 @@ fn parse_constraint(version: &Value) -> VersionConstraint {
     }
 }
 
+/// A compound constraint with exclusions, `>=1.0 <2.0 !=1.5.0`. Its parts
+/// are separated by spaces or commas and must all hold.
+fn parse_exclusions(raw: &str) -> VersionConstraint {
+    let parts = raw.split([' ', ',']).filter(|p| !p.is_empty()).map(|part| match part.strip_prefix("!=") {
+        Some(excluded) => {
+            let excluded = normalize_constraint(excluded);
+            VersionConstraint::Not(Box::new(VersionConstraint::Exact(pad_version(&excluded))))
+        }
+        None => parse_constraint(&Value::String(part.to_string())),
+    });
+    VersionConstraint::And(parts.collect())
+}
+
+/// Pads a version to three parts so it compares equal to the registry's
+/// releases: `1.5` is `1.5.0`.
+fn pad_version(version: &str) -> String {
+    match version.split('.').count() {
+        1 => format!("{}.0.0", version),
+        2 => format!("{}.0", version),
+        _ => version.to_string(),
+    }
+}
+
+/// Strips the `v` prefix composer allows on versions (`v2.1`, `^v1.0`),
+/// keeping any leading operator.
+fn normalize_constraint(raw: &str) -> String {
+    let raw = raw.trim();
+    let op_len = raw
+        .find(|c: char| !matches!(c, '^' | '~' | '>' | '<' | '=' | '!'))
+        .unwrap_or(raw.len());
+    let (op, version) = raw.split_at(op_len);
+    let version = version.trim_start();
+
+    let mut chars = version.chars();
+    let version = match (chars.next(), chars.next()) {
+        (Some('v' | 'V'), Some(c)) if c.is_ascii_digit() => &version[1..],
+        _ => version,
+    };
+
+    format!("{}{}", op, version)
+}
+
+/// Expands composer wildcards into explicit ranges:
+/// `1.x`/`1.*` -> `>=1.0.0, <2.0.0` and `1.0.x`/`1.0.*` -> `>=1.0.0, <1.1.0`.
+fn expand_wildcard(version: &str) -> Option<String> {
+    let base = version
+        .strip_suffix(".x")
+        .or_else(|| version.strip_suffix(".X"))
+        .or_else(|| version.strip_suffix(".*"))?;
+
+    let parts: Vec<u64> = base
+        .split('.')
+        .map(|p| p.parse().ok())
+        .collect::<Option<_>>()?;
+
+    match parts.as_slice() {
+        [major] => Some(format!(">={}.0.0, <{}.0.0", major, major + 1)),
+        [major, minor] => Some(format!(">={}.{}.0, <{}.{}.0", major, minor, major, minor + 1)),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rensa_core::VersionComparator;
+
+    fn constraint(raw: &str) -> VersionConstraint {
+        parse_constraint(&Value::String(raw.to_string()))
+    }
 
     #[tokio::test]
     async fn test_parse_simple_composer_json() {
@@ -112,6 +522,22 @@ mod tests {
         assert_eq!(deps.len(), 1);
         assert_eq!(deps[0].name, "guzzlehttp/guzzle");
         assert_eq!(deps[0].version, "^7.0");
+        assert_eq!(deps[0].scope.group(), "runtime");
+    }
+
+    #[tokio::test]
+    async fn test_parse_manifest_with_byte_order_mark() {
+        let file = DependencyFile {
+            ecosystem: Ecosystem::Composer,
+            path: std::path::PathBuf::from("composer.json"),
+            content: "\u{feff}\r\n  {\"name\": \"test/package\", \"require\": {\"monolog/monolog\": \"^3.0\"}}".to_string(),
+        };
+
+        let parser = ComposerParser::new();
+        let deps = parser.parse(&file).await.unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "monolog/monolog");
+        assert_eq!(parser.project(&file).unwrap().name, "test/package");
     }
 
     #[tokio::test]
@@ -134,5 +560,325 @@ mod tests {
 
         assert_eq!(deps.len(), 1);
         assert_eq!(deps[0].name, "phpstan/phpstan");
+        assert_eq!(deps[0].scope.group(), "dev");
+    }
+
+    #[tokio::test]
+    async fn test_sections_outside_the_configured_list_are_ignored() {
+        let file = DependencyFile {
+            ecosystem: Ecosystem::Composer,
+            path: std::path::PathBuf::from("composer.json"),
+            content: r#"{
+  "require": {
+    "monolog/monolog": "^3.0"
+  },
+  "require-dev": {
+    "phpstan/phpstan": "^1.0"
+  },
+  "require-plugin": {
+    "acme/plugin": "^2.0"
+  }
+}"#.to_string(),
+        };
+
+        let deps = ComposerParser::new().with_sections(vec!["require".to_string()]).parse(&file).await.unwrap();
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["monolog/monolog"]);
+
+        let deps = ComposerParser::new()
+            .with_sections(vec!["require".to_string(), "require-plugin".to_string()])
+            .parse(&file)
+            .await
+            .unwrap();
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(names, vec!["monolog/monolog", "acme/plugin"]);
+        assert_eq!(deps[1].scope.group(), "runtime");
+    }
+
+    #[test]
+    fn test_dev_dependencies_are_only_included_with_require_dev() {
+        assert!(ComposerParser::new().includes_dev());
+        assert!(!ComposerParser::new().with_sections(vec!["require".to_string()]).includes_dev());
+    }
+
+    #[tokio::test]
+    async fn test_package_in_both_sections_keeps_both_constraints() {
+        let parser = ComposerParser::new();
+        let file = DependencyFile {
+            ecosystem: Ecosystem::Composer,
+            path: std::path::PathBuf::from("composer.json"),
+            content: r#"{
+  "require": {
+    "symfony/console": "^5.4"
+  },
+  "require-dev": {
+    "symfony/console": "^6.0"
+  }
+}"#.to_string(),
+        };
+
+        let deps = parser.parse(&file).await.unwrap();
+
+        assert_eq!(deps.len(), 2);
+        assert!(deps.iter().all(|d| d.name == "symfony/console"));
+        assert_eq!(deps[0].scope, DependencyScope::Runtime);
+        assert_eq!(deps[0].constraint, VersionConstraint::Caret("5.4".to_string()));
+        assert_eq!(deps[1].scope, DependencyScope::Development);
+        assert_eq!(deps[1].constraint, VersionConstraint::Caret("6.0".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_merge_plugin_includes_are_scanned() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let extensions = temp_dir.path().join("extensions");
+        std::fs::create_dir_all(extensions.join("Auth")).unwrap();
+        std::fs::create_dir_all(extensions.join("Search")).unwrap();
+        std::fs::write(
+            extensions.join("Auth").join("composer.json"),
+            r#"{"require": {"firebase/php-jwt": "^6.0", "monolog/monolog": "^1.0"}}"#,
+        )
+        .unwrap();
+        std::fs::write(
+            extensions.join("Search").join("composer.json"),
+            r#"{"require-dev": {"phpunit/phpunit": "^10.0"}}"#,
+        )
+        .unwrap();
+        std::fs::write(temp_dir.path().join("composer.local.json"), r#"{"require": {"psr/log": "^3.0"}}"#).unwrap();
+
+        let file = DependencyFile {
+            ecosystem: Ecosystem::Composer,
+            path: temp_dir.path().join("composer.json"),
+            content: r#"{
+  "require": {
+    "monolog/monolog": "^2.0",
+    "wikimedia/composer-merge-plugin": "^2.1"
+  },
+  "extra": {
+    "merge-plugin": {
+      "include": ["composer.local.json", "extensions/*/composer.json"]
+    }
+  }
+}"#.to_string(),
+        };
+
+        let deps = ComposerParser::new().parse(&file).await.unwrap();
+        let names: Vec<&str> = deps.iter().map(|d| d.name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["monolog/monolog", "wikimedia/composer-merge-plugin", "psr/log", "firebase/php-jwt", "phpunit/phpunit"]
+        );
+
+        // The root constraint wins over an included one.
+        assert_eq!(deps[0].version, "^2.0");
+        let jwt = deps.iter().find(|d| d.name == "firebase/php-jwt").unwrap();
+        assert_eq!(jwt.file, extensions.join("Auth").join("composer.json"));
+        let phpunit = deps.iter().find(|d| d.name == "phpunit/phpunit").unwrap();
+        assert_eq!(phpunit.scope, DependencyScope::Development);
+    }
+
+    #[test]
+    fn test_minor_wildcard_constraints() {
+        for raw in ["1.0.x", "1.0.*", "v1.0.x"] {
+            let c = constraint(raw);
+            assert!(VersionComparator::satisfies(&c, "1.0.0"), "{}", raw);
+            assert!(VersionComparator::satisfies(&c, "1.0.9"), "{}", raw);
+            assert!(!VersionComparator::satisfies(&c, "1.1.0"), "{}", raw);
+            assert!(!VersionComparator::satisfies(&c, "0.9.9"), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn test_major_wildcard_constraints() {
+        for raw in ["1.x", "1.*", "v1.*"] {
+            let c = constraint(raw);
+            assert!(VersionComparator::satisfies(&c, "1.0.0"), "{}", raw);
+            assert!(VersionComparator::satisfies(&c, "1.9.3"), "{}", raw);
+            assert!(!VersionComparator::satisfies(&c, "2.0.0"), "{}", raw);
+        }
+
+        let c = constraint("v2.*");
+        assert!(VersionComparator::satisfies(&c, "2.4.1"));
+        assert!(!VersionComparator::satisfies(&c, "3.0.0"));
+        assert!(!VersionComparator::satisfies(&c, "1.9.0"));
+    }
+
+    #[test]
+    fn test_v_prefix_is_stripped() {
+        assert_eq!(constraint("^v1.2"), VersionConstraint::Caret("1.2".to_string()));
+        assert_eq!(constraint("~v2.0"), VersionConstraint::Tilde("2.0".to_string()));
+        assert_eq!(constraint(">=v7.0"), VersionConstraint::GreaterThanEqual("7.0".to_string()));
+        assert!(VersionComparator::satisfies(&constraint("^v1.2"), "1.5.0"));
+    }
+
+    #[test]
+    fn test_exclusion_constraints() {
+        for raw in [">=1.0 <2.0 !=1.5.0", ">=1.0, <2.0, !=1.5", ">=1.0 <2.0 !=v1.5.0"] {
+            let c = constraint(raw);
+            assert!(VersionComparator::satisfies(&c, "1.4.0"), "{}", raw);
+            assert!(VersionComparator::satisfies(&c, "1.6.0"), "{}", raw);
+            assert!(!VersionComparator::satisfies(&c, "1.5.0"), "{}", raw);
+            assert!(!VersionComparator::satisfies(&c, "2.1.0"), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn test_hyphen_range_constraints() {
+        for raw in ["1.0.0 - 2.0.0", "v1.0.0 - v2.0.0", "1.0 - 2.0.0"] {
+            let c = constraint(raw);
+            assert!(VersionComparator::satisfies(&c, "1.0.0"), "{}", raw);
+            assert!(VersionComparator::satisfies(&c, "2.0.0"), "{}", raw);
+            assert!(!VersionComparator::satisfies(&c, "2.0.1"), "{}", raw);
+        }
+    }
+
+    #[test]
+    fn test_non_wildcard_constraints_unchanged() {
+        assert_eq!(constraint("^7.0"), VersionConstraint::Caret("7.0".to_string()));
+        assert_eq!(constraint("dev-main"), VersionConstraint::Range("dev-main".to_string()));
+        assert_eq!(constraint("*"), VersionConstraint::Range("*".to_string()));
+    }
+
+    #[tokio::test]
+    async fn test_transitive_depth_limits_lock_graph() {
+        // app -> a/a -> b/b -> c/c -> d/d
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            temp_dir.path().join("composer.lock"),
+            r#"{
+  "packages": [
+    {"name": "a/a", "version": "1.0.0", "require": {"php": ">=8.1", "b/b": "^2.0"}},
+    {"name": "b/b", "version": "v2.3.0", "require": {"c/c": "^3.0", "ext-json": "*"}},
+    {"name": "c/c", "version": "3.1.0", "require": {"d/d": "^4.0"}},
+    {"name": "d/d", "version": "4.0.0"}
+  ],
+  "packages-dev": []
+}"#,
+        )
+        .unwrap();
+        let file = DependencyFile {
+            ecosystem: Ecosystem::Composer,
+            path: temp_dir.path().join("composer.json"),
+            content: r#"{"require": {"a/a": "^1.0"}}"#.to_string(),
+        };
+        let names = |deps: &[Dependency]| deps.iter().map(|d| d.name.clone()).collect::<Vec<_>>();
+
+        let direct = ComposerParser::new().parse(&file).await.unwrap();
+        assert_eq!(names(&direct), vec!["a/a"]);
+
+        let one_level = ComposerParser::new().with_transitive_depth(1).parse(&file).await.unwrap();
+        assert_eq!(names(&one_level), vec!["a/a", "b/b"]);
+        assert_eq!(one_level[1].version, "2.3.0");
+        assert_eq!(one_level[1].file, temp_dir.path().join("composer.lock"));
+
+        let three_levels = ComposerParser::new().with_transitive_depth(3).parse(&file).await.unwrap();
+        assert_eq!(names(&three_levels), vec!["a/a", "b/b", "c/c", "d/d"]);
+    }
+
+    #[test]
+    fn test_conflict_section() {
+        let file = DependencyFile {
+            ecosystem: Ecosystem::Composer,
+            path: std::path::PathBuf::from("composer.json"),
+            content: r#"{"conflict": {"symfony/console": ">=6.0", "doctrine/dbal": "2.13.*|3.0.*"}}"#.to_string(),
+        };
+
+        let conflicts = ComposerParser::new().conflicts(&file);
+        assert_eq!(conflicts.len(), 3);
+
+        let console = conflicts.iter().find(|c| c.name == "symfony/console").unwrap();
+        assert!(VersionComparator::satisfies(&console.constraint, "6.1.0"));
+        assert!(!VersionComparator::satisfies(&console.constraint, "5.4.0"));
+
+        let dbal: Vec<_> = conflicts.iter().filter(|c| c.name == "doctrine/dbal").collect();
+        assert!(dbal.iter().any(|c| VersionComparator::satisfies(&c.constraint, "3.0.2")));
+        assert!(!dbal.iter().any(|c| VersionComparator::satisfies(&c.constraint, "3.1.0")));
+        assert_eq!(dbal[0].version, "2.13.*|3.0.*");
+    }
+
+    #[test]
+    fn test_project_identity() {
+        let file = |content: &str| DependencyFile {
+            ecosystem: Ecosystem::Composer,
+            path: std::path::PathBuf::from("composer.json"),
+            content: content.to_string(),
+        };
+
+        let project = ComposerParser::new()
+            .project(&file(r#"{"name": "test/package", "version": "1.2.0", "require": {}}"#))
+            .unwrap();
+        assert_eq!(project.name, "test/package");
+        assert_eq!(project.to_string(), "test/package 1.2.0");
+        assert_eq!(project.description, None);
+
+        // Applications usually don't name themselves.
+        assert_eq!(ComposerParser::new().project(&file(r#"{"require": {}}"#)), None);
+    }
+
+    #[test]
+    fn test_runtime_requirements_reports_php() {
+        let file = DependencyFile {
+            path: std::path::PathBuf::from("composer.json"),
+            ecosystem: Ecosystem::Composer,
+            content: r#"{"require": {"php": "^7.4", "monolog/monolog": "^2.0"}}"#.to_string(),
+        };
+
+        let runtimes = ComposerParser::new().runtime_requirements(&file).unwrap();
+        assert_eq!(runtimes.len(), 1);
+        assert_eq!(runtimes[0].name, "php");
+        assert_eq!(runtimes[0].version, "^7.4");
+    }
+
+    #[test]
+    fn test_warnings_report_lock_drift() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manifest = r#"{"require": {"monolog/monolog": "^2.0"}}"#;
+        std::fs::write(
+            temp_dir.path().join("composer.lock"),
+            r#"{"content-hash": "0123456789abcdef0123456789abcdef", "packages": []}"#,
+        )
+        .unwrap();
+
+        let file = DependencyFile {
+            ecosystem: Ecosystem::Composer,
+            path: temp_dir.path().join("composer.json"),
+            content: manifest.to_string(),
+        };
+
+        let warnings = ComposerParser::new().warnings(&file);
+        assert_eq!(warnings.len(), 1);
+        assert!(warnings[0].ends_with(crate::lock::DRIFT_WARNING));
+    }
+
+    #[test]
+    fn test_warnings_report_locked_version_outside_constraint() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let manifest = r#"{
+            "require": {"monolog/monolog": "^3.0", "psr/log": "^1.1 || ^2.0", "acme/fork": "dev-main"},
+            "require-dev": {"phpunit/phpunit": ">=9.5 <11"}
+        }"#;
+        std::fs::write(
+            temp_dir.path().join("composer.lock"),
+            r#"{
+                "packages": [
+                    {"name": "monolog/monolog", "version": "2.9.1"},
+                    {"name": "psr/log", "version": "2.0.0"},
+                    {"name": "acme/fork", "version": "dev-main"}
+                ],
+                "packages-dev": [{"name": "phpunit/phpunit", "version": "10.5.0"}]
+            }"#,
+        )
+        .unwrap();
+
+        let file = DependencyFile {
+            ecosystem: Ecosystem::Composer,
+            path: temp_dir.path().join("composer.json"),
+            content: manifest.to_string(),
+        };
+
+        let warnings = ComposerParser::new().warnings(&file);
+        assert_eq!(warnings, vec![format!(
+            "{}: locked version 2.9.1 of monolog/monolog does not satisfy constraint ^3.0 in composer.json",
+            temp_dir.path().join("composer.lock").display()
+        )]);
     }
 }
@@ -1,42 +1,156 @@
 use rensa_core::types::Ecosystem;
+use std::time::Duration;
 
 pub struct ComposerPlugin {
     cache: Option<rensa_core::CacheManager>,
+    registry: Option<rensa_core::config::RegistryConfig>,
+    registry_timeout: Option<Duration>,
+    osv_timeout: Option<Duration>,
+    osv_url: Option<String>,
+    max_file_size: Option<u64>,
+    concurrency: Option<usize>,
+    include_related: bool,
+    severity_floor: Option<rensa_core::SeverityThreshold>,
+    transitive_depth: usize,
+    sections: Option<Vec<String>>,
 }
 
 impl ComposerPlugin {
     pub fn new() -> Self {
-        Self { cache: None }
+        Self {
+            cache: None,
+            registry: None,
+            registry_timeout: None,
+            osv_timeout: None,
+            osv_url: None,
+            max_file_size: None,
+            concurrency: None,
+            include_related: false,
+            severity_floor: None,
+            transitive_depth: 0,
+            sections: None,
+        }
     }
 
     pub fn with_cache(cache: rensa_core::CacheManager) -> Self {
-        Self { cache: Some(cache) }
+        Self {
+            cache: Some(cache),
+            ..Self::new()
+        }
     }
 
-    pub fn create_detector(&self) -> Option<Box<dyn rensa_core::Detector>> {
-        Some(Box::new(super::detector::ComposerDetector::new()))
+    /// Queries the private registry described by `config` instead of the
+    /// public one.
+    pub fn with_registry_config(mut self, config: rensa_core::config::RegistryConfig) -> Self {
+        self.registry = Some(config);
+        self
     }
 
-    pub fn create_parser(&self) -> Option<Box<dyn rensa_core::Parser>> {
-        Some(Box::new(super::parser::ComposerParser::new()))
+    /// Overrides the HTTP timeout used for registry lookups.
+    pub fn with_registry_timeout(mut self, timeout: Duration) -> Self {
+        self.registry_timeout = Some(timeout);
+        self
     }
 
-    pub fn create_registry_client(&self) -> Option<Box<dyn rensa_core::RegistryClient>> {
-        let client = match &self.cache {
-            Some(cache) => super::registry::PackagistClient::new().with_cache(cache.clone()),
+    /// Overrides the HTTP timeout used for OSV queries.
+    pub fn with_osv_timeout(mut self, timeout: Duration) -> Self {
+        self.osv_timeout = Some(timeout);
+        self
+    }
+
+    /// Sends OSV queries to another OSV-compatible API, e.g. a mirror.
+    pub fn with_osv_url(mut self, url: String) -> Self {
+        self.osv_url = Some(url);
+        self
+    }
+
+    /// Also reports OSV advisories related to the ones found.
+    pub fn with_include_related(mut self, include: bool) -> Self {
+        self.include_related = include;
+        self
+    }
+
+    /// Scans locked packages up to `depth` levels below the direct
+    /// requirements.
+    pub fn with_transitive_depth(mut self, depth: usize) -> Self {
+        self.transitive_depth = depth;
+        self
+    }
+
+    /// Only parses these composer.json sections instead of `require` and
+    /// `require-dev`.
+    pub fn with_sections(mut self, sections: Vec<String>) -> Self {
+        self.sections = Some(sections);
+        self
+    }
+
+    /// Only reports OSV advisories rated at or above `floor`.
+    pub fn with_severity_floor(mut self, floor: rensa_core::SeverityThreshold) -> Self {
+        self.severity_floor = Some(floor);
+        self
+    }
+
+    /// Skips manifests larger than `bytes` during detection.
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Sizes connection pools and OSV advisory fan-out for `concurrency` requests in
+    /// flight at once.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    fn detector(&self) -> super::detector::ComposerDetector {
+        let detector = super::detector::ComposerDetector::new();
+        match self.max_file_size {
+            Some(bytes) => detector.with_max_file_size(bytes),
+            None => detector,
+        }
+    }
+
+    fn registry_client(&self) -> super::registry::PackagistClient {
+        let mut client = match &self.registry {
+            Some(config) => super::registry::PackagistClient::new()
+                .with_registry_config(config)
+                .unwrap_or_else(|e| {
+                    eprintln!("Warning: Ignoring the composer registry config: {}", e);
+                    super::registry::PackagistClient::new()
+                }),
             None => super::registry::PackagistClient::new(),
         };
-        Some(Box::new(client))
+        if let Some(cache) = &self.cache {
+            client = client.with_cache(cache.clone());
+        }
+        if let Some(timeout) = self.registry_timeout {
+            client = client.with_timeout(timeout);
+        }
+        if let Some(concurrency) = self.concurrency {
+            client = client.with_pool_max_idle_per_host(concurrency);
+        }
+        client
     }
 
-    pub fn create_vulnerability_scanner(
-        &self,
-    ) -> Option<Box<dyn rensa_core::VulnerabilityScanner>> {
-        let client = match &self.cache {
-            Some(cache) => super::osv::OsvScanner::new().with_cache(cache.clone()),
-            None => super::osv::OsvScanner::new(),
-        };
-        Some(Box::new(client))
+    fn osv_scanner(&self) -> super::osv::OsvScanner {
+        let mut scanner = super::osv::OsvScanner::new();
+        if let Some(url) = &self.osv_url {
+            scanner = scanner.with_base_url(url.clone());
+        }
+        if let Some(cache) = &self.cache {
+            scanner = scanner.with_cache(cache.clone());
+        }
+        if let Some(timeout) = self.osv_timeout {
+            scanner = scanner.with_timeout(timeout);
+        }
+        if let Some(floor) = self.severity_floor {
+            scanner = scanner.with_severity_floor(floor);
+        }
+        if let Some(concurrency) = self.concurrency {
+            scanner = scanner.with_concurrency(concurrency);
+        }
+        scanner.with_include_related(self.include_related)
     }
 }
 
@@ -48,4 +162,129 @@ impl rensa_core::Plugin for ComposerPlugin {
     fn ecosystem(&self) -> Ecosystem {
         Ecosystem::Composer
     }
+
+    fn create_detector(&self) -> Option<Box<dyn rensa_core::Detector>> {
+        Some(Box::new(self.detector()))
+    }
+
+    fn create_parser(&self) -> Option<Box<dyn rensa_core::Parser>> {
+        let mut parser = super::parser::ComposerParser::new().with_transitive_depth(self.transitive_depth);
+        if let Some(sections) = &self.sections {
+            parser = parser.with_sections(sections.clone());
+        }
+        Some(Box::new(parser))
+    }
+
+    fn create_registry_client(&self) -> Option<Box<dyn rensa_core::RegistryClient>> {
+        Some(Box::new(self.registry_client()))
+    }
+
+    fn create_vulnerability_scanner(
+        &self,
+    ) -> Option<Box<dyn rensa_core::VulnerabilityScanner>> {
+        Some(Box::new(self.osv_scanner()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry_and_osv_timeouts_are_independent() {
+        let plugin = ComposerPlugin::new()
+            .with_registry_timeout(Duration::from_secs(90))
+            .with_osv_timeout(Duration::from_secs(5));
+
+        assert_eq!(plugin.registry_client().timeout(), Duration::from_secs(90));
+        assert_eq!(plugin.osv_scanner().timeout(), Duration::from_secs(5));
+        assert_ne!(plugin.registry_client().timeout(), plugin.osv_scanner().timeout());
+    }
+
+    #[tokio::test]
+    async fn test_osv_queries_go_to_configured_url() {
+        use rensa_core::types::{Dependency, DependencyScope, VersionConstraint};
+        use rensa_core::VulnerabilityScanner;
+        use wiremock::matchers::{body_partial_json, method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let mirror = MockServer::start().await;
+        Mock::given(method("POST"))
+            .and(path("/v1/query"))
+            .and(body_partial_json(serde_json::json!({
+                "package": { "name": "monolog/monolog", "ecosystem": "Packagist" },
+                "version": "2.9.1"
+            })))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .expect(1)
+            .mount(&mirror)
+            .await;
+
+        let dependency = Dependency {
+            name: "monolog/monolog".to_string(),
+            ecosystem: Ecosystem::Composer,
+            scope: DependencyScope::Runtime,
+            version: "2.9.1".to_string(),
+            constraint: VersionConstraint::Exact("2.9.1".to_string()),
+            file: std::path::PathBuf::from("composer.json"),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        };
+        let scanner = ComposerPlugin::new().with_osv_url(format!("{}/", mirror.uri())).osv_scanner();
+        assert!(scanner.scan(&dependency).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_registry_lookups_go_to_configured_url() {
+        use rensa_core::types::{Dependency, DependencyScope, VersionConstraint};
+        use rensa_core::RegistryClient;
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let private = MockServer::start().await;
+        Mock::given(wiremock::matchers::any())
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({})))
+            .mount(&private)
+            .await;
+
+        let dependency = Dependency {
+            name: "acme/internal".to_string(),
+            ecosystem: Ecosystem::Composer,
+            scope: DependencyScope::Runtime,
+            version: "1.0.0".to_string(),
+            constraint: VersionConstraint::Exact("1.0.0".to_string()),
+            file: std::path::PathBuf::from("composer.json"),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        };
+        let config = rensa_core::config::RegistryConfig {
+            url: Some(format!("{}/", private.uri())),
+            ..Default::default()
+        };
+        let client = ComposerPlugin::new().with_registry_config(config).registry_client();
+        let _ = client.get_latest_version(&dependency).await;
+        assert!(!private.received_requests().await.unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_concurrency_sizes_pools_and_osv_fan_out() {
+        let plugin = ComposerPlugin::new().with_concurrency(12);
+        assert_eq!(plugin.registry_client().pool_max_idle_per_host(), 12);
+        assert_eq!(plugin.osv_scanner().concurrency(), 12);
+
+        let plugin = ComposerPlugin::new();
+        assert_eq!(plugin.registry_client().pool_max_idle_per_host(), 32);
+        assert_eq!(plugin.osv_scanner().concurrency(), 4);
+    }
+
+    #[test]
+    fn test_default_timeouts_match() {
+        let plugin = ComposerPlugin::new();
+        assert_eq!(plugin.registry_client().timeout(), plugin.osv_scanner().timeout());
+    }
 }
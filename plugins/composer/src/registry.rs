@@ -1,12 +1,25 @@
 use rensa_core::plugin::RegistryClient;
-use rensa_core::types::{Dependency, Ecosystem, UpdateInfo};
+use rensa_core::types::{Dependency, Ecosystem, LookupTrace, PackageInfo, UpdateInfo};
 use rensa_core::{VersionComparator, UpdateType};
-use rensa_core::Result;
-use semver::Version;
+use rensa_core::{RensaError, Result};
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
 
 pub struct PackagistClient {
     client: rensa_core::HttpClient,
     base_url: String,
+    repo_url: String,
+}
+
+/// What rensa needs from a package's metadata.
+struct Metadata {
+    /// Stable versions, newest first.
+    versions: Vec<String>,
+    repository: Option<String>,
+    /// Licenses of the newest release.
+    license: Option<Vec<String>>,
+    /// Each release's `time`, keyed by version.
+    released: HashMap<String, DateTime<Utc>>,
 }
 
 impl PackagistClient {
@@ -14,18 +27,216 @@ impl PackagistClient {
         Self {
             client: rensa_core::HttpClient::new(),
             base_url: "https://packagist.org".to_string(),
+            repo_url: "https://repo.packagist.org".to_string(),
         }
     }
 
+    /// Points both the p2 and legacy endpoints at `base_url`, as mirrors
+    /// serve them from one host.
     pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.repo_url = base_url.clone();
         self.base_url = base_url;
         self
     }
 
+    /// Points the client at a private registry's `url`, if set, and
+    /// applies its TLS settings.
+    pub fn with_registry_config(self, config: &rensa_core::config::RegistryConfig) -> Result<Self> {
+        let mut client = match &config.url {
+            Some(url) => self.with_base_url(url.trim_end_matches('/').to_string()),
+            None => self,
+        };
+        client.client = client.client.with_registry_config(config)?;
+        Ok(client)
+    }
+
     pub fn with_cache(mut self, cache: rensa_core::CacheManager) -> Self {
         self.client = rensa_core::HttpClient::with_cache(self.client, cache);
         self
     }
+
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.client = self.client.with_timeout(timeout);
+        self
+    }
+
+    pub fn timeout(&self) -> std::time::Duration {
+        self.client.timeout()
+    }
+
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.client = self.client.with_pool_max_idle_per_host(max);
+        self
+    }
+
+    pub fn pool_max_idle_per_host(&self) -> usize {
+        self.client.pool_max_idle_per_host()
+    }
+
+    /// Resolves versions from the p2 API, which serves small, cacheable
+    /// files. The legacy `/packages/{name}.json` endpoint is used when p2
+    /// doesn't know the package, and for the repository when no p2 version
+    /// has a source.
+    async fn fetch_metadata(&self, dependency: &Dependency) -> Result<Metadata> {
+        let url = self.p2_url(dependency);
+        let p2 = match self.client.get::<serde_json::Value>(&url).await {
+            Ok(data) => p2_metadata(&data, &dependency.name),
+            Err(e @ RensaError::RateLimited { .. }) => return Err(e),
+            Err(_) => None,
+        };
+
+        match p2 {
+            Some(metadata) if metadata.repository.is_some() => Ok(metadata),
+            Some(metadata) => Ok(Metadata {
+                repository: self.fetch_legacy(dependency).await.ok().and_then(|data| repository_url(&data)),
+                ..metadata
+            }),
+            None => {
+                let data = self.fetch_legacy(dependency).await?;
+                let versions = stable_versions(&data);
+                let releases = data.get("package").and_then(|p| p.get("versions")).and_then(|v| v.as_object());
+                Ok(Metadata {
+                    license: versions.first().and_then(|newest| {
+                        licenses(data.get("package")?.get("versions")?.get(newest.as_str())?)
+                    }),
+                    repository: repository_url(&data),
+                    released: release_times(releases.into_iter().flat_map(|r| r.values())),
+                    versions,
+                })
+            }
+        }
+    }
+
+    fn p2_url(&self, dependency: &Dependency) -> String {
+        format!("{}/p2/{}.json", self.repo_url, dependency.name)
+    }
+
+    async fn fetch_legacy(&self, dependency: &Dependency) -> Result<serde_json::Value> {
+        let url = format!("{}/packages/{}.json", self.base_url, dependency.name);
+        self.client.get(&url).await
+    }
+
+    async fn fetch_versions(&self, dependency: &Dependency) -> Result<Vec<String>> {
+        Ok(self.fetch_metadata(dependency).await?.versions)
+    }
+}
+
+/// Reads p2 metadata: `packages.{name}` is a list of releases, newest
+/// first. Minified responses only list the fields that changed from the
+/// previous release, so the first `source.url` applies until overridden.
+fn p2_metadata(data: &serde_json::Value, name: &str) -> Option<Metadata> {
+    let releases = data.get("packages")?.get(name)?.as_array()?;
+    let versions = sort_stable(releases.iter().filter_map(|r| r.get("version")?.as_str()));
+    let repository = releases
+        .iter()
+        .find_map(|r| r.get("source")?.get("url")?.as_str())
+        .map(|url| url.trim_end_matches(".git").to_string());
+    let license = releases.first().and_then(licenses);
+    let released = release_times(releases.iter());
+
+    Some(Metadata { versions, repository, license, released })
+}
+
+/// Each release's publication `time`, keyed by its `version`.
+fn release_times<'a>(releases: impl Iterator<Item = &'a serde_json::Value>) -> HashMap<String, DateTime<Utc>> {
+    releases
+        .filter_map(|r| {
+            let version = r.get("version")?.as_str()?;
+            let time = DateTime::parse_from_rfc3339(r.get("time")?.as_str()?).ok()?;
+            Some((version.to_string(), time.with_timezone(&Utc)))
+        })
+        .collect()
+}
+
+/// A release's `license` array, e.g. `["MIT"]`.
+fn licenses(release: &serde_json::Value) -> Option<Vec<String>> {
+    let licenses: Vec<String> = release
+        .get("license")?
+        .as_array()?
+        .iter()
+        .filter_map(|l| l.as_str().map(str::to_string))
+        .collect();
+    (!licenses.is_empty()).then_some(licenses)
+}
+
+/// The package's repository URL: the top-level `repository`, falling back
+/// to the `source.url` of its versions. A trailing `.git` is dropped so the
+/// URL opens in a browser.
+fn repository_url(data: &serde_json::Value) -> Option<String> {
+    let package = data.get("package")?;
+    let url = package
+        .get("repository")
+        .and_then(|r| r.as_str())
+        .filter(|r| !r.is_empty())
+        .or_else(|| {
+            package
+                .get("versions")?
+                .as_object()?
+                .values()
+                .find_map(|v| v.get("source")?.get("url")?.as_str())
+        })?;
+    Some(url.trim_end_matches(".git").to_string())
+}
+
+/// Classifies the newest stable version against the dependency's.
+fn update_info(dependency: &Dependency, versions: &[String]) -> Option<UpdateInfo> {
+    let latest_version = versions
+        .iter()
+        .find(|v| VersionComparator::parse_lenient(v).is_some())
+        .cloned()?;
+
+    // Clean version strings for comparison (remove 'v' prefix if present)
+    let current_clean = dependency.version.trim_start_matches('v');
+    let latest_clean = latest_version.trim_start_matches('v');
+
+    // Only return if there's an actual update (not None or Unknown)
+    match VersionComparator::classify_update(current_clean, latest_clean) {
+        UpdateType::None | UpdateType::Unknown => None,
+        _ => Some(UpdateInfo {
+            dependency: dependency.clone(),
+            current_version: dependency.version.clone(),
+            latest_in_constraint: VersionComparator::highest_satisfying(&dependency.constraint, versions),
+            versions_behind: None,
+            age_behind_days: None,
+            intermediate_releases: Vec::new(),
+            latest_version,
+            changelog: None,
+        }),
+    }
+}
+
+/// Returns the stable versions in legacy Packagist metadata, newest first.
+fn stable_versions(data: &serde_json::Value) -> Vec<String> {
+    match data.get("package")
+        .and_then(|p| p.get("versions"))
+        .and_then(|v| v.as_object()) {
+        Some(versions_obj) => sort_stable(versions_obj.keys().map(String::as_str)),
+        None => Vec::new(),
+    }
+}
+
+fn sort_stable<'a>(candidates: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut versions = Vec::new();
+
+    for version_key in candidates {
+        let v = version_key.to_lowercase();
+        // Filter out dev/alpha/beta/rc versions
+        if !v.contains("dev") && !v.contains("alpha") && !v.contains("beta") && !v.contains("rc") {
+            versions.push(version_key.to_string());
+        }
+    }
+
+    // Sort versions (newest first)
+    versions.sort_by(|a, b| {
+        let a_clean = a.trim_start_matches('v');
+        let b_clean = b.trim_start_matches('v');
+        match (VersionComparator::parse_lenient(a_clean), VersionComparator::parse_lenient(b_clean)) {
+            (Some(va), Some(vb)) => vb.cmp(&va), // Reverse for newest first
+            _ => b.cmp(a), // Fallback to string comparison
+        }
+    });
+
+    versions
 }
 
 #[async_trait::async_trait]
@@ -34,70 +245,74 @@ impl RegistryClient for PackagistClient {
         Ecosystem::Composer
     }
 
-    async fn get_latest_version(&self, dependency: &Dependency) -> Result<Option<String>> {
-        let url = format!("{}/packages/{}.json", self.base_url, dependency.name);
+    fn is_valid_name(&self, name: &str) -> bool {
+        is_valid_package_name(name)
+    }
 
-        let data: serde_json::Value = self.client.get(&url).await?;
+    fn endpoint(&self) -> Option<&str> {
+        Some(&self.base_url)
+    }
 
-        if let Some(versions) = data.get("package")
-            .and_then(|p| p.get("versions"))
-            .and_then(|v| v.as_object()) {
-            
-            let mut parsed_versions: Vec<(Version, String)> = versions
-                .keys()
-                .filter(|v| {
-                    let v = v.to_lowercase();
-                    !v.contains("dev") && !v.contains("alpha") && !v.contains("beta") && !v.contains("rc")
-                })
-                .filter_map(|v| {
-                    let clean = v.trim_start_matches('v');
-                    Version::parse(clean).ok().map(|parsed| (parsed, v.clone()))
-                })
-                .collect();
-            
-            parsed_versions.sort_by(|a, b| b.0.cmp(&a.0));
-            
-            if let Some((_, original)) = parsed_versions.first() {
-                return Ok(Some(original.clone()));
-            }
-        }
-        
-        Ok(None)
+    fn trace(&self, dependency: &Dependency) -> Option<LookupTrace> {
+        let url = self.p2_url(dependency);
+        let cached = self.client.is_cached(&url);
+        Some(LookupTrace::new(url).with_cached(cached))
+    }
+
+    async fn get_latest_version(&self, dependency: &Dependency) -> Result<Option<String>> {
+        let versions = self.fetch_versions(dependency).await?;
+
+        Ok(versions
+            .into_iter()
+            .find(|v| VersionComparator::parse_lenient(v).is_some()))
     }
 
     async fn get_update_info(&self, dependency: &Dependency) -> Result<Option<UpdateInfo>> {
-        let latest = self.get_latest_version(dependency).await?;
-        
-        if let Some(latest_version) = latest {
-            // Clean version strings for comparison (remove 'v' prefix if present)
-            let current_clean = dependency.version.trim_start_matches('v');
-            let latest_clean = latest_version.trim_start_matches('v');
-            
-            // Use VersionComparator to classify the update
-            let update_type = VersionComparator::classify_update(current_clean, latest_clean);
-            
-            // Only return if there's an actual update (not None or Unknown)
-            match update_type {
-                UpdateType::None | UpdateType::Unknown => {
-                    // No meaningful update
-                    Ok(None)
-                }
-                _ => {
-                    // Return update info with metadata
-                    Ok(Some(UpdateInfo {
-                        dependency: dependency.clone(),
-                        current_version: dependency.version.clone(),
-                        latest_version,
-                        changelog: None,
-                    }))
-                }
-            }
-        } else {
-            Ok(None)
-        }
+        let versions = self.fetch_versions(dependency).await?;
+        Ok(update_info(dependency, &versions))
+    }
+
+    async fn get_package_info(&self, dependency: &Dependency) -> Result<PackageInfo> {
+        let metadata = self.fetch_metadata(dependency).await?;
+        Ok(PackageInfo {
+            update: update_info(dependency, &metadata.versions),
+            repository: metadata.repository,
+            license: metadata.license,
+            provenance: None,
+            deprecated: None,
+            versions: metadata.versions,
+            released: metadata.released,
+        })
     }
 }
 
+/// Checks a name against composer's `vendor/package` rules: lowercase
+/// alphanumerics separated by single `.`, `_` or `-` (`--` is allowed).
+pub fn is_valid_package_name(name: &str) -> bool {
+    match name.split_once('/') {
+        Some((vendor, package)) => is_valid_name_part(vendor) && is_valid_name_part(package),
+        None => false,
+    }
+}
+
+fn is_valid_name_part(part: &str) -> bool {
+    let is_separator = |c: char| matches!(c, '.' | '_' | '-');
+
+    if part.is_empty()
+        || !part.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || is_separator(c))
+        || part.starts_with(is_separator)
+        || part.ends_with(is_separator)
+    {
+        return false;
+    }
+
+    // Separators may not repeat, except for a double dash.
+    !part
+        .as_bytes()
+        .windows(2)
+        .any(|w| is_separator(w[0] as char) && is_separator(w[1] as char) && w != b"--")
+}
+
 /// Extension trait for PackagistClient to provide additional functionality
 pub trait PackagistClientExt {
     /// Get all available versions for a dependency
@@ -116,36 +331,7 @@ pub struct UpdateCheck {
 
 impl PackagistClientExt for PackagistClient {
     async fn get_all_versions(&self, dependency: &Dependency) -> Result<Vec<String>> {
-        let url = format!("{}/packages/{}.json", self.base_url, dependency.name);
-
-        let data: serde_json::Value = self.client.get(&url).await?;
-
-        let mut versions = Vec::new();
-
-        if let Some(versions_obj) = data.get("package")
-            .and_then(|p| p.get("versions"))
-            .and_then(|v| v.as_object()) {
-            
-            for version_key in versions_obj.keys() {
-                let v = version_key.to_lowercase();
-                // Filter out dev/alpha/beta/rc versions
-                if !v.contains("dev") && !v.contains("alpha") && !v.contains("beta") && !v.contains("rc") {
-                    versions.push(version_key.clone());
-                }
-            }
-            
-            // Sort versions (newest first)
-            versions.sort_by(|a, b| {
-                let a_clean = a.trim_start_matches('v');
-                let b_clean = b.trim_start_matches('v');
-                match (Version::parse(a_clean), Version::parse(b_clean)) {
-                    (Ok(va), Ok(vb)) => vb.cmp(&va), // Reverse for newest first
-                    _ => b.cmp(a), // Fallback to string comparison
-                }
-            });
-        }
-        
-        Ok(versions)
+        self.fetch_versions(dependency).await
     }
 
     async fn check_update(&self, dependency: &Dependency) -> Result<Option<UpdateCheck>> {
@@ -167,3 +353,210 @@ impl PackagistClientExt for PackagistClient {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rensa_core::types::VersionConstraint;
+
+    #[test]
+    fn test_valid_package_names() {
+        for name in [
+            "monolog/monolog",
+            "symfony/http-foundation",
+            "doctrine/dbal",
+            "phpunit/php-code-coverage",
+            "vendor_1/package.name",
+            "acme/some--thing",
+        ] {
+            assert!(is_valid_package_name(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn test_invalid_package_names() {
+        for name in [
+            "",
+            "monolog",
+            "Monolog/Monolog",
+            "vendor/",
+            "/package",
+            "vendor/pack age",
+            "vendor/package/extra",
+            "vendor/-package",
+            "vendor/package.",
+            "vendor/a..b",
+            "../../etc/passwd",
+            "vendor/package?x=1",
+            "ext-json",
+        ] {
+            assert!(!is_valid_package_name(name), "{}", name);
+        }
+
+        assert!(!PackagistClient::new().is_valid_name("vendor/package#frag"));
+    }
+
+    fn versions() -> Vec<String> {
+        let data = serde_json::json!({
+            "package": {
+                "versions": {
+                    "1.2.0": {}, "1.2.9": {}, "1.3.0": {}, "1.9.4": {},
+                    "2.0.0": {}, "2.1.0-beta1": {}, "dev-main": {}
+                }
+            }
+        });
+        stable_versions(&data)
+    }
+
+    #[test]
+    fn test_stable_versions_sorted_newest_first() {
+        assert_eq!(versions(), vec!["2.0.0", "1.9.4", "1.3.0", "1.2.9", "1.2.0"]);
+    }
+
+    #[test]
+    fn test_latest_in_tilde_constraint() {
+        // ~1.2 only allows patch releases of 1.2
+        let constraint = VersionConstraint::Tilde("1.2".to_string());
+        assert_eq!(VersionComparator::highest_satisfying(&constraint, &versions()), Some("1.2.9".to_string()));
+    }
+
+    #[test]
+    fn test_latest_in_caret_constraint() {
+        // ^1.2 allows any 1.x release from 1.2.0
+        let constraint = VersionConstraint::Caret("1.2".to_string());
+        assert_eq!(VersionComparator::highest_satisfying(&constraint, &versions()), Some("1.9.4".to_string()));
+    }
+
+    #[test]
+    fn test_latest_in_constraint_none_satisfying() {
+        let constraint = VersionConstraint::Caret("3.0".to_string());
+        assert_eq!(VersionComparator::highest_satisfying(&constraint, &versions()), None);
+    }
+
+    #[test]
+    fn test_four_segment_versions() {
+        let data = serde_json::json!({
+            "package": {
+                "versions": {
+                    "v1.4.2.9": {}, "v1.4.2.10": {}, "v1.4.1.0": {}, "v1.5.0.0-RC1": {}, "dev-master": {}
+                }
+            }
+        });
+        let versions = stable_versions(&data);
+        assert_eq!(versions, vec!["v1.4.2.10", "v1.4.2.9", "v1.4.1.0"]);
+
+        let update = update_info(&dependency("phpoffice/phpexcel", "1.4.2.9"), &versions).unwrap();
+        assert_eq!(update.latest_version, "v1.4.2.10");
+        assert_eq!(
+            VersionComparator::classify_update(&update.current_version, &update.latest_version),
+            UpdateType::Patch
+        );
+        assert!(update_info(&dependency("phpoffice/phpexcel", "1.4.2.10"), &versions).is_none());
+    }
+
+    fn dependency(name: &str, version: &str) -> Dependency {
+        Dependency {
+            name: name.to_string(),
+            ecosystem: Ecosystem::Composer,
+            scope: rensa_core::DependencyScope::Runtime,
+            version: version.to_string(),
+            constraint: VersionConstraint::Caret(version.to_string()),
+            file: std::path::PathBuf::from("composer.json"),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_package_info_includes_repository() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/packages/monolog/monolog.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "package": {
+                    "name": "monolog/monolog",
+                    "repository": "https://github.com/Seldaek/monolog",
+                    "versions": { "3.5.0": { "license": ["MIT"] }, "2.9.2": {} }
+                }
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/packages/acme/private.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "package": { "versions": { "1.0.0": {} } }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = PackagistClient::new().with_base_url(server.uri());
+
+        let info = client.get_package_info(&dependency("monolog/monolog", "2.9.2")).await.unwrap();
+        assert_eq!(info.repository.as_deref(), Some("https://github.com/Seldaek/monolog"));
+        assert_eq!(info.license, Some(vec!["MIT".to_string()]));
+        assert_eq!(info.update.unwrap().latest_version, "3.5.0");
+
+        let info = client.get_package_info(&dependency("acme/private", "1.0.0")).await.unwrap();
+        assert_eq!(info.repository, None);
+        assert!(info.update.is_none());
+    }
+
+    /// Trimmed from `https://repo.packagist.org/p2/monolog/monolog.json`.
+    const P2_MONOLOG: &str = r#"{"packages":{"monolog/monolog":[{"name":"monolog/monolog","version":"3.5.0","version_normalized":"3.5.0.0","time":"2023-10-27T15:32:31+00:00","license":["MIT"],"source":{"url":"https://github.com/Seldaek/monolog.git","type":"git","reference":"c915e2634718dbc8a4a15c61b0e62e7a44e14448"},"dist":{"url":"https://api.github.com/repos/Seldaek/monolog/zipball/c915e2634718dbc8a4a15c61b0e62e7a44e14448","type":"zip"}},{"version":"3.5.0-RC1","version_normalized":"3.5.0.0-RC1"},{"version":"3.4.0","version_normalized":"3.4.0.0","time":"2023-06-21T08:46:11+00:00"},{"version":"2.9.2","version_normalized":"2.9.2.0","time":"2023-10-27T15:25:26+00:00"}]},"minified":"composer/2.0"}"#;
+
+    #[test]
+    fn test_p2_metadata() {
+        let data: serde_json::Value = serde_json::from_str(P2_MONOLOG).unwrap();
+        let metadata = p2_metadata(&data, "monolog/monolog").unwrap();
+
+        assert_eq!(metadata.versions, vec!["3.5.0", "3.4.0", "2.9.2"]);
+        assert_eq!(metadata.repository.as_deref(), Some("https://github.com/Seldaek/monolog"));
+        assert_eq!(metadata.license, Some(vec!["MIT".to_string()]));
+        assert_eq!(metadata.released.len(), 3);
+        assert_eq!(metadata.released["3.4.0"].to_rfc3339(), "2023-06-21T08:46:11+00:00");
+        assert!(p2_metadata(&data, "acme/other").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_versions_resolved_from_p2() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/p2/monolog/monolog.json"))
+            .respond_with(ResponseTemplate::new(200).set_body_string(P2_MONOLOG))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/packages/monolog/monolog.json"))
+            .respond_with(ResponseTemplate::new(500))
+            .expect(0)
+            .mount(&server)
+            .await;
+
+        let client = PackagistClient::new().with_base_url(server.uri());
+        let info = client.get_package_info(&dependency("monolog/monolog", "2.9.2")).await.unwrap();
+
+        assert_eq!(info.update.unwrap().latest_version, "3.5.0");
+        assert_eq!(info.repository.as_deref(), Some("https://github.com/Seldaek/monolog"));
+    }
+
+    #[test]
+    fn test_repository_falls_back_to_version_source() {
+        let data = serde_json::json!({
+            "package": {
+                "versions": {
+                    "1.0.0": { "source": { "type": "git", "url": "https://github.com/acme/widget.git" } }
+                }
+            }
+        });
+        assert_eq!(repository_url(&data).as_deref(), Some("https://github.com/acme/widget"));
+    }
+}
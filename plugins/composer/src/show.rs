@@ -0,0 +1,97 @@
+use rensa_core::types::{Dependency, DependencyScope, Ecosystem, VersionConstraint};
+use rensa_core::{RensaError, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+/// The output of `composer show --format=json`, with or without `--all`.
+#[derive(Debug, Deserialize)]
+struct ShowOutput {
+    #[serde(default)]
+    installed: Vec<InstalledPackage>,
+}
+
+#[derive(Debug, Deserialize)]
+struct InstalledPackage {
+    name: String,
+    #[serde(default)]
+    version: String,
+}
+
+/// Parses `composer show --format=json` output into dependencies at their
+/// installed versions. Packages without a version are skipped.
+pub fn parse_composer_show(content: &str, path: &Path) -> Result<Vec<Dependency>> {
+    let output: ShowOutput = serde_json::from_str(content).map_err(|e| RensaError::ParseError {
+        file: path.to_path_buf(),
+        source: e,
+    })?;
+
+    Ok(output
+        .installed
+        .into_iter()
+        .filter(|package| !package.version.is_empty())
+        .map(|package| Dependency {
+            ecosystem: Ecosystem::Composer,
+            scope: DependencyScope::Runtime,
+            name: package.name,
+            constraint: VersionConstraint::Exact(package.version.clone()),
+            version: package.version,
+            file: path.to_path_buf(),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Trimmed output of `composer show --format=json` (composer 2.6).
+    const SHOW_OUTPUT: &str = r#"{
+    "installed": [
+        {
+            "name": "guzzlehttp/psr7",
+            "direct-dependency": false,
+            "homepage": null,
+            "source": "https://github.com/guzzle/psr7/tree/2.4.4",
+            "version": "2.4.4",
+            "description": "PSR-7 message implementation that also provides common utility methods",
+            "abandoned": false
+        },
+        {
+            "name": "symfony/console",
+            "direct-dependency": true,
+            "homepage": "https://symfony.com",
+            "source": "https://github.com/symfony/console/tree/v6.3.4",
+            "version": "v6.3.4",
+            "description": "Eases the creation of beautiful and testable command line interfaces",
+            "abandoned": false
+        }
+    ]
+}"#;
+
+    #[test]
+    fn test_parse_composer_show() {
+        let deps = parse_composer_show(SHOW_OUTPUT, Path::new("show.json")).unwrap();
+
+        assert_eq!(deps.len(), 2);
+        assert_eq!(deps[0].name, "guzzlehttp/psr7");
+        assert_eq!(deps[0].version, "2.4.4");
+        assert_eq!(deps[0].constraint, VersionConstraint::Exact("2.4.4".to_string()));
+        assert_eq!(deps[1].name, "symfony/console");
+        assert_eq!(deps[1].version, "v6.3.4");
+        assert!(deps.iter().all(|d| d.ecosystem == Ecosystem::Composer));
+    }
+
+    #[test]
+    fn test_empty_and_invalid_output() {
+        assert!(parse_composer_show("{}", Path::new("show.json")).unwrap().is_empty());
+        assert!(matches!(
+            parse_composer_show("not json", Path::new("show.json")),
+            Err(RensaError::ParseError { .. })
+        ));
+    }
+}
@@ -0,0 +1,124 @@
+use rensa_core::manifest::{read_manifest, DEFAULT_MAX_FILE_SIZE};
+use rensa_core::plugin::Detector;
+use rensa_core::types::{DependencyFile, Ecosystem};
+use rensa_core::Result;
+use std::path::Path;
+use walkdir::WalkDir;
+
+pub struct NpmDetector {
+    max_file_size: u64,
+}
+
+impl Default for NpmDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NpmDetector {
+    pub fn new() -> Self {
+        Self {
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        }
+    }
+
+    /// Skips manifests larger than `bytes` instead of reading them.
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl Detector for NpmDetector {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Npm
+    }
+
+    async fn detect(&self, path: &Path) -> Result<Vec<DependencyFile>> {
+        let mut files = Vec::new();
+
+        let walker = WalkDir::new(path)
+            .follow_links(true)
+            .into_iter()
+            .filter_entry(|e| e.file_name() != std::ffi::OsStr::new("node_modules"));
+
+        for entry in walker {
+            let entry = match entry {
+                Ok(e) => e,
+                Err(_) => continue,
+            };
+
+            let file_path = entry.path();
+            let file_name = match file_path.file_name().and_then(|n| n.to_str()) {
+                Some(n) => n,
+                None => continue,
+            };
+
+            match file_name {
+                "package-lock.json" => {}
+                // The lockfile has exact versions, so only fall back to the
+                // manifest when there is no lockfile next to it.
+                "package.json" => {
+                    if file_path.with_file_name("package-lock.json").exists() {
+                        continue;
+                    }
+                }
+                _ => continue,
+            }
+
+            match read_manifest(file_path, self.max_file_size) {
+                Ok(content) => {
+                    files.push(DependencyFile {
+                        ecosystem: Ecosystem::Npm,
+                        path: file_path.to_path_buf(),
+                        content,
+                    });
+                }
+                Err(e) => {
+                    eprintln!("Warning: Skipping {}: {}", file_path.display(), e);
+                }
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_detect_prefers_lockfile() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("package.json"), r#"{"name":"app"}"#).unwrap();
+        let lock_path = temp_dir.path().join("package-lock.json");
+        fs::write(&lock_path, r#"{"lockfileVersion":3,"packages":{}}"#).unwrap();
+
+        let detector = NpmDetector::new();
+        let files = detector.detect(temp_dir.path()).await.unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, lock_path);
+    }
+
+    #[tokio::test]
+    async fn test_detect_skips_node_modules() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = temp_dir.path().join("package.json");
+        fs::write(&manifest, r#"{"name":"app"}"#).unwrap();
+
+        let nested = temp_dir.path().join("node_modules").join("lodash");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("package.json"), r#"{"name":"lodash"}"#).unwrap();
+
+        let detector = NpmDetector::new();
+        let files = detector.detect(temp_dir.path()).await.unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, manifest);
+    }
+}
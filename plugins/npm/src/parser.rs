@@ -0,0 +1,291 @@
+use rensa_core::plugin::Parser;
+use rensa_core::types::{Dependency, DependencyFile, DependencyScope, Ecosystem, VersionConstraint};
+use rensa_core::{RensaError, Result};
+use serde_json::Value;
+
+pub struct NpmParser;
+
+impl Default for NpmParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NpmParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl Parser for NpmParser {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Npm
+    }
+
+    async fn parse(&self, file: &DependencyFile) -> Result<Vec<Dependency>> {
+        let json: Value = serde_json::from_str(&file.content).map_err(|e| RensaError::ParseError {
+            file: file.path.clone(),
+            source: e,
+        })?;
+
+        if file.path.file_name() == Some(std::ffi::OsStr::new("package-lock.json")) {
+            parse_lockfile(&json, file)
+        } else {
+            Ok(parse_manifest(&json, file))
+        }
+    }
+}
+
+/// Parses the `packages` map of a lockfileVersion 2/3 `package-lock.json`.
+fn parse_lockfile(json: &Value, file: &DependencyFile) -> Result<Vec<Dependency>> {
+    let packages = json
+        .get("packages")
+        .and_then(|p| p.as_object())
+        .ok_or_else(|| RensaError::Plugin {
+            message: format!(
+                "{}: unsupported lockfile (only lockfileVersion 2 and 3 are supported)",
+                file.path.display()
+            ),
+        })?;
+
+    // What the root project requires itself; anything else is transitive.
+    // Without a root entry every package is taken as direct.
+    let root = packages.get("").map(|root| {
+        ["dependencies", "devDependencies", "optionalDependencies", "peerDependencies"]
+            .iter()
+            .filter_map(|section| root.get(section).and_then(|d| d.as_object()))
+            .flat_map(|deps| deps.keys().map(String::as_str))
+            .collect::<std::collections::HashSet<&str>>()
+    });
+
+    let mut dependencies = Vec::new();
+
+    for (key, entry) in packages {
+        // "" is the root project; keys without node_modules are workspace members.
+        let name = match package_name_from_key(key) {
+            Some(n) => n,
+            None => continue,
+        };
+
+        if entry.get("link").and_then(|l| l.as_bool()).unwrap_or(false) {
+            continue;
+        }
+
+        let version = match entry.get("version").and_then(|v| v.as_str()) {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let flag = |key: &str| entry.get(key).and_then(|f| f.as_bool()).unwrap_or(false);
+        let scope = if flag("dev") {
+            DependencyScope::Development
+        } else if flag("peer") {
+            DependencyScope::Peer
+        } else if flag("optional") {
+            DependencyScope::Optional
+        } else {
+            DependencyScope::Runtime
+        };
+
+        dependencies.push(Dependency {
+            ecosystem: Ecosystem::Npm,
+            scope,
+            name: name.to_string(),
+            version: version.to_string(),
+            constraint: VersionConstraint::Exact(version.to_string()),
+            file: file.path.clone(),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: root
+                .as_ref()
+                .is_none_or(|root| key.strip_prefix("node_modules/") == Some(name) && root.contains(name)),
+            trace: None,
+        });
+    }
+
+    Ok(dependencies)
+}
+
+/// Extracts the package name from a lockfile key like
+/// `node_modules/a/node_modules/@scope/b`.
+fn package_name_from_key(key: &str) -> Option<&str> {
+    key.rfind("node_modules/")
+        .map(|idx| &key[idx + "node_modules/".len()..])
+        .filter(|name| !name.is_empty())
+}
+
+fn parse_manifest(json: &Value, file: &DependencyFile) -> Vec<Dependency> {
+    let mut dependencies = Vec::new();
+
+    for (section, scope) in [
+        ("dependencies", DependencyScope::Runtime),
+        ("devDependencies", DependencyScope::Development),
+        ("optionalDependencies", DependencyScope::Optional),
+        ("peerDependencies", DependencyScope::Peer),
+    ] {
+        if let Some(deps) = json.get(section).and_then(|d| d.as_object()) {
+            for (name, version) in deps {
+                let version = match version.as_str() {
+                    Some(v) => v,
+                    None => continue,
+                };
+
+                dependencies.push(Dependency {
+                    ecosystem: Ecosystem::Npm,
+                    scope,
+                    name: name.to_string(),
+                    version: version.to_string(),
+                    constraint: parse_constraint(version),
+                    file: file.path.clone(),
+                    repository: None,
+                    license: None,
+                    provenance: None,
+                    direct: true,
+                    trace: None,
+                });
+            }
+        }
+    }
+
+    dependencies
+}
+
+fn parse_constraint(version: &str) -> VersionConstraint {
+    if let Some(range) = VersionConstraint::parse_hyphen_range(version) {
+        range
+    } else if let Some(v) = version.strip_prefix('^') {
+        VersionConstraint::Caret(v.to_string())
+    } else if let Some(v) = version.strip_prefix('~') {
+        VersionConstraint::Tilde(v.to_string())
+    } else if let Some(v) = version.strip_prefix(">=") {
+        VersionConstraint::GreaterThanEqual(v.to_string())
+    } else if semver::Version::parse(version).is_ok() {
+        VersionConstraint::Exact(version.to_string())
+    } else {
+        VersionConstraint::Range(version.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const LOCKFILE_V2: &str = r#"{
+  "name": "app",
+  "version": "1.0.0",
+  "lockfileVersion": 2,
+  "requires": true,
+  "packages": {
+    "": {
+      "name": "app",
+      "version": "1.0.0",
+      "dependencies": { "express": "^4.18.0" }
+    },
+    "node_modules/express": {
+      "version": "4.18.2",
+      "dependencies": { "accepts": "~1.3.8" }
+    },
+    "node_modules/accepts": {
+      "version": "1.3.8"
+    },
+    "node_modules/express/node_modules/debug": {
+      "version": "2.6.9"
+    }
+  },
+  "dependencies": {
+    "express": { "version": "4.18.2" }
+  }
+}"#;
+
+    const LOCKFILE_V3: &str = r#"{
+  "name": "monorepo",
+  "lockfileVersion": 3,
+  "packages": {
+    "": {
+      "name": "monorepo",
+      "workspaces": ["packages/*"]
+    },
+    "node_modules/@babel/core": {
+      "version": "7.23.0",
+      "dev": true
+    },
+    "node_modules/shared": {
+      "resolved": "packages/shared",
+      "link": true
+    },
+    "packages/shared": {
+      "name": "shared",
+      "version": "0.1.0"
+    }
+  }
+}"#;
+
+    fn lockfile(content: &str) -> DependencyFile {
+        DependencyFile {
+            ecosystem: Ecosystem::Npm,
+            path: PathBuf::from("package-lock.json"),
+            content: content.to_string(),
+        }
+    }
+
+    fn find<'a>(deps: &'a [Dependency], name: &str) -> Option<&'a Dependency> {
+        deps.iter().find(|d| d.name == name)
+    }
+
+    #[tokio::test]
+    async fn test_parse_lockfile_v2_resolved_versions() {
+        let deps = NpmParser::new().parse(&lockfile(LOCKFILE_V2)).await.unwrap();
+
+        assert_eq!(deps.len(), 3);
+        assert_eq!(find(&deps, "express").unwrap().version, "4.18.2");
+        assert_eq!(find(&deps, "accepts").unwrap().version, "1.3.8");
+        // Nested transitive dependency
+        assert_eq!(find(&deps, "debug").unwrap().version, "2.6.9");
+    }
+
+    #[tokio::test]
+    async fn test_parse_lockfile_v3_skips_links_and_workspaces() {
+        let deps = NpmParser::new().parse(&lockfile(LOCKFILE_V3)).await.unwrap();
+
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].name, "@babel/core");
+        assert_eq!(deps[0].version, "7.23.0");
+        assert_eq!(deps[0].scope, DependencyScope::Development);
+        assert!(find(&deps, "shared").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_parse_lockfile_v1_is_rejected() {
+        let content = r#"{"lockfileVersion": 1, "dependencies": {"express": {"version": "4.18.2"}}}"#;
+        let result = NpmParser::new().parse(&lockfile(content)).await;
+
+        assert!(matches!(result, Err(RensaError::Plugin { .. })));
+    }
+
+    #[tokio::test]
+    async fn test_parse_package_json() {
+        let file = DependencyFile {
+            ecosystem: Ecosystem::Npm,
+            path: PathBuf::from("package.json"),
+            content: r#"{
+  "dependencies": { "lodash": "^4.17.0" },
+  "devDependencies": { "jest": "~29.0.0" },
+  "optionalDependencies": { "fsevents": "^2.3.0" },
+  "peerDependencies": { "react": ">=17.0.0" }
+}"#.to_string(),
+        };
+
+        let deps = NpmParser::new().parse(&file).await.unwrap();
+
+        assert_eq!(deps.len(), 4);
+        assert_eq!(find(&deps, "fsevents").unwrap().scope, DependencyScope::Optional);
+        assert_eq!(find(&deps, "react").unwrap().scope, DependencyScope::Peer);
+        assert_eq!(find(&deps, "lodash").unwrap().constraint, VersionConstraint::Caret("4.17.0".to_string()));
+        assert_eq!(find(&deps, "jest").unwrap().constraint, VersionConstraint::Tilde("29.0.0".to_string()));
+        assert_eq!(find(&deps, "lodash").unwrap().scope, DependencyScope::Runtime);
+        assert_eq!(find(&deps, "jest").unwrap().scope, DependencyScope::Development);
+    }
+}
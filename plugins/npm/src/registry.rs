@@ -0,0 +1,358 @@
+use rensa_core::plugin::RegistryClient;
+use rensa_core::types::{Dependency, Ecosystem, LookupTrace, PackageInfo, ProvenanceInfo, UpdateInfo};
+use rensa_core::{VersionComparator, UpdateType};
+use rensa_core::Result;
+
+pub struct NpmRegistryClient {
+    client: rensa_core::HttpClient,
+    base_url: String,
+}
+
+impl Default for NpmRegistryClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl NpmRegistryClient {
+    pub fn new() -> Self {
+        Self {
+            client: rensa_core::HttpClient::new(),
+            base_url: "https://registry.npmjs.org".to_string(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Points the client at a private registry's `url`, if set, and
+    /// applies its TLS settings.
+    pub fn with_registry_config(self, config: &rensa_core::config::RegistryConfig) -> Result<Self> {
+        let mut client = match &config.url {
+            Some(url) => self.with_base_url(url.trim_end_matches('/').to_string()),
+            None => self,
+        };
+        client.client = client.client.with_registry_config(config)?;
+        Ok(client)
+    }
+
+    pub fn with_cache(mut self, cache: rensa_core::CacheManager) -> Self {
+        self.client = rensa_core::HttpClient::with_cache(self.client, cache);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.client = self.client.with_timeout(timeout);
+        self
+    }
+
+    pub fn timeout(&self) -> std::time::Duration {
+        self.client.timeout()
+    }
+
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.client = self.client.with_pool_max_idle_per_host(max);
+        self
+    }
+
+    pub fn pool_max_idle_per_host(&self) -> usize {
+        self.client.pool_max_idle_per_host()
+    }
+
+    fn package_url(&self, name: &str) -> String {
+        // Scoped packages keep the '@' but encode the slash: @scope%2fname
+        format!("{}/{}", self.base_url, name.replace('/', "%2f"))
+    }
+
+    async fn fetch_packument(&self, dependency: &Dependency) -> Result<serde_json::Value> {
+        self.client.get(&self.package_url(&dependency.name)).await
+    }
+}
+
+fn latest_version(packument: &serde_json::Value) -> Option<String> {
+    packument
+        .get("dist-tags")
+        .and_then(|t| t.get("latest"))
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string())
+}
+
+/// The version a dependency is on: the locked one when it comes from
+/// package-lock.json, otherwise the lowest version its range allows.
+fn current_version(dependency: &Dependency) -> &str {
+    dependency.constraint.lower_bound().unwrap_or(&dependency.version)
+}
+
+fn update_info(dependency: &Dependency, latest_version: String) -> Option<UpdateInfo> {
+    let current = current_version(dependency);
+    match VersionComparator::classify_update(current, &latest_version) {
+        UpdateType::None | UpdateType::Unknown => None,
+        _ => Some(UpdateInfo {
+            dependency: dependency.clone(),
+            current_version: current.to_string(),
+            latest_version,
+            changelog: None,
+            latest_in_constraint: None,
+            versions_behind: None,
+            age_behind_days: None,
+            intermediate_releases: Vec::new(),
+        }),
+    }
+}
+
+/// Whether `version` was published with a provenance attestation, which
+/// npm lists under `dist.attestations`. `None` when the packument doesn't
+/// have that version, e.g. when `version` is a range.
+fn provenance(packument: &serde_json::Value, version: &str) -> Option<ProvenanceInfo> {
+    let dist = packument.get("versions")?.get(version.trim_start_matches('v'))?.get("dist")?;
+    let attestations = dist.get("attestations").filter(|a| a.get("provenance").is_some());
+
+    Some(ProvenanceInfo {
+        signed: attestations.is_some(),
+        attestation_url: attestations
+            .and_then(|a| a.get("url"))
+            .and_then(|u| u.as_str())
+            .map(str::to_string),
+    })
+}
+
+/// The deprecation message npm publishes on `version`, if any. An empty
+/// message is how npm records un-deprecating a version.
+fn deprecation(packument: &serde_json::Value, version: &str) -> Option<String> {
+    packument
+        .get("versions")?
+        .get(version.trim_start_matches('v'))?
+        .get("deprecated")?
+        .as_str()
+        .filter(|message| !message.is_empty())
+        .map(str::to_string)
+}
+
+/// Checks a name against npm's rules: at most 214 URL-safe lowercase
+/// characters, optionally scoped as `@scope/name`.
+pub fn is_valid_package_name(name: &str) -> bool {
+    if name.is_empty() || name.len() > 214 {
+        return false;
+    }
+
+    match name.strip_prefix('@') {
+        Some(scoped) => match scoped.split_once('/') {
+            Some((scope, package)) => is_valid_name_part(scope) && is_valid_name_part(package),
+            None => false,
+        },
+        None => is_valid_name_part(name),
+    }
+}
+
+fn is_valid_name_part(part: &str) -> bool {
+    !part.is_empty()
+        && !part.starts_with(['.', '_'])
+        && part
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || matches!(c, '-' | '.' | '_' | '~'))
+}
+
+#[async_trait::async_trait]
+impl RegistryClient for NpmRegistryClient {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Npm
+    }
+
+    fn is_valid_name(&self, name: &str) -> bool {
+        is_valid_package_name(name)
+    }
+
+    fn endpoint(&self) -> Option<&str> {
+        Some(&self.base_url)
+    }
+
+    fn trace(&self, dependency: &Dependency) -> Option<LookupTrace> {
+        let url = self.package_url(&dependency.name);
+        let cached = self.client.is_cached(&url);
+        Some(LookupTrace::new(url).with_cached(cached))
+    }
+
+    async fn get_latest_version(&self, dependency: &Dependency) -> Result<Option<String>> {
+        Ok(latest_version(&self.fetch_packument(dependency).await?))
+    }
+
+    async fn get_update_info(&self, dependency: &Dependency) -> Result<Option<UpdateInfo>> {
+        let latest = self.get_latest_version(dependency).await?;
+        Ok(latest.and_then(|latest| update_info(dependency, latest)))
+    }
+
+    async fn get_package_info(&self, dependency: &Dependency) -> Result<PackageInfo> {
+        let packument = self.fetch_packument(dependency).await?;
+
+        Ok(PackageInfo {
+            update: latest_version(&packument).and_then(|latest| update_info(dependency, latest)),
+            provenance: provenance(&packument, &dependency.version),
+            deprecated: deprecation(&packument, &dependency.version),
+            ..PackageInfo::default()
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_name_rules() {
+        for name in ["lodash", "@babel/core", "left-pad", "lodash.merge", "@types/node"] {
+            assert!(is_valid_package_name(name), "{}", name);
+        }
+        for name in ["", "Lodash", "@babel", "@babel/", ".hidden", "_private", "a b", "@scope/a/b", "../x"] {
+            assert!(!is_valid_package_name(name), "{}", name);
+        }
+        assert!(!is_valid_package_name(&"a".repeat(215)));
+    }
+
+    #[tokio::test]
+    async fn test_provenance_from_packument() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/sigstore"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "name": "sigstore",
+                "dist-tags": { "latest": "2.1.0" },
+                "versions": {
+                    "2.0.0": { "dist": { "tarball": "https://registry.npmjs.org/sigstore/-/sigstore-2.0.0.tgz" } },
+                    "2.1.0": {
+                        "dist": {
+                            "tarball": "https://registry.npmjs.org/sigstore/-/sigstore-2.1.0.tgz",
+                            "attestations": {
+                                "url": "https://registry.npmjs.org/-/npm/v1/attestations/sigstore@2.1.0",
+                                "provenance": { "predicateType": "https://slsa.dev/provenance/v1" }
+                            }
+                        }
+                    }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = NpmRegistryClient::new().with_base_url(server.uri());
+        let dependency = |version: &str| Dependency {
+            name: "sigstore".to_string(),
+            ecosystem: Ecosystem::Npm,
+            scope: rensa_core::DependencyScope::Runtime,
+            version: version.to_string(),
+            constraint: rensa_core::VersionConstraint::Exact(version.to_string()),
+            file: std::path::PathBuf::from("package.json"),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        };
+
+        let signed = client.get_package_info(&dependency("2.1.0")).await.unwrap();
+        assert_eq!(
+            signed.provenance,
+            Some(ProvenanceInfo {
+                signed: true,
+                attestation_url: Some("https://registry.npmjs.org/-/npm/v1/attestations/sigstore@2.1.0".to_string()),
+            })
+        );
+        assert!(signed.update.is_none());
+
+        let unsigned = client.get_package_info(&dependency("2.0.0")).await.unwrap();
+        assert_eq!(unsigned.provenance, Some(ProvenanceInfo { signed: false, attestation_url: None }));
+        assert_eq!(unsigned.update.unwrap().latest_version, "2.1.0");
+
+        let range = client.get_package_info(&dependency("^2.0.0")).await.unwrap();
+        assert_eq!(range.provenance, None);
+    }
+
+    #[tokio::test]
+    async fn test_deprecated_version_from_packument() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/request"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "name": "request",
+                "dist-tags": { "latest": "2.88.2" },
+                "versions": {
+                    "2.88.0": { "deprecated": "" },
+                    "2.88.2": { "deprecated": "request has been deprecated, see https://github.com/request/request/issues/3142" }
+                }
+            })))
+            .mount(&server)
+            .await;
+
+        let client = NpmRegistryClient::new().with_base_url(server.uri());
+        let dependency = |version: &str| Dependency {
+            name: "request".to_string(),
+            ecosystem: Ecosystem::Npm,
+            scope: rensa_core::DependencyScope::Runtime,
+            version: version.to_string(),
+            constraint: rensa_core::VersionConstraint::Exact(version.to_string()),
+            file: std::path::PathBuf::from("package.json"),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        };
+
+        let deprecated = client.get_package_info(&dependency("2.88.2")).await.unwrap();
+        assert_eq!(
+            deprecated.deprecated.as_deref(),
+            Some("request has been deprecated, see https://github.com/request/request/issues/3142")
+        );
+
+        let undeprecated = client.get_package_info(&dependency("2.88.0")).await.unwrap();
+        assert_eq!(undeprecated.deprecated, None);
+    }
+
+    #[test]
+    fn test_package_url_encodes_scope() {
+        let client = NpmRegistryClient::new();
+        assert_eq!(client.package_url("lodash"), "https://registry.npmjs.org/lodash");
+        assert_eq!(client.package_url("@babel/core"), "https://registry.npmjs.org/@babel%2fcore");
+    }
+
+    #[test]
+    fn test_registry_config_url_replaces_the_base_url() {
+        let config = rensa_core::config::RegistryConfig {
+            url: Some("https://npm.internal/".to_string()),
+            ..Default::default()
+        };
+        let client = NpmRegistryClient::new().with_registry_config(&config).unwrap();
+        assert_eq!(client.base_url, "https://npm.internal");
+    }
+
+    #[test]
+    fn test_range_is_classified_from_its_lower_bound() {
+        let dependency = |version: &str, constraint| Dependency {
+            name: "express".to_string(),
+            ecosystem: Ecosystem::Npm,
+            scope: rensa_core::DependencyScope::Runtime,
+            version: version.to_string(),
+            constraint,
+            file: std::path::PathBuf::from("package.json"),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        };
+
+        let range = dependency("^4.17.0", rensa_core::VersionConstraint::Caret("4.17.0".to_string()));
+        let update = update_info(&range, "4.18.2".to_string()).unwrap();
+        assert_eq!(update.current_version, "4.17.0");
+        assert_eq!(update.latest_version, "4.18.2");
+
+        let locked = dependency("4.18.2", rensa_core::VersionConstraint::Exact("4.18.2".to_string()));
+        assert!(update_info(&locked, "4.18.2".to_string()).is_none());
+    }
+}
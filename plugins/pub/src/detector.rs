@@ -0,0 +1,125 @@
+use rensa_core::manifest::{read_manifest, DEFAULT_MAX_FILE_SIZE};
+use rensa_core::plugin::Detector;
+use rensa_core::types::{DependencyFile, Ecosystem};
+use rensa_core::Result;
+use std::path::Path;
+use walkdir::WalkDir;
+
+pub struct PubDetector {
+    max_file_size: u64,
+}
+
+impl Default for PubDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PubDetector {
+    pub fn new() -> Self {
+        Self {
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        }
+    }
+
+    /// Skips manifests larger than `bytes` instead of reading them.
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl Detector for PubDetector {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Pub
+    }
+
+    async fn detect(&self, path: &Path) -> Result<Vec<DependencyFile>> {
+        let mut files = Vec::new();
+
+        // .dart_tool and build hold generated copies of package metadata
+        let walker = WalkDir::new(path)
+            .follow_links(true)
+            .into_iter()
+            .filter_entry(|e| {
+                let name = e.file_name();
+                name != std::ffi::OsStr::new(".dart_tool") && name != std::ffi::OsStr::new("build")
+            });
+
+        for entry in walker {
+            let entry = match entry {
+                Ok(e) => e,
+                Err(_) => continue,
+            };
+
+            let file_path = entry.path();
+            let file_name = match file_path.file_name().and_then(|n| n.to_str()) {
+                Some(n) => n,
+                None => continue,
+            };
+
+            match file_name {
+                "pubspec.lock" => {}
+                // Prefer the lockfile's resolved versions when it exists.
+                "pubspec.yaml" => {
+                    if file_path.with_file_name("pubspec.lock").exists() {
+                        continue;
+                    }
+                }
+                _ => continue,
+            }
+
+            match read_manifest(file_path, self.max_file_size) {
+                Ok(content) => {
+                    files.push(DependencyFile {
+                        ecosystem: Ecosystem::Pub,
+                        path: file_path.to_path_buf(),
+                        content,
+                    });
+                }
+                Err(e) => {
+                    eprintln!("Warning: Skipping {}: {}", file_path.display(), e);
+                }
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_detect_prefers_lockfile() {
+        let temp_dir = TempDir::new().unwrap();
+        fs::write(temp_dir.path().join("pubspec.yaml"), "name: app\n").unwrap();
+        let lock_path = temp_dir.path().join("pubspec.lock");
+        fs::write(&lock_path, "packages: {}\n").unwrap();
+
+        let files = PubDetector::new().detect(temp_dir.path()).await.unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, lock_path);
+    }
+
+    #[tokio::test]
+    async fn test_detect_skips_dart_tool() {
+        let temp_dir = TempDir::new().unwrap();
+        let manifest = temp_dir.path().join("pubspec.yaml");
+        fs::write(&manifest, "name: app\n").unwrap();
+
+        let generated = temp_dir.path().join(".dart_tool").join("pkg");
+        fs::create_dir_all(&generated).unwrap();
+        fs::write(generated.join("pubspec.yaml"), "name: pkg\n").unwrap();
+
+        let files = PubDetector::new().detect(temp_dir.path()).await.unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, manifest);
+    }
+}
@@ -0,0 +1,8 @@
+pub mod detector;
+pub mod parser;
+pub mod registry;
+pub mod osv;
+pub mod plugin;
+
+pub use plugin::PubPlugin;
+pub use registry::PubRegistryClient;
@@ -0,0 +1,263 @@
+use rensa_core::plugin::Parser;
+use rensa_core::types::{Dependency, DependencyFile, DependencyScope, Ecosystem, VersionConstraint};
+use rensa_core::{RensaError, Result};
+use serde_yaml::Value;
+
+pub struct PubParser;
+
+impl Default for PubParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PubParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl Parser for PubParser {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Pub
+    }
+
+    async fn parse(&self, file: &DependencyFile) -> Result<Vec<Dependency>> {
+        let yaml: Value = serde_yaml::from_str(&file.content).map_err(|e| RensaError::Plugin {
+            message: format!("{}: invalid YAML: {}", file.path.display(), e),
+        })?;
+
+        if file.path.file_name() == Some(std::ffi::OsStr::new("pubspec.lock")) {
+            Ok(parse_lockfile(&yaml, file))
+        } else {
+            Ok(parse_pubspec(&yaml, file))
+        }
+    }
+}
+
+fn parse_pubspec(yaml: &Value, file: &DependencyFile) -> Vec<Dependency> {
+    let mut dependencies = Vec::new();
+
+    for (section, scope) in [
+        ("dependencies", DependencyScope::Runtime),
+        ("dev_dependencies", DependencyScope::Development),
+    ] {
+        let deps = match yaml.get(section).and_then(|d| d.as_mapping()) {
+            Some(d) => d,
+            None => continue,
+        };
+
+        for (name, spec) in deps {
+            let name = match name.as_str() {
+                Some(n) => n,
+                None => continue,
+            };
+
+            if let Some(version) = hosted_version(spec) {
+                dependencies.push(Dependency {
+                    ecosystem: Ecosystem::Pub,
+                    scope,
+                    name: name.to_string(),
+                    version: version.clone(),
+                    constraint: parse_constraint(&version),
+                    file: file.path.clone(),
+                    repository: None,
+                    license: None,
+                    provenance: None,
+                    direct: true,
+                    trace: None,
+                });
+            }
+        }
+    }
+
+    dependencies
+}
+
+/// Returns the version requirement of a dependency that comes from a package
+/// registry, or `None` for `sdk:`, `git:` and `path:` sources.
+fn hosted_version(spec: &Value) -> Option<String> {
+    match spec {
+        // `pkg:` with no value means any version
+        Value::Null => Some("any".to_string()),
+        Value::String(v) => Some(v.clone()),
+        Value::Mapping(table) => {
+            let is_registry = ["sdk", "git", "path"]
+                .iter()
+                .all(|key| !table.contains_key(*key));
+            if !is_registry {
+                return None;
+            }
+            Some(
+                table
+                    .get("version")
+                    .and_then(|v| v.as_str())
+                    .unwrap_or("any")
+                    .to_string(),
+            )
+        }
+        _ => None,
+    }
+}
+
+fn parse_lockfile(yaml: &Value, file: &DependencyFile) -> Vec<Dependency> {
+    let mut dependencies = Vec::new();
+
+    let packages = match yaml.get("packages").and_then(|p| p.as_mapping()) {
+        Some(p) => p,
+        None => return dependencies,
+    };
+
+    for (name, entry) in packages {
+        let name = match name.as_str() {
+            Some(n) => n,
+            None => continue,
+        };
+
+        if entry.get("source").and_then(|s| s.as_str()) != Some("hosted") {
+            continue;
+        }
+
+        let kind = entry.get("dependency").and_then(|d| d.as_str());
+        let scope = match kind {
+            Some("direct dev") => DependencyScope::Development,
+            _ => DependencyScope::Runtime,
+        };
+
+        if let Some(version) = entry.get("version").and_then(|v| v.as_str()) {
+            dependencies.push(Dependency {
+                ecosystem: Ecosystem::Pub,
+                scope,
+                name: name.to_string(),
+                version: version.to_string(),
+                constraint: VersionConstraint::Exact(version.to_string()),
+                file: file.path.clone(),
+                repository: None,
+                license: None,
+                provenance: None,
+                direct: kind != Some("transitive"),
+                trace: None,
+            });
+        }
+    }
+
+    dependencies
+}
+
+fn parse_constraint(version: &str) -> VersionConstraint {
+    if version == "any" {
+        VersionConstraint::Range("*".to_string())
+    } else if let Some(v) = version.strip_prefix('^') {
+        VersionConstraint::Caret(v.to_string())
+    } else if let Some(v) = version.strip_prefix(">=") {
+        if v.contains(' ') {
+            VersionConstraint::Range(version.to_string())
+        } else {
+            VersionConstraint::GreaterThanEqual(v.to_string())
+        }
+    } else if semver::Version::parse(version).is_ok() {
+        VersionConstraint::Exact(version.to_string())
+    } else {
+        VersionConstraint::Range(version.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn file(name: &str, content: &str) -> DependencyFile {
+        DependencyFile {
+            ecosystem: Ecosystem::Pub,
+            path: PathBuf::from(name),
+            content: content.to_string(),
+        }
+    }
+
+    fn find<'a>(deps: &'a [Dependency], name: &str) -> Option<&'a Dependency> {
+        deps.iter().find(|d| d.name == name)
+    }
+
+    #[tokio::test]
+    async fn test_parse_pubspec_dependency_forms() {
+        let content = r#"
+name: my_app
+environment:
+  sdk: ">=3.0.0 <4.0.0"
+dependencies:
+  flutter:
+    sdk: flutter
+  http: ^1.1.0
+  collection: 1.18.0
+  path_provider:
+  intl:
+    version: ^0.18.1
+    hosted: https://pub.dev
+  local_pkg:
+    path: ../local_pkg
+  forked:
+    git:
+      url: https://github.com/example/forked.git
+      ref: main
+dev_dependencies:
+  lints: ">=2.0.0 <4.0.0"
+"#;
+
+        let deps = PubParser::new().parse(&file("pubspec.yaml", content)).await.unwrap();
+
+        assert_eq!(deps.len(), 5);
+        assert_eq!(find(&deps, "http").unwrap().constraint, VersionConstraint::Caret("1.1.0".to_string()));
+        assert_eq!(find(&deps, "collection").unwrap().constraint, VersionConstraint::Exact("1.18.0".to_string()));
+        assert_eq!(find(&deps, "path_provider").unwrap().version, "any");
+        assert_eq!(find(&deps, "intl").unwrap().version, "^0.18.1");
+        assert_eq!(find(&deps, "lints").unwrap().constraint, VersionConstraint::Range(">=2.0.0 <4.0.0".to_string()));
+
+        // sdk, path and git sources are not on pub.dev
+        assert!(find(&deps, "flutter").is_none());
+        assert!(find(&deps, "local_pkg").is_none());
+        assert!(find(&deps, "forked").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_parse_pubspec_lock() {
+        let content = r#"
+packages:
+  http:
+    dependency: "direct main"
+    description:
+      name: http
+      url: "https://pub.dev"
+    source: hosted
+    version: "1.1.2"
+  meta:
+    dependency: transitive
+    description:
+      name: meta
+      url: "https://pub.dev"
+    source: hosted
+    version: "1.11.0"
+  flutter:
+    dependency: "direct main"
+    description: flutter
+    source: sdk
+    version: "0.0.0"
+sdks:
+  dart: ">=3.0.0 <4.0.0"
+"#;
+
+        let deps = PubParser::new().parse(&file("pubspec.lock", content)).await.unwrap();
+
+        assert_eq!(deps.len(), 2);
+        assert_eq!(find(&deps, "http").unwrap().version, "1.1.2");
+        assert_eq!(find(&deps, "meta").unwrap().version, "1.11.0");
+        assert!(find(&deps, "flutter").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_parse_invalid_yaml() {
+        let result = PubParser::new().parse(&file("pubspec.yaml", "dependencies: [")).await;
+        assert!(matches!(result, Err(RensaError::Plugin { .. })));
+    }
+}
@@ -0,0 +1,171 @@
+use rensa_core::types::Ecosystem;
+use std::time::Duration;
+
+pub struct PubPlugin {
+    cache: Option<rensa_core::CacheManager>,
+    registry: Option<rensa_core::config::RegistryConfig>,
+    registry_timeout: Option<Duration>,
+    osv_timeout: Option<Duration>,
+    osv_url: Option<String>,
+    max_file_size: Option<u64>,
+    concurrency: Option<usize>,
+    include_related: bool,
+    severity_floor: Option<rensa_core::SeverityThreshold>,
+}
+
+impl Default for PubPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PubPlugin {
+    pub fn new() -> Self {
+        Self {
+            cache: None,
+            registry: None,
+            registry_timeout: None,
+            osv_timeout: None,
+            osv_url: None,
+            max_file_size: None,
+            concurrency: None,
+            include_related: false,
+            severity_floor: None,
+        }
+    }
+
+    pub fn with_cache(cache: rensa_core::CacheManager) -> Self {
+        Self {
+            cache: Some(cache),
+            ..Self::new()
+        }
+    }
+
+    /// Queries the private registry described by `config` instead of the
+    /// public one.
+    pub fn with_registry_config(mut self, config: rensa_core::config::RegistryConfig) -> Self {
+        self.registry = Some(config);
+        self
+    }
+
+    /// Overrides the HTTP timeout used for registry lookups.
+    pub fn with_registry_timeout(mut self, timeout: Duration) -> Self {
+        self.registry_timeout = Some(timeout);
+        self
+    }
+
+    /// Overrides the HTTP timeout used for OSV queries.
+    pub fn with_osv_timeout(mut self, timeout: Duration) -> Self {
+        self.osv_timeout = Some(timeout);
+        self
+    }
+
+    /// Sends OSV queries to another OSV-compatible API, e.g. a mirror.
+    pub fn with_osv_url(mut self, url: String) -> Self {
+        self.osv_url = Some(url);
+        self
+    }
+
+    /// Also reports OSV advisories related to the ones found.
+    pub fn with_include_related(mut self, include: bool) -> Self {
+        self.include_related = include;
+        self
+    }
+
+    /// Only reports OSV advisories rated at or above `floor`.
+    pub fn with_severity_floor(mut self, floor: rensa_core::SeverityThreshold) -> Self {
+        self.severity_floor = Some(floor);
+        self
+    }
+
+    /// Skips manifests larger than `bytes` during detection.
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Sizes connection pools and OSV advisory fan-out for `concurrency` requests in
+    /// flight at once.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    fn detector(&self) -> super::detector::PubDetector {
+        let detector = super::detector::PubDetector::new();
+        match self.max_file_size {
+            Some(bytes) => detector.with_max_file_size(bytes),
+            None => detector,
+        }
+    }
+
+    fn registry_client(&self) -> super::registry::PubRegistryClient {
+        let mut client = match &self.registry {
+            Some(config) => super::registry::PubRegistryClient::new()
+                .with_registry_config(config)
+                .unwrap_or_else(|e| {
+                    eprintln!("Warning: Ignoring the pub registry config: {}", e);
+                    super::registry::PubRegistryClient::new()
+                }),
+            None => super::registry::PubRegistryClient::new(),
+        };
+        if let Some(cache) = &self.cache {
+            client = client.with_cache(cache.clone());
+        }
+        if let Some(timeout) = self.registry_timeout {
+            client = client.with_timeout(timeout);
+        }
+        if let Some(concurrency) = self.concurrency {
+            client = client.with_pool_max_idle_per_host(concurrency);
+        }
+        client
+    }
+
+    fn osv_scanner(&self) -> super::osv::OsvScanner {
+        let mut scanner = super::osv::OsvScanner::new();
+        if let Some(url) = &self.osv_url {
+            scanner = scanner.with_base_url(url.clone());
+        }
+        if let Some(cache) = &self.cache {
+            scanner = scanner.with_cache(cache.clone());
+        }
+        if let Some(timeout) = self.osv_timeout {
+            scanner = scanner.with_timeout(timeout);
+        }
+        if let Some(floor) = self.severity_floor {
+            scanner = scanner.with_severity_floor(floor);
+        }
+        if let Some(concurrency) = self.concurrency {
+            scanner = scanner.with_concurrency(concurrency);
+        }
+        scanner.with_include_related(self.include_related)
+    }
+}
+
+impl rensa_core::Plugin for PubPlugin {
+    fn name(&self) -> &'static str {
+        "pub"
+    }
+
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Pub
+    }
+
+    fn create_detector(&self) -> Option<Box<dyn rensa_core::Detector>> {
+        Some(Box::new(self.detector()))
+    }
+
+    fn create_parser(&self) -> Option<Box<dyn rensa_core::Parser>> {
+        Some(Box::new(super::parser::PubParser::new()))
+    }
+
+    fn create_registry_client(&self) -> Option<Box<dyn rensa_core::RegistryClient>> {
+        Some(Box::new(self.registry_client()))
+    }
+
+    fn create_vulnerability_scanner(
+        &self,
+    ) -> Option<Box<dyn rensa_core::VulnerabilityScanner>> {
+        Some(Box::new(self.osv_scanner()))
+    }
+}
@@ -0,0 +1,195 @@
+use rensa_core::plugin::RegistryClient;
+use rensa_core::types::{Dependency, Ecosystem, LookupTrace, UpdateInfo};
+use rensa_core::{VersionComparator, UpdateType};
+use rensa_core::Result;
+
+pub struct PubRegistryClient {
+    client: rensa_core::HttpClient,
+    base_url: String,
+}
+
+impl Default for PubRegistryClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PubRegistryClient {
+    pub fn new() -> Self {
+        Self {
+            client: rensa_core::HttpClient::new(),
+            base_url: "https://pub.dev".to_string(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Points the client at a private registry's `url`, if set, and
+    /// applies its TLS settings.
+    pub fn with_registry_config(self, config: &rensa_core::config::RegistryConfig) -> Result<Self> {
+        let mut client = match &config.url {
+            Some(url) => self.with_base_url(url.trim_end_matches('/').to_string()),
+            None => self,
+        };
+        client.client = client.client.with_registry_config(config)?;
+        Ok(client)
+    }
+
+    pub fn with_cache(mut self, cache: rensa_core::CacheManager) -> Self {
+        self.client = rensa_core::HttpClient::with_cache(self.client, cache);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.client = self.client.with_timeout(timeout);
+        self
+    }
+
+    pub fn timeout(&self) -> std::time::Duration {
+        self.client.timeout()
+    }
+
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.client = self.client.with_pool_max_idle_per_host(max);
+        self
+    }
+
+    pub fn pool_max_idle_per_host(&self) -> usize {
+        self.client.pool_max_idle_per_host()
+    }
+
+    fn package_url(&self, dependency: &Dependency) -> String {
+        format!("{}/api/packages/{}", self.base_url, dependency.name)
+    }
+}
+
+/// Pub package names are lowercase identifiers: `[a-z_][a-z0-9_]*`.
+pub fn is_valid_package_name(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_lowercase() || c == '_' => {
+            chars.all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+        }
+        _ => false,
+    }
+}
+
+fn latest_version(data: &serde_json::Value) -> Option<String> {
+    data.get("latest")
+        .and_then(|l| l.get("version"))
+        .and_then(|v| v.as_str())
+        .map(|v| v.to_string())
+}
+
+#[async_trait::async_trait]
+impl RegistryClient for PubRegistryClient {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Pub
+    }
+
+    fn is_valid_name(&self, name: &str) -> bool {
+        is_valid_package_name(name)
+    }
+
+    fn endpoint(&self) -> Option<&str> {
+        Some(&self.base_url)
+    }
+
+    fn trace(&self, dependency: &Dependency) -> Option<LookupTrace> {
+        let url = self.package_url(dependency);
+        let cached = self.client.is_cached(&url);
+        Some(LookupTrace::new(url).with_cached(cached))
+    }
+
+    async fn get_latest_version(&self, dependency: &Dependency) -> Result<Option<String>> {
+        let url = self.package_url(dependency);
+
+        let data: serde_json::Value = self.client.get(&url).await?;
+
+        Ok(latest_version(&data))
+    }
+
+    async fn get_update_info(&self, dependency: &Dependency) -> Result<Option<UpdateInfo>> {
+        let latest = self.get_latest_version(dependency).await?;
+        Ok(latest.and_then(|latest| update_info(dependency, latest)))
+    }
+}
+
+/// The version a dependency is on: the locked one when it comes from
+/// pubspec.lock, otherwise the lowest version its constraint allows.
+fn current_version(dependency: &Dependency) -> &str {
+    dependency.constraint.lower_bound().unwrap_or(&dependency.version)
+}
+
+fn update_info(dependency: &Dependency, latest_version: String) -> Option<UpdateInfo> {
+    let current = current_version(dependency);
+    match VersionComparator::classify_update(current, &latest_version) {
+        UpdateType::None | UpdateType::Unknown => None,
+        _ => Some(UpdateInfo {
+            dependency: dependency.clone(),
+            current_version: current.to_string(),
+            latest_version,
+            changelog: None,
+            latest_in_constraint: None,
+            versions_behind: None,
+            age_behind_days: None,
+            intermediate_releases: Vec::new(),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_package_name_rules() {
+        assert!(is_valid_package_name("http"));
+        assert!(is_valid_package_name("flutter_bloc"));
+        assert!(is_valid_package_name("_internal2"));
+        assert!(!is_valid_package_name(""));
+        assert!(!is_valid_package_name("2fa"));
+        assert!(!is_valid_package_name("Http"));
+        assert!(!is_valid_package_name("http/../x"));
+    }
+
+    #[test]
+    fn test_constraint_is_classified_from_its_lower_bound() {
+        let dependency = |version: &str, constraint| Dependency {
+            name: "http".to_string(),
+            ecosystem: Ecosystem::Pub,
+            scope: rensa_core::DependencyScope::Runtime,
+            version: version.to_string(),
+            constraint,
+            file: std::path::PathBuf::from("pubspec.yaml"),
+            repository: None,
+            license: None,
+            provenance: None,
+            direct: true,
+            trace: None,
+        };
+
+        let declared = dependency("^1.1.0", rensa_core::VersionConstraint::Caret("1.1.0".to_string()));
+        let update = update_info(&declared, "1.2.0".to_string()).unwrap();
+        assert_eq!(update.current_version, "1.1.0");
+        assert_eq!(update.latest_version, "1.2.0");
+
+        let locked = dependency("1.2.0", rensa_core::VersionConstraint::Exact("1.2.0".to_string()));
+        assert!(update_info(&locked, "1.2.0".to_string()).is_none());
+    }
+
+    #[test]
+    fn test_latest_version_from_api_response() {
+        let data = serde_json::json!({
+            "name": "http",
+            "latest": { "version": "1.2.0", "pubspec": { "name": "http" } },
+            "versions": [{ "version": "1.1.0" }, { "version": "1.2.0" }]
+        });
+
+        assert_eq!(latest_version(&data), Some("1.2.0".to_string()));
+        assert_eq!(latest_version(&serde_json::json!({})), None);
+    }
+}
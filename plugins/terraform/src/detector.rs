@@ -0,0 +1,98 @@
+use rensa_core::manifest::{read_manifest, DEFAULT_MAX_FILE_SIZE};
+use rensa_core::plugin::Detector;
+use rensa_core::types::{DependencyFile, Ecosystem};
+use rensa_core::Result;
+use std::path::Path;
+use walkdir::WalkDir;
+
+pub struct TerraformDetector {
+    max_file_size: u64,
+}
+
+impl Default for TerraformDetector {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TerraformDetector {
+    pub fn new() -> Self {
+        Self {
+            max_file_size: DEFAULT_MAX_FILE_SIZE,
+        }
+    }
+
+    /// Skips files larger than `bytes` instead of reading them.
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = bytes;
+        self
+    }
+}
+
+#[async_trait::async_trait]
+impl Detector for TerraformDetector {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Terraform
+    }
+
+    async fn detect(&self, path: &Path) -> Result<Vec<DependencyFile>> {
+        let mut files = Vec::new();
+
+        // .terraform holds downloaded copies of every module in use
+        let walker = WalkDir::new(path)
+            .follow_links(true)
+            .into_iter()
+            .filter_entry(|e| e.file_name() != std::ffi::OsStr::new(".terraform"));
+
+        for entry in walker {
+            let entry = match entry {
+                Ok(e) => e,
+                Err(_) => continue,
+            };
+
+            let file_path = entry.path();
+            if !entry.file_type().is_file() || file_path.extension() != Some(std::ffi::OsStr::new("tf")) {
+                continue;
+            }
+
+            match read_manifest(file_path, self.max_file_size) {
+                Ok(content) => {
+                    files.push(DependencyFile {
+                        ecosystem: Ecosystem::Terraform,
+                        path: file_path.to_path_buf(),
+                        content,
+                    });
+                }
+                Err(e) => {
+                    eprintln!("Warning: Skipping {}: {}", file_path.display(), e);
+                }
+            }
+        }
+
+        Ok(files)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_detect_skips_terraform_dir() {
+        let temp_dir = TempDir::new().unwrap();
+        let main = temp_dir.path().join("main.tf");
+        fs::write(&main, "").unwrap();
+        fs::write(temp_dir.path().join("terraform.tfvars"), "").unwrap();
+
+        let downloaded = temp_dir.path().join(".terraform").join("modules").join("vpc");
+        fs::create_dir_all(&downloaded).unwrap();
+        fs::write(downloaded.join("main.tf"), "").unwrap();
+
+        let files = TerraformDetector::new().detect(temp_dir.path()).await.unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, main);
+    }
+}
@@ -0,0 +1,7 @@
+pub mod detector;
+pub mod parser;
+pub mod registry;
+pub mod plugin;
+
+pub use plugin::TerraformPlugin;
+pub use registry::TerraformRegistryClient;
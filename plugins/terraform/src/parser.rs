@@ -0,0 +1,311 @@
+use hcl::{Body, Expression, ObjectKey};
+use rensa_core::plugin::Parser;
+use rensa_core::types::{Dependency, DependencyFile, DependencyScope, Ecosystem, VersionConstraint};
+use rensa_core::{RensaError, Result};
+
+/// Public registries whose addresses may prefix a module or provider
+/// source. Both serve the same registry protocol.
+const PUBLIC_REGISTRIES: &[&str] = &["registry.terraform.io", "registry.opentofu.org"];
+
+pub struct TerraformParser;
+
+impl Default for TerraformParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TerraformParser {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+#[async_trait::async_trait]
+impl Parser for TerraformParser {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Terraform
+    }
+
+    async fn parse(&self, file: &DependencyFile) -> Result<Vec<Dependency>> {
+        let body = parse_body(file)?;
+        Ok(requirements(&body)
+            .into_iter()
+            .filter_map(|r| {
+                let version = r.version?;
+                Some(Dependency {
+                    ecosystem: Ecosystem::Terraform,
+                    scope: DependencyScope::Runtime,
+                    name: r.source,
+                    version: lower_bound(&version),
+                    constraint: parse_constraint(&version),
+                    file: file.path.clone(),
+                    repository: None,
+                    license: None,
+                    provenance: None,
+                    direct: true,
+                    trace: None,
+                })
+            })
+            .collect())
+    }
+
+    /// Registry modules and providers without a `version` float to the
+    /// newest release on every `init`.
+    fn warnings(&self, file: &DependencyFile) -> Vec<String> {
+        let body = match parse_body(file) {
+            Ok(body) => body,
+            Err(_) => return Vec::new(),
+        };
+        requirements(&body)
+            .into_iter()
+            .filter(|r| r.version.is_none())
+            .map(|r| format!("{} {} has no version constraint in {}", r.kind, r.source, file.path.display()))
+            .collect()
+    }
+}
+
+/// A module or provider sourced from a public registry.
+struct Requirement {
+    kind: &'static str,
+    /// `namespace/name/provider` for modules, `namespace/type` for providers.
+    source: String,
+    version: Option<String>,
+}
+
+fn parse_body(file: &DependencyFile) -> Result<Body> {
+    hcl::parse(&file.content).map_err(|e| RensaError::Plugin {
+        message: format!("{}: invalid HCL: {}", file.path.display(), e),
+    })
+}
+
+fn requirements(body: &Body) -> Vec<Requirement> {
+    let mut requirements = Vec::new();
+
+    for block in body.blocks() {
+        match block.identifier() {
+            "module" => {
+                let source = string_attribute(block.body(), "source").and_then(|s| registry_address(&s, 3));
+                if let Some(source) = source {
+                    requirements.push(Requirement {
+                        kind: "module",
+                        source,
+                        version: string_attribute(block.body(), "version"),
+                    });
+                }
+            }
+            "terraform" => {
+                let providers = block.body().blocks().filter(|b| b.identifier() == "required_providers");
+                for attribute in providers.flat_map(|b| b.body().attributes()) {
+                    requirements.extend(provider(attribute.key(), attribute.expr()));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    requirements
+}
+
+/// A `required_providers` entry: `aws = { source = "hashicorp/aws",
+/// version = "~> 5.0" }`, or the pre-0.13 `aws = "~> 5.0"`, which implies
+/// the `hashicorp` namespace.
+fn provider(local_name: &str, expr: &Expression) -> Option<Requirement> {
+    let (source, version) = match expr {
+        Expression::String(version) => (format!("hashicorp/{}", local_name), Some(version.clone())),
+        Expression::Object(object) => {
+            let field = |name: &str| {
+                object.iter().find_map(|(key, value)| match (key_str(key)?, value) {
+                    (key, Expression::String(s)) if key == name => Some(s.clone()),
+                    _ => None,
+                })
+            };
+            let source = field("source").unwrap_or_else(|| format!("hashicorp/{}", local_name));
+            (source, field("version"))
+        }
+        _ => return None,
+    };
+
+    Some(Requirement {
+        kind: "provider",
+        source: registry_address(&source, 2)?,
+        version,
+    })
+}
+
+fn key_str(key: &ObjectKey) -> Option<&str> {
+    match key {
+        ObjectKey::Identifier(ident) => Some(ident.as_str()),
+        ObjectKey::Expression(Expression::String(s)) => Some(s),
+        _ => None,
+    }
+}
+
+fn string_attribute(body: &Body, key: &str) -> Option<String> {
+    body.attributes().find(|a| a.key() == key).and_then(|a| match a.expr() {
+        Expression::String(s) => Some(s.clone()),
+        _ => None,
+    })
+}
+
+/// Normalizes a registry source with `parts` segments, dropping a public
+/// registry host and a module's `//subdirectory`. Local paths, VCS and
+/// URL sources, and private registries return `None`.
+fn registry_address(source: &str, parts: usize) -> Option<String> {
+    let source = source.split("//").next()?;
+    if source.contains("::") || source.contains('?') || source.starts_with('.') || source.starts_with('/') {
+        return None;
+    }
+
+    let mut segments: Vec<&str> = source.split('/').collect();
+    if segments.len() == parts + 1 {
+        let host = segments.remove(0).to_ascii_lowercase();
+        if !PUBLIC_REGISTRIES.contains(&host.as_str()) {
+            return None;
+        }
+    }
+
+    let valid = segments.len() == parts
+        && segments
+            .iter()
+            .all(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'));
+    valid.then(|| segments.join("/").to_ascii_lowercase())
+}
+
+/// The version a constraint starts from, padded to three parts so it can
+/// be compared with registry releases: `~> 3.0` is `3.0.0`.
+fn lower_bound(constraint: &str) -> String {
+    let first = constraint.split(',').next().unwrap_or(constraint);
+    let version = first.trim().trim_start_matches(['~', '>', '=', 'v']).trim();
+    match version.split('.').count() {
+        1 => format!("{}.0.0", version),
+        2 => format!("{}.0", version),
+        _ => version.to_string(),
+    }
+}
+
+/// Maps Terraform's constraint syntax onto the shared constraint types.
+/// `~> 3.0` allows any 3.x release and `~> 3.0.1` any 3.0.x from 3.0.1.
+fn parse_constraint(constraint: &str) -> VersionConstraint {
+    let constraint = constraint.trim();
+    if let Some(v) = constraint.strip_prefix("~>").map(str::trim) {
+        match v.split('.').count() {
+            2 => return VersionConstraint::Caret(v.to_string()),
+            3 => return VersionConstraint::Tilde(v.to_string()),
+            _ => {}
+        }
+    }
+
+    let exact = constraint.strip_prefix('=').unwrap_or(constraint).trim();
+    if semver::Version::parse(exact).is_ok() {
+        VersionConstraint::Exact(exact.to_string())
+    } else if let Some(v) = constraint.strip_prefix(">=").filter(|v| !v.contains(',')) {
+        VersionConstraint::GreaterThanEqual(v.trim().to_string())
+    } else {
+        VersionConstraint::Range(constraint.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    const MAIN_TF: &str = r#"
+terraform {
+  required_version = ">= 1.5"
+
+  required_providers {
+    aws = {
+      source  = "hashicorp/aws"
+      version = "~> 5.0"
+    }
+    random = {
+      source = "registry.opentofu.org/hashicorp/random"
+    }
+    google = "~> 4.84.0"
+  }
+}
+
+module "vpc" {
+  source  = "terraform-aws-modules/vpc/aws"
+  version = "5.1.2"
+}
+
+module "iam_user" {
+  source  = "registry.terraform.io/terraform-aws-modules/iam/aws//modules/iam-user"
+  version = ">= 5.30.0, < 6.0.0"
+}
+
+module "unpinned" {
+  source = "terraform-aws-modules/s3-bucket/aws"
+}
+
+module "local" {
+  source = "./modules/network"
+}
+
+module "git" {
+  source = "git::https://example.com/network.git?ref=v1.2.0"
+}
+"#;
+
+    fn file(content: &str) -> DependencyFile {
+        DependencyFile {
+            ecosystem: Ecosystem::Terraform,
+            path: PathBuf::from("main.tf"),
+            content: content.to_string(),
+        }
+    }
+
+    fn find<'a>(deps: &'a [Dependency], name: &str) -> Option<&'a Dependency> {
+        deps.iter().find(|d| d.name == name)
+    }
+
+    #[tokio::test]
+    async fn test_module_versions() {
+        let deps = TerraformParser::new().parse(&file(MAIN_TF)).await.unwrap();
+
+        let vpc = find(&deps, "terraform-aws-modules/vpc/aws").unwrap();
+        assert_eq!(vpc.version, "5.1.2");
+        assert_eq!(vpc.constraint, VersionConstraint::Exact("5.1.2".to_string()));
+
+        let iam = find(&deps, "terraform-aws-modules/iam/aws").unwrap();
+        assert_eq!(iam.version, "5.30.0");
+        assert_eq!(iam.constraint, VersionConstraint::Range(">= 5.30.0, < 6.0.0".to_string()));
+
+        // Unpinned, local and git modules aren't looked up.
+        assert_eq!(deps.len(), 4);
+        assert!(find(&deps, "terraform-aws-modules/s3-bucket/aws").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_provider_versions() {
+        let deps = TerraformParser::new().parse(&file(MAIN_TF)).await.unwrap();
+
+        let aws = find(&deps, "hashicorp/aws").unwrap();
+        assert_eq!(aws.version, "5.0.0");
+        assert_eq!(aws.constraint, VersionConstraint::Caret("5.0".to_string()));
+
+        let google = find(&deps, "hashicorp/google").unwrap();
+        assert_eq!(google.version, "4.84.0");
+        assert_eq!(google.constraint, VersionConstraint::Tilde("4.84.0".to_string()));
+    }
+
+    #[test]
+    fn test_unpinned_requirements_are_flagged() {
+        assert_eq!(
+            TerraformParser::new().warnings(&file(MAIN_TF)),
+            vec![
+                "provider hashicorp/random has no version constraint in main.tf",
+                "module terraform-aws-modules/s3-bucket/aws has no version constraint in main.tf",
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_invalid_hcl() {
+        let result = TerraformParser::new().parse(&file("module \"x\" {")).await;
+        assert!(matches!(result, Err(RensaError::Plugin { .. })));
+    }
+}
@@ -0,0 +1,115 @@
+use rensa_core::types::Ecosystem;
+use std::time::Duration;
+
+/// Terraform and OpenTofu modules and providers. OSV has little coverage
+/// here, so the plugin only checks for updates.
+pub struct TerraformPlugin {
+    cache: Option<rensa_core::CacheManager>,
+    registry: Option<rensa_core::config::RegistryConfig>,
+    registry_timeout: Option<Duration>,
+    max_file_size: Option<u64>,
+    concurrency: Option<usize>,
+}
+
+impl Default for TerraformPlugin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TerraformPlugin {
+    pub fn new() -> Self {
+        Self {
+            cache: None,
+            registry: None,
+            registry_timeout: None,
+            max_file_size: None,
+            concurrency: None,
+        }
+    }
+
+    pub fn with_cache(cache: rensa_core::CacheManager) -> Self {
+        Self {
+            cache: Some(cache),
+            ..Self::new()
+        }
+    }
+
+    /// Queries the private registry described by `config` instead of the
+    /// public one.
+    pub fn with_registry_config(mut self, config: rensa_core::config::RegistryConfig) -> Self {
+        self.registry = Some(config);
+        self
+    }
+
+    /// Overrides the HTTP timeout used for registry lookups.
+    pub fn with_registry_timeout(mut self, timeout: Duration) -> Self {
+        self.registry_timeout = Some(timeout);
+        self
+    }
+
+    /// Skips `.tf` files larger than `bytes` during detection.
+    pub fn with_max_file_size(mut self, bytes: u64) -> Self {
+        self.max_file_size = Some(bytes);
+        self
+    }
+
+    /// Sizes connection pools for `concurrency` requests in
+    /// flight at once.
+    pub fn with_concurrency(mut self, concurrency: usize) -> Self {
+        self.concurrency = Some(concurrency);
+        self
+    }
+
+    fn detector(&self) -> super::detector::TerraformDetector {
+        let detector = super::detector::TerraformDetector::new();
+        match self.max_file_size {
+            Some(bytes) => detector.with_max_file_size(bytes),
+            None => detector,
+        }
+    }
+
+    fn registry_client(&self) -> super::registry::TerraformRegistryClient {
+        let mut client = match &self.registry {
+            Some(config) => super::registry::TerraformRegistryClient::new()
+                .with_registry_config(config)
+                .unwrap_or_else(|e| {
+                    eprintln!("Warning: Ignoring the terraform registry config: {}", e);
+                    super::registry::TerraformRegistryClient::new()
+                }),
+            None => super::registry::TerraformRegistryClient::new(),
+        };
+        if let Some(cache) = &self.cache {
+            client = client.with_cache(cache.clone());
+        }
+        if let Some(timeout) = self.registry_timeout {
+            client = client.with_timeout(timeout);
+        }
+        if let Some(concurrency) = self.concurrency {
+            client = client.with_pool_max_idle_per_host(concurrency);
+        }
+        client
+    }
+}
+
+impl rensa_core::Plugin for TerraformPlugin {
+    fn name(&self) -> &'static str {
+        "terraform"
+    }
+
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Terraform
+    }
+
+    fn create_detector(&self) -> Option<Box<dyn rensa_core::Detector>> {
+        Some(Box::new(self.detector()))
+    }
+
+    fn create_parser(&self) -> Option<Box<dyn rensa_core::Parser>> {
+        Some(Box::new(super::parser::TerraformParser::new()))
+    }
+
+    fn create_registry_client(&self) -> Option<Box<dyn rensa_core::RegistryClient>> {
+        Some(Box::new(self.registry_client()))
+    }
+}
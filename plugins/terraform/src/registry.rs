@@ -0,0 +1,197 @@
+use rensa_core::plugin::RegistryClient;
+use rensa_core::types::{Dependency, Ecosystem, LookupTrace, UpdateInfo};
+use rensa_core::{VersionComparator, UpdateType};
+use rensa_core::Result;
+use semver::Version;
+
+/// Looks up module and provider releases through the registry protocol's
+/// `versions` endpoints, which both registry.terraform.io and
+/// registry.opentofu.org serve.
+pub struct TerraformRegistryClient {
+    client: rensa_core::HttpClient,
+    base_url: String,
+}
+
+impl Default for TerraformRegistryClient {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TerraformRegistryClient {
+    pub fn new() -> Self {
+        Self {
+            client: rensa_core::HttpClient::new(),
+            base_url: "https://registry.terraform.io".to_string(),
+        }
+    }
+
+    pub fn with_base_url(mut self, base_url: String) -> Self {
+        self.base_url = base_url;
+        self
+    }
+
+    /// Points the client at a private registry's `url`, if set, and
+    /// applies its TLS settings.
+    pub fn with_registry_config(self, config: &rensa_core::config::RegistryConfig) -> Result<Self> {
+        let mut client = match &config.url {
+            Some(url) => self.with_base_url(url.trim_end_matches('/').to_string()),
+            None => self,
+        };
+        client.client = client.client.with_registry_config(config)?;
+        Ok(client)
+    }
+
+    pub fn with_cache(mut self, cache: rensa_core::CacheManager) -> Self {
+        self.client = rensa_core::HttpClient::with_cache(self.client, cache);
+        self
+    }
+
+    pub fn with_timeout(mut self, timeout: std::time::Duration) -> Self {
+        self.client = self.client.with_timeout(timeout);
+        self
+    }
+
+    pub fn timeout(&self) -> std::time::Duration {
+        self.client.timeout()
+    }
+
+    pub fn with_pool_max_idle_per_host(mut self, max: usize) -> Self {
+        self.client = self.client.with_pool_max_idle_per_host(max);
+        self
+    }
+
+    pub fn pool_max_idle_per_host(&self) -> usize {
+        self.client.pool_max_idle_per_host()
+    }
+
+    fn versions_url(&self, dependency: &Dependency) -> String {
+        // Modules are `namespace/name/provider`, providers `namespace/type`.
+        let kind = match dependency.name.split('/').count() {
+            3 => "modules",
+            _ => "providers",
+        };
+        format!("{}/v1/{}/{}/versions", self.base_url, kind, dependency.name)
+    }
+
+    async fn fetch_versions(&self, dependency: &Dependency) -> Result<Vec<String>> {
+        let data: serde_json::Value = self.client.get(&self.versions_url(dependency)).await?;
+        Ok(stable_versions(&data))
+    }
+}
+
+/// Module and provider addresses: two or three `/`-separated segments of
+/// alphanumerics, `-` and `_`.
+pub fn is_valid_address(name: &str) -> bool {
+    let segments: Vec<&str> = name.split('/').collect();
+    (segments.len() == 2 || segments.len() == 3)
+        && segments
+            .iter()
+            .all(|s| !s.is_empty() && s.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_'))
+}
+
+/// Stable releases, newest first. Module responses nest the list under
+/// `modules[0].versions`; provider responses have it at the top level.
+fn stable_versions(data: &serde_json::Value) -> Vec<String> {
+    let releases = data
+        .get("modules")
+        .and_then(|m| m.get(0))
+        .unwrap_or(data)
+        .get("versions")
+        .and_then(|v| v.as_array());
+
+    let mut versions: Vec<Version> = releases
+        .into_iter()
+        .flatten()
+        .filter_map(|r| r.get("version")?.as_str())
+        .filter_map(|v| Version::parse(v.trim_start_matches('v')).ok())
+        .filter(|v| v.pre.is_empty())
+        .collect();
+    versions.sort_by(|a, b| b.cmp(a));
+    versions.iter().map(Version::to_string).collect()
+}
+
+#[async_trait::async_trait]
+impl RegistryClient for TerraformRegistryClient {
+    fn ecosystem(&self) -> Ecosystem {
+        Ecosystem::Terraform
+    }
+
+    fn is_valid_name(&self, name: &str) -> bool {
+        is_valid_address(name)
+    }
+
+    fn endpoint(&self) -> Option<&str> {
+        Some(&self.base_url)
+    }
+
+    fn trace(&self, dependency: &Dependency) -> Option<LookupTrace> {
+        let url = self.versions_url(dependency);
+        let cached = self.client.is_cached(&url);
+        Some(LookupTrace::new(url).with_cached(cached))
+    }
+
+    async fn get_latest_version(&self, dependency: &Dependency) -> Result<Option<String>> {
+        Ok(self.fetch_versions(dependency).await?.into_iter().next())
+    }
+
+    async fn get_update_info(&self, dependency: &Dependency) -> Result<Option<UpdateInfo>> {
+        let versions = self.fetch_versions(dependency).await?;
+        let latest_version = match versions.first() {
+            Some(v) => v.clone(),
+            None => return Ok(None),
+        };
+
+        match VersionComparator::classify_update(&dependency.version, &latest_version) {
+            UpdateType::None | UpdateType::Unknown => Ok(None),
+            _ => Ok(Some(UpdateInfo {
+                dependency: dependency.clone(),
+                current_version: dependency.version.clone(),
+                latest_in_constraint: VersionComparator::highest_satisfying(&dependency.constraint, &versions),
+                versions_behind: None,
+                age_behind_days: None,
+                intermediate_releases: Vec::new(),
+                latest_version,
+                changelog: None,
+            })),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_address_rules() {
+        assert!(is_valid_address("hashicorp/aws"));
+        assert!(is_valid_address("terraform-aws-modules/vpc/aws"));
+        assert!(!is_valid_address("aws"));
+        assert!(!is_valid_address("hashicorp/aws/../x"));
+        assert!(!is_valid_address("hashicorp/aws?x=1"));
+    }
+
+    #[test]
+    fn test_module_versions_response() {
+        let data = serde_json::json!({
+            "modules": [{
+                "source": "terraform-aws-modules/vpc/aws",
+                "versions": [{ "version": "5.1.2" }, { "version": "5.4.0" }, { "version": "6.0.0-beta1" }]
+            }]
+        });
+        assert_eq!(stable_versions(&data), vec!["5.4.0", "5.1.2"]);
+    }
+
+    #[test]
+    fn test_provider_versions_response() {
+        let data = serde_json::json!({
+            "id": "hashicorp/aws",
+            "versions": [
+                { "version": "4.67.0", "protocols": ["5.0"] },
+                { "version": "5.31.0", "protocols": ["5.0"] }
+            ]
+        });
+        assert_eq!(stable_versions(&data), vec!["5.31.0", "4.67.0"]);
+        assert!(stable_versions(&serde_json::json!({})).is_empty());
+    }
+}